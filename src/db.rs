@@ -6,7 +6,13 @@ use r2d2::{Pool, PooledConnection};
 use r2d2_sqlite::SqliteConnectionManager;
 use rusqlite::{Connection, Result as SqlResult};
 
+pub mod activations;
+pub mod bisect;
+pub mod defs;
 pub mod history;
+pub mod notes;
+pub mod stats;
+pub mod tags;
 
 /// Returns the application's data directory (platform-specific).
 pub fn data_dir() -> PathBuf {
@@ -58,15 +64,29 @@ pub fn get_conn(pool: &DbPool) -> PooledConnection<SqliteConnectionManager> {
     pool.get().expect("Failed to get DB connection from pool")
 }
 
+/// History columns copied from the table created before instances.
+const HISTORY_COLUMNS: &str = "event_id, event_type, timestamp, mod_id, name, version, authors, \
+                               steam_app_id, path, source, created, modified";
+
 /// Run database migrations (create tables if they don't exist).
 /// Extend this function as you add more tables.
 pub fn run_migrations(conn: &Connection) -> SqlResult<()> {
+    let tx = conn.unchecked_transaction()?;
+
+    // history recorded before instances is moved aside and copied into the default instance
+    let history_columns = table_columns(&tx, "history")?;
+    let unpartitioned_history =
+        !history_columns.is_empty() && !history_columns.iter().any(|column| column == "instance");
+    if unpartitioned_history {
+        tx.execute_batch("ALTER TABLE history RENAME TO history_unpartitioned;")?;
+    }
+
     // Example: mod_events table
-    conn.execute_batch(
+    tx.execute_batch(
         r#"
-        DROP TABLE IF EXISTS history;
         CREATE TABLE IF NOT EXISTS history (
             event_id      INTEGER PRIMARY KEY AUTOINCREMENT,
+            instance      TEXT NOT NULL DEFAULT 'default',
             event_type    TEXT NOT NULL,
             timestamp     TEXT NOT NULL,
             mod_id        TEXT NOT NULL,
@@ -79,7 +99,97 @@ pub fn run_migrations(conn: &Connection) -> SqlResult<()> {
             created       TEXT,
             modified      TEXT
         );
+        CREATE TABLE IF NOT EXISTS tags (
+            mod_id        TEXT NOT NULL,
+            tag           TEXT NOT NULL,
+            PRIMARY KEY (mod_id, tag)
+        );
+        CREATE TABLE IF NOT EXISTS notes (
+            mod_id        TEXT PRIMARY KEY,
+            note          TEXT NOT NULL,
+            updated       TEXT NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS activations (
+            instance        TEXT NOT NULL DEFAULT 'default',
+            mod_id          TEXT NOT NULL,
+            first_activated TEXT NOT NULL,
+            last_activated  TEXT NOT NULL,
+            PRIMARY KEY (instance, mod_id)
+        );
+        CREATE TABLE IF NOT EXISTS mod_stats (
+            instance         TEXT NOT NULL DEFAULT 'default',
+            mod_id           TEXT NOT NULL,
+            path             TEXT NOT NULL,
+            modified         TEXT,
+            disk_size        INTEGER NOT NULL,
+            file_count       INTEGER NOT NULL,
+            texture_count    INTEGER NOT NULL,
+            texture_bytes    INTEGER NOT NULL,
+            assembly_count   INTEGER NOT NULL,
+            def_file_count   INTEGER NOT NULL,
+            patch_file_count INTEGER NOT NULL,
+            PRIMARY KEY (instance, mod_id)
+        );
+        CREATE TABLE IF NOT EXISTS def_index (
+            instance      TEXT NOT NULL DEFAULT 'default',
+            mod_id        TEXT NOT NULL,
+            path          TEXT NOT NULL,
+            modified      TEXT,
+            folders       TEXT NOT NULL,
+            PRIMARY KEY (instance, mod_id)
+        );
+        CREATE TABLE IF NOT EXISTS defs (
+            instance      TEXT NOT NULL DEFAULT 'default',
+            mod_id        TEXT NOT NULL,
+            def_type      TEXT NOT NULL,
+            def_name      TEXT NOT NULL,
+            label         TEXT,
+            folder        TEXT NOT NULL,
+            file          TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS defs_mod_id ON defs (instance, mod_id);
+        CREATE VIRTUAL TABLE IF NOT EXISTS defs_fts USING fts5 (
+            def_type, def_name, label, file,
+            content = 'defs', content_rowid = 'rowid', tokenize = 'trigram'
+        );
+        CREATE TRIGGER IF NOT EXISTS defs_fts_insert AFTER INSERT ON defs BEGIN
+            INSERT INTO defs_fts (rowid, def_type, def_name, label, file)
+            VALUES (new.rowid, new.def_type, new.def_name, new.label, new.file);
+        END;
+        CREATE TRIGGER IF NOT EXISTS defs_fts_delete AFTER DELETE ON defs BEGIN
+            INSERT INTO defs_fts (defs_fts, rowid, def_type, def_name, label, file)
+            VALUES ('delete', old.rowid, old.def_type, old.def_name, old.label, old.file);
+        END;
+        CREATE TABLE IF NOT EXISTS bisect_mods (
+            instance      TEXT NOT NULL DEFAULT 'default',
+            position      INTEGER NOT NULL,
+            mod_id        TEXT NOT NULL,
+            role          TEXT NOT NULL,
+            testing       INTEGER NOT NULL,
+            PRIMARY KEY (instance, position)
+        );
+        CREATE TABLE IF NOT EXISTS bisect_steps (
+            instance      TEXT NOT NULL DEFAULT 'default',
+            step          INTEGER NOT NULL,
+            outcome       TEXT NOT NULL,
+            timestamp     TEXT NOT NULL,
+            PRIMARY KEY (instance, step)
+        );
         "#,
     )?;
-    Ok(())
+
+    if unpartitioned_history {
+        tx.execute_batch(&format!(
+            "INSERT INTO history ({HISTORY_COLUMNS}) SELECT {HISTORY_COLUMNS} FROM history_unpartitioned;
+             DROP TABLE history_unpartitioned;"
+        ))?;
+    }
+    tx.commit()
+}
+
+/// Get the column names of a table, which are empty if the table does not exist.
+fn table_columns(conn: &Connection, table: &str) -> SqlResult<Vec<String>> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({table})"))?;
+    stmt.query_map([], |row| row.get::<_, String>("name"))?
+        .collect()
 }
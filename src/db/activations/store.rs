@@ -0,0 +1,83 @@
+use std::{collections::HashSet, sync::Arc};
+
+use anyhow::Context;
+use chrono::Utc;
+use r2d2::{Pool, PooledConnection};
+use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::params;
+
+/// Trait for recording which mods have ever been activated.
+pub trait ActivationStore: Send + Sync {
+    /// Get the ids of all mods that have been activated at least once.
+    fn get_activated_mod_ids(&self) -> anyhow::Result<HashSet<String>>;
+
+    /// Record that the given mods are active now.
+    fn record_activations(&self, mod_ids: &[String]) -> anyhow::Result<()>;
+}
+
+/// SQLite-backed implementation of ActivationStore, partitioned by instance.
+pub struct SqliteActivationStore {
+    pool: Arc<Pool<SqliteConnectionManager>>,
+    instance: String,
+}
+
+impl SqliteActivationStore {
+    pub fn new(pool: Arc<Pool<SqliteConnectionManager>>, instance: &str) -> Self {
+        Self {
+            pool,
+            instance: instance.to_string(),
+        }
+    }
+
+    fn conn(&self) -> anyhow::Result<PooledConnection<SqliteConnectionManager>> {
+        self.pool
+            .get()
+            .with_context(|| "Failed to get SQLite connection from pool")
+    }
+}
+
+impl ActivationStore for SqliteActivationStore {
+    fn get_activated_mod_ids(&self) -> anyhow::Result<HashSet<String>> {
+        let conn = self
+            .conn()
+            .context("Failed to get DB connection for get_activated_mod_ids")?;
+        let mut stmt = conn
+            .prepare(
+                r#"
+            SELECT mod_id FROM activations WHERE instance = ?1
+            "#,
+            )
+            .context("Failed to prepare statement for get_activated_mod_ids")?;
+        let mod_ids = stmt
+            .query_map(params![self.instance], |row| row.get::<_, String>("mod_id"))
+            .context("Failed to query activations")?
+            .collect::<Result<HashSet<_>, _>>()
+            .context("Failed to collect activations")?;
+        Ok(mod_ids)
+    }
+
+    /// Upsert activations in a transaction, keeping the first activation time.
+    fn record_activations(&self, mod_ids: &[String]) -> anyhow::Result<()> {
+        let mut conn = self
+            .conn()
+            .context("Failed to get DB connection for record_activations")?;
+        let tx = conn
+            .transaction()
+            .context("Failed to start transaction for record_activations")?;
+        let now = Utc::now().to_rfc3339();
+        for mod_id in mod_ids {
+            tx.execute(
+                r#"
+                INSERT INTO activations (instance, mod_id, first_activated, last_activated)
+                VALUES (?1, ?2, ?3, ?3)
+                ON CONFLICT(instance, mod_id) DO UPDATE SET last_activated = excluded.last_activated
+                "#,
+                params![self.instance, mod_id, now],
+            )
+            .with_context(|| format!("Failed to record activation for mod_id: {mod_id}"))?;
+        }
+        tx.commit()
+            .context("Failed to commit transaction for record_activations")?;
+        Ok(())
+    }
+}
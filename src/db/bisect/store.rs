@@ -0,0 +1,163 @@
+use std::sync::Arc;
+
+use anyhow::Context;
+use chrono::Utc;
+use r2d2::{Pool, PooledConnection};
+use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::params;
+
+use crate::game::mods::{Bisect, BisectMod, BisectOutcome, BisectRole};
+
+/// Trait for persisting the bisect in progress.
+pub trait BisectStore: Send + Sync {
+    /// Get the bisect in progress, if any.
+    fn get_bisect(&self) -> anyhow::Result<Option<Bisect>>;
+
+    /// Replace the saved bisect.
+    fn save_bisect(&self, bisect: &Bisect) -> anyhow::Result<()>;
+
+    /// Remove the saved bisect.
+    fn clear_bisect(&self) -> anyhow::Result<()>;
+}
+
+/// SQLite-backed implementation of BisectStore, partitioned by instance.
+pub struct SqliteBisectStore {
+    pool: Arc<Pool<SqliteConnectionManager>>,
+    instance: String,
+}
+
+impl SqliteBisectStore {
+    pub fn new(pool: Arc<Pool<SqliteConnectionManager>>, instance: &str) -> Self {
+        Self {
+            pool,
+            instance: instance.to_string(),
+        }
+    }
+
+    fn conn(&self) -> anyhow::Result<PooledConnection<SqliteConnectionManager>> {
+        self.pool
+            .get()
+            .with_context(|| "Failed to get SQLite connection from pool")
+    }
+}
+
+impl BisectStore for SqliteBisectStore {
+    fn get_bisect(&self) -> anyhow::Result<Option<Bisect>> {
+        let conn = self
+            .conn()
+            .context("Failed to get DB connection for get_bisect")?;
+        let mut stmt = conn
+            .prepare(
+                r#"
+            SELECT mod_id, role, testing FROM bisect_mods WHERE instance = ?1 ORDER BY position
+            "#,
+            )
+            .context("Failed to prepare statement for get_bisect")?;
+        let rows = stmt
+            .query_map(params![self.instance], |row| {
+                Ok((
+                    row.get::<_, String>("mod_id")?,
+                    row.get::<_, String>("role")?,
+                    row.get::<_, bool>("testing")?,
+                ))
+            })
+            .context("Failed to query bisect mods")?
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to collect bisect mods")?;
+        if rows.is_empty() {
+            return Ok(None);
+        }
+        let mods = rows
+            .into_iter()
+            .map(|(id, role, testing)| {
+                Ok(BisectMod {
+                    role: BisectRole::try_from(role.as_str())?,
+                    id,
+                    testing,
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let mut stmt = conn
+            .prepare(
+                r#"
+            SELECT outcome FROM bisect_steps WHERE instance = ?1 ORDER BY step
+            "#,
+            )
+            .context("Failed to prepare statement for get_bisect")?;
+        let outcomes = stmt
+            .query_map(params![self.instance], |row| {
+                row.get::<_, String>("outcome")
+            })
+            .context("Failed to query bisect steps")?
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to collect bisect steps")?
+            .iter()
+            .map(|outcome| BisectOutcome::try_from(outcome.as_str()))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(Some(Bisect { mods, outcomes }))
+    }
+
+    /// Replace the saved bisect in a transaction, keeping the timestamps of earlier steps.
+    fn save_bisect(&self, bisect: &Bisect) -> anyhow::Result<()> {
+        let mut conn = self
+            .conn()
+            .context("Failed to get DB connection for save_bisect")?;
+        let tx = conn
+            .transaction()
+            .context("Failed to start transaction for save_bisect")?;
+        tx.execute(
+            r#"
+            DELETE FROM bisect_mods WHERE instance = ?1
+            "#,
+            params![self.instance],
+        )
+        .context("Failed to clear bisect mods")?;
+        for (position, m) in bisect.mods.iter().enumerate() {
+            tx.execute(
+                r#"
+                INSERT INTO bisect_mods (instance, position, mod_id, role, testing)
+                VALUES (?1, ?2, ?3, ?4, ?5)
+                "#,
+                params![self.instance, position as i64, m.id, m.role, m.testing],
+            )
+            .with_context(|| format!("Failed to save bisect mod: {}", m.id))?;
+        }
+        tx.execute(
+            r#"
+            DELETE FROM bisect_steps WHERE instance = ?1 AND step >= ?2
+            "#,
+            params![self.instance, bisect.outcomes.len() as i64],
+        )
+        .context("Failed to clear bisect steps")?;
+        let now = Utc::now().to_rfc3339();
+        for (step, outcome) in bisect.outcomes.iter().enumerate() {
+            tx.execute(
+                r#"
+                INSERT OR IGNORE INTO bisect_steps (instance, step, outcome, timestamp)
+                VALUES (?1, ?2, ?3, ?4)
+                "#,
+                params![self.instance, step as i64, outcome, now],
+            )
+            .with_context(|| format!("Failed to save bisect step: {step}"))?;
+        }
+        tx.commit()
+            .context("Failed to commit transaction for save_bisect")?;
+        Ok(())
+    }
+
+    fn clear_bisect(&self) -> anyhow::Result<()> {
+        let conn = self
+            .conn()
+            .context("Failed to get DB connection for clear_bisect")?;
+        for table in ["bisect_mods", "bisect_steps"] {
+            conn.execute(
+                &format!("DELETE FROM {table} WHERE instance = ?1"),
+                params![self.instance],
+            )
+            .with_context(|| format!("Failed to clear {table}"))?;
+        }
+        Ok(())
+    }
+}
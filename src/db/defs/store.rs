@@ -0,0 +1,306 @@
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
+
+use anyhow::Context;
+use r2d2::{Pool, PooledConnection};
+use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::{Row, params, params_from_iter};
+
+use crate::game::defs::DefEntry;
+
+/// Separator for the content folders stored in a single column.
+const FOLDER_SEPARATOR: &str = "\n";
+
+/// Indexed state of a mod, whose defs are valid while its path, Defs modified time and folders are unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DefIndexRecord {
+    pub mod_id: String,
+    pub path: String,
+    pub modified: Option<String>, // ISO8601 or Unix time
+    /// content folders the defs were read from, relative to the mod directory
+    pub folders: Vec<PathBuf>,
+}
+
+/// A def and the mod (lowercase id) defining it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DefRecord {
+    pub mod_id: String,
+    pub def: DefEntry,
+}
+
+/// Trait for caching the defs of installed mods.
+pub trait DefStore: Send + Sync {
+    /// Get the indexed state of all mods, keyed by mod id.
+    fn get_index(&self) -> anyhow::Result<HashMap<String, DefIndexRecord>>;
+
+    /// Get all cached defs, keyed by mod id.
+    fn get_all_defs(&self) -> anyhow::Result<HashMap<String, Vec<DefEntry>>>;
+
+    /// Replace the cached defs of each mod.
+    fn save_defs(&self, records: &[(DefIndexRecord, Vec<DefEntry>)]) -> anyhow::Result<()>;
+
+    /// Remove the cached defs of mods that are no longer installed in the instance.
+    fn remove_mods(&self, mod_ids: &[String]) -> anyhow::Result<()>;
+
+    /// Search defs by type, defName, label and file, optionally limited to one mod.
+    ///
+    /// Every whitespace separated term must match (case-insensitive).
+    fn search_defs(
+        &self,
+        search: &str,
+        mod_id: Option<&str>,
+        limit: usize,
+    ) -> anyhow::Result<Vec<DefRecord>>;
+}
+
+/// SQLite-backed implementation of DefStore, partitioned by instance.
+pub struct SqliteDefStore {
+    pool: Arc<Pool<SqliteConnectionManager>>,
+    instance: String,
+}
+
+impl SqliteDefStore {
+    pub fn new(pool: Arc<Pool<SqliteConnectionManager>>, instance: &str) -> Self {
+        Self {
+            pool,
+            instance: instance.to_string(),
+        }
+    }
+
+    fn conn(&self) -> anyhow::Result<PooledConnection<SqliteConnectionManager>> {
+        self.pool
+            .get()
+            .with_context(|| "Failed to get SQLite connection from pool")
+    }
+
+    fn row_to_index_record(row: &Row) -> rusqlite::Result<DefIndexRecord> {
+        let folders: String = row.get("folders")?;
+        Ok(DefIndexRecord {
+            mod_id: row.get("mod_id")?,
+            path: row.get("path")?,
+            modified: row.get::<_, Option<String>>("modified")?,
+            folders: folders.split(FOLDER_SEPARATOR).map(PathBuf::from).collect(),
+        })
+    }
+
+    fn row_to_record(row: &Row) -> rusqlite::Result<DefRecord> {
+        Ok(DefRecord {
+            mod_id: row.get("mod_id")?,
+            def: DefEntry {
+                def_type: row.get("def_type")?,
+                def_name: row.get("def_name")?,
+                label: row.get::<_, Option<String>>("label")?,
+                folder: PathBuf::from(row.get::<_, String>("folder")?),
+                file: PathBuf::from(row.get::<_, String>("file")?),
+            },
+        })
+    }
+}
+
+/// Shortest term the trigram search index can match, shorter terms are matched with LIKE.
+const MIN_INDEXED_TERM_LEN: usize = 3;
+
+/// Escape LIKE wildcards so search terms match literally.
+fn like_pattern(term: &str) -> String {
+    let escaped = term
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{escaped}%")
+}
+
+/// Build a full-text query matching every term as a substring, quoting terms so they match
+/// literally.
+fn match_query<'a>(terms: impl IntoIterator<Item = &'a str>) -> String {
+    terms
+        .into_iter()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" AND ")
+}
+
+impl DefStore for SqliteDefStore {
+    fn get_index(&self) -> anyhow::Result<HashMap<String, DefIndexRecord>> {
+        let conn = self
+            .conn()
+            .context("Failed to get DB connection for get_index")?;
+        let mut stmt = conn
+            .prepare(
+                r#"
+            SELECT * FROM def_index WHERE instance = ?1
+            "#,
+            )
+            .context("Failed to prepare statement for get_index")?;
+        let records = stmt
+            .query_map(params![self.instance], Self::row_to_index_record)
+            .context("Failed to query def index")?
+            .map(|record| record.map(|record| (record.mod_id.clone(), record)))
+            .collect::<Result<HashMap<_, _>, _>>()
+            .context("Failed to collect def index")?;
+        Ok(records)
+    }
+
+    fn get_all_defs(&self) -> anyhow::Result<HashMap<String, Vec<DefEntry>>> {
+        let conn = self
+            .conn()
+            .context("Failed to get DB connection for get_all_defs")?;
+        let mut stmt = conn
+            .prepare(
+                r#"
+            SELECT * FROM defs WHERE instance = ?1 ORDER BY rowid
+            "#,
+            )
+            .context("Failed to prepare statement for get_all_defs")?;
+        let rows = stmt
+            .query_map(params![self.instance], Self::row_to_record)
+            .context("Failed to query defs")?
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to collect defs")?;
+
+        let mut defs: HashMap<String, Vec<DefEntry>> = HashMap::new();
+        for record in rows {
+            defs.entry(record.mod_id).or_default().push(record.def);
+        }
+        Ok(defs)
+    }
+
+    /// Bulk replace mod defs in a transaction.
+    fn save_defs(&self, records: &[(DefIndexRecord, Vec<DefEntry>)]) -> anyhow::Result<()> {
+        let mut conn = self
+            .conn()
+            .context("Failed to get DB connection for save_defs")?;
+        let tx = conn
+            .transaction()
+            .context("Failed to start transaction for save_defs")?;
+        for (record, defs) in records {
+            let folders = record
+                .folders
+                .iter()
+                .map(|folder| folder.to_string_lossy())
+                .collect::<Vec<_>>()
+                .join(FOLDER_SEPARATOR);
+            tx.execute(
+                r#"
+                INSERT OR REPLACE INTO def_index (instance, mod_id, path, modified, folders)
+                VALUES (?1, ?2, ?3, ?4, ?5)
+                "#,
+                params![
+                    self.instance,
+                    record.mod_id,
+                    record.path,
+                    record.modified,
+                    folders
+                ],
+            )
+            .with_context(|| format!("Failed to save def index for mod_id: {}", record.mod_id))?;
+            tx.execute(
+                r#"
+                DELETE FROM defs WHERE instance = ?1 AND mod_id = ?2
+                "#,
+                params![self.instance, record.mod_id],
+            )
+            .with_context(|| format!("Failed to clear defs for mod_id: {}", record.mod_id))?;
+            for def in defs {
+                tx.execute(
+                    r#"
+                    INSERT INTO defs (instance, mod_id, def_type, def_name, label, folder, file)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                    "#,
+                    params![
+                        self.instance,
+                        record.mod_id,
+                        def.def_type,
+                        def.def_name,
+                        def.label,
+                        def.folder.to_string_lossy(),
+                        def.file.to_string_lossy(),
+                    ],
+                )
+                .with_context(|| format!("Failed to save defs for mod_id: {}", record.mod_id))?;
+            }
+        }
+        tx.commit()
+            .context("Failed to commit transaction for save_defs")?;
+        Ok(())
+    }
+
+    fn remove_mods(&self, mod_ids: &[String]) -> anyhow::Result<()> {
+        let mut conn = self
+            .conn()
+            .context("Failed to get DB connection for remove_mods")?;
+        let tx = conn
+            .transaction()
+            .context("Failed to start transaction for remove_mods")?;
+        for mod_id in mod_ids {
+            tx.execute(
+                r#"
+                DELETE FROM def_index WHERE instance = ?1 AND mod_id = ?2
+                "#,
+                params![self.instance, mod_id],
+            )
+            .with_context(|| format!("Failed to remove def index for mod_id: {mod_id}"))?;
+            tx.execute(
+                r#"
+                DELETE FROM defs WHERE instance = ?1 AND mod_id = ?2
+                "#,
+                params![self.instance, mod_id],
+            )
+            .with_context(|| format!("Failed to remove defs for mod_id: {mod_id}"))?;
+        }
+        tx.commit()
+            .context("Failed to commit transaction for remove_mods")?;
+        Ok(())
+    }
+
+    fn search_defs(
+        &self,
+        search: &str,
+        mod_id: Option<&str>,
+        limit: usize,
+    ) -> anyhow::Result<Vec<DefRecord>> {
+        let conn = self
+            .conn()
+            .context("Failed to get DB connection for search_defs")?;
+
+        let mut values = vec![self.instance.clone()];
+        let mut conditions = vec!["instance = ?1".to_string()];
+        if let Some(mod_id) = mod_id {
+            values.push(mod_id.to_ascii_lowercase());
+            conditions.push(format!("mod_id = ?{}", values.len()));
+        }
+        let (indexed, short): (Vec<&str>, Vec<&str>) = search
+            .split_whitespace()
+            .partition(|term| term.chars().count() >= MIN_INDEXED_TERM_LEN);
+        if !indexed.is_empty() {
+            values.push(match_query(indexed));
+            conditions.push(format!(
+                "rowid IN (SELECT rowid FROM defs_fts WHERE defs_fts MATCH ?{})",
+                values.len()
+            ));
+        }
+        for term in short {
+            values.push(like_pattern(term));
+            let n = values.len();
+            conditions.push(format!(
+                "(def_type LIKE ?{n} ESCAPE '\\' OR def_name LIKE ?{n} ESCAPE '\\' \
+                 OR label LIKE ?{n} ESCAPE '\\' OR file LIKE ?{n} ESCAPE '\\')"
+            ));
+        }
+        let where_clause = conditions.join(" AND ");
+
+        let mut stmt = conn
+            .prepare(&format!(
+                r#"
+            SELECT * FROM defs WHERE {where_clause}
+            ORDER BY def_type, def_name, mod_id
+            LIMIT {limit}
+            "#
+            ))
+            .context("Failed to prepare statement for search_defs")?;
+        let records = stmt
+            .query_map(params_from_iter(values), Self::row_to_record)
+            .context("Failed to search defs")?
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to collect defs")?;
+        Ok(records)
+    }
+}
@@ -24,14 +24,18 @@ pub trait HistoryStore: Send + Sync {
     fn get_all_events(&self) -> anyhow::Result<Vec<Event>>;
 }
 
-/// SQLite-backed implementation of HistoryStore.
+/// SQLite-backed implementation of HistoryStore, partitioned by instance.
 pub struct SqliteHistoryStore {
     pool: Arc<Pool<SqliteConnectionManager>>,
+    instance: String,
 }
 
 impl SqliteHistoryStore {
-    pub fn new(pool: Arc<Pool<SqliteConnectionManager>>) -> Self {
-        Self { pool }
+    pub fn new(pool: Arc<Pool<SqliteConnectionManager>>, instance: &str) -> Self {
+        Self {
+            pool,
+            instance: instance.to_string(),
+        }
     }
 
     fn conn(&self) -> anyhow::Result<PooledConnection<SqliteConnectionManager>> {
@@ -89,6 +93,7 @@ impl HistoryStore for SqliteHistoryStore {
             tx.execute(
                 r#"
                     INSERT INTO history (
+                        instance,
                         event_type,
                         timestamp,
                         mod_id,
@@ -100,9 +105,10 @@ impl HistoryStore for SqliteHistoryStore {
                         source,
                         created,
                         modified
-                    ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+                    ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
                     "#,
                 params![
+                    self.instance,
                     event.event_type,
                     event.timestamp,
                     event.mod_id,
@@ -131,7 +137,7 @@ impl HistoryStore for SqliteHistoryStore {
             .prepare(
                 r#"
             SELECT * FROM history
-            WHERE mod_id = ?1
+            WHERE instance = ?1 AND mod_id = ?2
             ORDER BY timestamp ASC
             "#,
             )
@@ -139,7 +145,7 @@ impl HistoryStore for SqliteHistoryStore {
                 format!("Failed to prepare statement for get_mod_history, mod_id: {mod_id}")
             })?;
         let events = stmt
-            .query_map(params![mod_id], Self::row_to_event)
+            .query_map(params![self.instance, mod_id], Self::row_to_event)
             .with_context(|| format!("Failed to query events for mod_id: {mod_id}"))?
             .collect::<Result<Vec<_>, _>>()
             .with_context(|| format!("Failed to collect events for mod_id: {mod_id}"))?;
@@ -159,14 +165,16 @@ impl HistoryStore for SqliteHistoryStore {
             INNER JOIN (
                 SELECT mod_id, MAX(timestamp) as max_ts
                 FROM history
+                WHERE instance = ?1
                 GROUP BY mod_id
             ) latest
             ON e.mod_id = latest.mod_id AND e.timestamp = latest.max_ts
+            WHERE e.instance = ?1
             "#,
             )
             .context("Failed to prepare statement for get_latest_events")?;
         let events = stmt
-            .query_map([], Self::row_to_event)
+            .query_map(params![self.instance], Self::row_to_event)
             .context("Failed to query latest events")?
             .collect::<Result<Vec<_>, _>>()
             .context("Failed to collect latest events")?;
@@ -181,12 +189,13 @@ impl HistoryStore for SqliteHistoryStore {
             .prepare(
                 r#"
             SELECT * FROM history
+            WHERE instance = ?1
             ORDER BY timestamp ASC
             "#,
             )
             .context("Failed to prepare statement for get_all_events")?;
         let events = stmt
-            .query_map([], Self::row_to_event)
+            .query_map(params![self.instance], Self::row_to_event)
             .context("Failed to query all events")?
             .collect::<Result<Vec<_>, _>>()
             .context("Failed to collect all events")?;
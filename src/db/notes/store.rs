@@ -0,0 +1,84 @@
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::Context;
+use chrono::Utc;
+use r2d2::{Pool, PooledConnection};
+use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::params;
+
+/// Trait for storing and retrieving personal mod notes.
+pub trait NoteStore: Send + Sync {
+    /// Get all notes, keyed by mod id.
+    fn get_all_notes(&self) -> anyhow::Result<HashMap<String, String>>;
+
+    /// Set the note for a mod, removing it if the note is empty.
+    fn set_note(&self, mod_id: &str, note: &str) -> anyhow::Result<()>;
+}
+
+/// SQLite-backed implementation of NoteStore.
+pub struct SqliteNoteStore {
+    pool: Arc<Pool<SqliteConnectionManager>>,
+}
+
+impl SqliteNoteStore {
+    pub fn new(pool: Arc<Pool<SqliteConnectionManager>>) -> Self {
+        Self { pool }
+    }
+
+    fn conn(&self) -> anyhow::Result<PooledConnection<SqliteConnectionManager>> {
+        self.pool
+            .get()
+            .with_context(|| "Failed to get SQLite connection from pool")
+    }
+}
+
+impl NoteStore for SqliteNoteStore {
+    fn get_all_notes(&self) -> anyhow::Result<HashMap<String, String>> {
+        let conn = self
+            .conn()
+            .context("Failed to get DB connection for get_all_notes")?;
+        let mut stmt = conn
+            .prepare(
+                r#"
+            SELECT mod_id, note FROM notes
+            "#,
+            )
+            .context("Failed to prepare statement for get_all_notes")?;
+        let notes = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>("mod_id")?,
+                    row.get::<_, String>("note")?,
+                ))
+            })
+            .context("Failed to query notes")?
+            .collect::<Result<HashMap<_, _>, _>>()
+            .context("Failed to collect notes")?;
+        Ok(notes)
+    }
+
+    fn set_note(&self, mod_id: &str, note: &str) -> anyhow::Result<()> {
+        let conn = self
+            .conn()
+            .context("Failed to get DB connection for set_note")?;
+        if note.trim().is_empty() {
+            conn.execute(
+                r#"
+                DELETE FROM notes WHERE mod_id = ?1
+                "#,
+                params![mod_id],
+            )
+            .with_context(|| format!("Failed to remove note for mod_id: {mod_id}"))?;
+        } else {
+            conn.execute(
+                r#"
+                INSERT INTO notes (mod_id, note, updated) VALUES (?1, ?2, ?3)
+                ON CONFLICT(mod_id) DO UPDATE SET note = excluded.note, updated = excluded.updated
+                "#,
+                params![mod_id, note, Utc::now().to_rfc3339()],
+            )
+            .with_context(|| format!("Failed to set note for mod_id: {mod_id}"))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::Context;
+use r2d2::{Pool, PooledConnection};
+use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::{Row, params};
+
+use crate::game::mods::ModStats;
+
+/// Cached statistics for a mod, valid while its path and latest modified time are unchanged.
+#[derive(Debug, Clone)]
+pub struct StatsRecord {
+    pub mod_id: String,
+    pub path: String,
+    pub modified: Option<String>, // ISO8601 or Unix time
+    pub stats: ModStats,
+}
+
+/// Trait for caching mod statistics.
+pub trait StatsStore: Send + Sync {
+    /// Get all cached statistics, keyed by mod id.
+    fn get_all_stats(&self) -> anyhow::Result<HashMap<String, StatsRecord>>;
+
+    /// Insert or replace cached statistics.
+    fn save_stats(&self, records: &[StatsRecord]) -> anyhow::Result<()>;
+}
+
+/// SQLite-backed implementation of StatsStore, partitioned by instance.
+pub struct SqliteStatsStore {
+    pool: Arc<Pool<SqliteConnectionManager>>,
+    instance: String,
+}
+
+impl SqliteStatsStore {
+    pub fn new(pool: Arc<Pool<SqliteConnectionManager>>, instance: &str) -> Self {
+        Self {
+            pool,
+            instance: instance.to_string(),
+        }
+    }
+
+    fn conn(&self) -> anyhow::Result<PooledConnection<SqliteConnectionManager>> {
+        self.pool
+            .get()
+            .with_context(|| "Failed to get SQLite connection from pool")
+    }
+
+    fn row_to_record(row: &Row) -> rusqlite::Result<StatsRecord> {
+        let count = |name: &str| row.get::<_, i64>(name).map(|value| value as u64);
+        Ok(StatsRecord {
+            mod_id: row.get("mod_id")?,
+            path: row.get("path")?,
+            modified: row.get::<_, Option<String>>("modified")?,
+            stats: ModStats {
+                disk_size: count("disk_size")?,
+                file_count: count("file_count")?,
+                texture_count: count("texture_count")?,
+                texture_bytes: count("texture_bytes")?,
+                assembly_count: count("assembly_count")?,
+                def_file_count: count("def_file_count")?,
+                patch_file_count: count("patch_file_count")?,
+            },
+        })
+    }
+}
+
+impl StatsStore for SqliteStatsStore {
+    fn get_all_stats(&self) -> anyhow::Result<HashMap<String, StatsRecord>> {
+        let conn = self
+            .conn()
+            .context("Failed to get DB connection for get_all_stats")?;
+        let mut stmt = conn
+            .prepare(
+                r#"
+            SELECT * FROM mod_stats WHERE instance = ?1
+            "#,
+            )
+            .context("Failed to prepare statement for get_all_stats")?;
+        let records = stmt
+            .query_map(params![self.instance], Self::row_to_record)
+            .context("Failed to query mod stats")?
+            .map(|record| record.map(|record| (record.mod_id.clone(), record)))
+            .collect::<Result<HashMap<_, _>, _>>()
+            .context("Failed to collect mod stats")?;
+        Ok(records)
+    }
+
+    /// Bulk upsert mod stats in a transaction.
+    fn save_stats(&self, records: &[StatsRecord]) -> anyhow::Result<()> {
+        let mut conn = self
+            .conn()
+            .context("Failed to get DB connection for save_stats")?;
+        let tx = conn
+            .transaction()
+            .context("Failed to start transaction for save_stats")?;
+        for record in records {
+            let stats = &record.stats;
+            tx.execute(
+                r#"
+                INSERT OR REPLACE INTO mod_stats (
+                    instance,
+                    mod_id,
+                    path,
+                    modified,
+                    disk_size,
+                    file_count,
+                    texture_count,
+                    texture_bytes,
+                    assembly_count,
+                    def_file_count,
+                    patch_file_count
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+                "#,
+                params![
+                    self.instance,
+                    record.mod_id,
+                    record.path,
+                    record.modified,
+                    stats.disk_size as i64,
+                    stats.file_count as i64,
+                    stats.texture_count as i64,
+                    stats.texture_bytes as i64,
+                    stats.assembly_count as i64,
+                    stats.def_file_count as i64,
+                    stats.patch_file_count as i64,
+                ],
+            )
+            .with_context(|| format!("Failed to save stats for mod_id: {}", record.mod_id))?;
+        }
+        tx.commit()
+            .context("Failed to commit transaction for save_stats")?;
+        Ok(())
+    }
+}
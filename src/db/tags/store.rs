@@ -0,0 +1,105 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::Arc,
+};
+
+use anyhow::Context;
+use r2d2::{Pool, PooledConnection};
+use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::params;
+
+/// Trait for storing and retrieving user-defined mod tags.
+pub trait TagStore: Send + Sync {
+    /// Get all tags, keyed by mod id.
+    fn get_all_tags(&self) -> anyhow::Result<HashMap<String, BTreeSet<String>>>;
+
+    /// Add a tag to each of the given mods.
+    fn add_tag(&self, mod_ids: &[String], tag: &str) -> anyhow::Result<()>;
+
+    /// Remove a tag from a mod.
+    fn remove_tag(&self, mod_id: &str, tag: &str) -> anyhow::Result<()>;
+}
+
+/// SQLite-backed implementation of TagStore.
+pub struct SqliteTagStore {
+    pool: Arc<Pool<SqliteConnectionManager>>,
+}
+
+impl SqliteTagStore {
+    pub fn new(pool: Arc<Pool<SqliteConnectionManager>>) -> Self {
+        Self { pool }
+    }
+
+    fn conn(&self) -> anyhow::Result<PooledConnection<SqliteConnectionManager>> {
+        self.pool
+            .get()
+            .with_context(|| "Failed to get SQLite connection from pool")
+    }
+}
+
+impl TagStore for SqliteTagStore {
+    fn get_all_tags(&self) -> anyhow::Result<HashMap<String, BTreeSet<String>>> {
+        let conn = self
+            .conn()
+            .context("Failed to get DB connection for get_all_tags")?;
+        let mut stmt = conn
+            .prepare(
+                r#"
+            SELECT mod_id, tag FROM tags
+            "#,
+            )
+            .context("Failed to prepare statement for get_all_tags")?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>("mod_id")?,
+                    row.get::<_, String>("tag")?,
+                ))
+            })
+            .context("Failed to query tags")?
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to collect tags")?;
+
+        let mut tags: HashMap<String, BTreeSet<String>> = HashMap::new();
+        for (mod_id, tag) in rows {
+            tags.entry(mod_id).or_default().insert(tag);
+        }
+        Ok(tags)
+    }
+
+    /// Bulk insert the tag in a transaction, ignoring mods that already have it.
+    fn add_tag(&self, mod_ids: &[String], tag: &str) -> anyhow::Result<()> {
+        let mut conn = self
+            .conn()
+            .context("Failed to get DB connection for add_tag")?;
+        let tx = conn
+            .transaction()
+            .context("Failed to start transaction for add_tag")?;
+        for mod_id in mod_ids {
+            tx.execute(
+                r#"
+                    INSERT OR IGNORE INTO tags (mod_id, tag) VALUES (?1, ?2)
+                    "#,
+                params![mod_id, tag],
+            )
+            .with_context(|| format!("Failed to add tag {tag} to mod_id: {mod_id}"))?;
+        }
+        tx.commit()
+            .context("Failed to commit transaction for add_tag")?;
+        Ok(())
+    }
+
+    fn remove_tag(&self, mod_id: &str, tag: &str) -> anyhow::Result<()> {
+        let conn = self
+            .conn()
+            .context("Failed to get DB connection for remove_tag")?;
+        conn.execute(
+            r#"
+            DELETE FROM tags WHERE mod_id = ?1 AND tag = ?2
+            "#,
+            params![mod_id, tag],
+        )
+        .with_context(|| format!("Failed to remove tag {tag} from mod_id: {mod_id}"))?;
+        Ok(())
+    }
+}
@@ -1,3 +1,10 @@
+pub mod assembly;
+pub mod defs;
+pub mod launch;
 pub mod mods;
+pub mod patches;
 pub mod paths;
+pub mod player_log;
+pub mod vdf;
+pub mod version;
 pub mod xml;
@@ -0,0 +1,621 @@
+//! Reader for .NET assembly identities, using the PE/CLI metadata headers (ECMA-335 §II.24-25).
+
+use std::{fmt::Display, fs, path::Path};
+
+use crate::game::xml::ParseResult;
+
+const CLI_HEADER_DIRECTORY: usize = 14;
+const METADATA_SIGNATURE: u32 = 0x424a_5342;
+const ASSEMBLY_TABLE: usize = 0x20;
+const TABLE_COUNT: usize = 64;
+
+/// Version of an assembly, e.g. `2.3.1.0`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AssemblyVersion {
+    pub major: u16,
+    pub minor: u16,
+    pub build: u16,
+    pub revision: u16,
+}
+
+impl Display for AssemblyVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}.{}",
+            self.major, self.minor, self.build, self.revision
+        )
+    }
+}
+
+/// Identity of an assembly, read from its Assembly metadata table.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct AssemblyName {
+    pub name: String,
+    pub version: AssemblyVersion,
+}
+
+impl AssemblyName {
+    /// Read the assembly identity from a DLL file.
+    pub fn read(path: &Path) -> ParseResult<Self> {
+        let data = fs::read(path).map_err(|e| format!("reading assembly {path:?}: {e}"))?;
+        Self::parse(&data).map_err(|e| format!("parsing assembly {path:?}: {e}"))
+    }
+
+    /// Parse the assembly identity from the bytes of a PE image.
+    pub fn parse(data: &[u8]) -> ParseResult<Self> {
+        let image = PeImage::parse(data)?;
+        let cli_rva = image.data_directory(CLI_HEADER_DIRECTORY)?;
+        let cli_header = image.offset(cli_rva)?;
+        let metadata_rva = read_u32(data, cli_header + 8)?;
+        let metadata = image.offset(metadata_rva)?;
+        Metadata::parse(data, metadata)?.assembly_name()
+    }
+}
+
+/// Just enough of a PE image to map relative virtual addresses to file offsets.
+struct PeImage<'a> {
+    data: &'a [u8],
+    data_directories: usize,
+    data_directory_count: usize,
+    sections: Vec<Section>,
+}
+
+struct Section {
+    virtual_address: u32,
+    virtual_size: u32,
+    raw_size: u32,
+    raw_offset: u32,
+}
+
+impl<'a> PeImage<'a> {
+    fn parse(data: &'a [u8]) -> ParseResult<Self> {
+        if data.get(..2) != Some(b"MZ") {
+            return Err("missing DOS header".into());
+        }
+        let pe = read_u32(data, 0x3c)? as usize;
+        if data.get(pe..pe + 4) != Some(b"PE\0\0") {
+            return Err("missing PE signature".into());
+        }
+
+        let coff = pe + 4;
+        let section_count = read_u16(data, coff + 2)? as usize;
+        let optional_size = read_u16(data, coff + 16)? as usize;
+        let optional = coff + 20;
+        let (data_directories, count_offset) = match read_u16(data, optional)? {
+            0x10b => (optional + 96, optional + 92),
+            0x20b => (optional + 112, optional + 108),
+            magic => return Err(format!("unknown optional header magic {magic:#x}")),
+        };
+        let data_directory_count = read_u32(data, count_offset)? as usize;
+
+        let section_table = optional + optional_size;
+        let sections = (0..section_count)
+            .map(|i| {
+                let header = section_table + i * 40;
+                Ok(Section {
+                    virtual_size: read_u32(data, header + 8)?,
+                    virtual_address: read_u32(data, header + 12)?,
+                    raw_size: read_u32(data, header + 16)?,
+                    raw_offset: read_u32(data, header + 20)?,
+                })
+            })
+            .collect::<ParseResult<_>>()?;
+
+        Ok(Self {
+            data,
+            data_directories,
+            data_directory_count,
+            sections,
+        })
+    }
+
+    /// Get the address of a data directory, failing if it is absent.
+    fn data_directory(&self, index: usize) -> ParseResult<u32> {
+        if index >= self.data_directory_count {
+            return Err("not a .NET assembly".into());
+        }
+        let rva = read_u32(self.data, self.data_directories + index * 8)?;
+        if rva == 0 {
+            return Err("not a .NET assembly".into());
+        }
+        Ok(rva)
+    }
+
+    /// Map a relative virtual address to a file offset.
+    fn offset(&self, rva: u32) -> ParseResult<usize> {
+        let section = self
+            .sections
+            .iter()
+            .find(|s| {
+                let size = s.virtual_size.max(s.raw_size);
+                rva >= s.virtual_address && rva - s.virtual_address < size
+            })
+            .ok_or_else(|| format!("address {rva:#x} is outside all sections"))?;
+        (rva - section.virtual_address)
+            .checked_add(section.raw_offset)
+            .map(|offset| offset as usize)
+            .ok_or_else(|| format!("address {rva:#x} maps past the end of the file"))
+    }
+}
+
+/// The metadata root with the table and string heap locations resolved.
+struct Metadata<'a> {
+    data: &'a [u8],
+    strings: usize,
+    tables: usize,
+    heap_sizes: u8,
+    rows: [u32; TABLE_COUNT],
+}
+
+impl<'a> Metadata<'a> {
+    fn parse(data: &'a [u8], root: usize) -> ParseResult<Self> {
+        if read_u32(data, root)? != METADATA_SIGNATURE {
+            return Err("invalid metadata signature".into());
+        }
+        let version_len = read_u32(data, root + 12)? as usize;
+        let stream_count = read_u16(data, root + 16 + version_len + 2)?;
+
+        let mut strings = None;
+        let mut tables = None;
+        let mut header = root + 16 + version_len + 4;
+        for _ in 0..stream_count {
+            let offset = root + read_u32(data, header)? as usize;
+            let name_start = header + 8;
+            let name_len = data
+                .get(name_start..)
+                .and_then(|rest| rest.iter().position(|&b| b == 0))
+                .ok_or("unterminated stream name")?;
+            match &data[name_start..name_start + name_len] {
+                b"#Strings" => strings = Some(offset),
+                b"#~" | b"#-" => tables = Some(offset),
+                _ => {}
+            }
+            // names are null terminated and padded to four bytes
+            header = name_start + (name_len + 4) / 4 * 4;
+        }
+        let strings = strings.ok_or("missing #Strings stream")?;
+        let tables = tables.ok_or("missing tables stream")?;
+
+        let heap_sizes = *data.get(tables + 6).ok_or("truncated tables stream")?;
+        let valid = read_u64(data, tables + 8)?;
+        let mut rows = [0; TABLE_COUNT];
+        let mut row_counts = tables + 24;
+        for (table, count) in rows.iter_mut().enumerate() {
+            if valid & (1 << table) != 0 {
+                *count = read_u32(data, row_counts)?;
+                row_counts += 4;
+            }
+        }
+        // an extra four bytes follow the row counts in some uncompressed streams
+        if heap_sizes & 0x40 != 0 {
+            row_counts += 4;
+        }
+
+        Ok(Self {
+            data,
+            strings,
+            tables: row_counts,
+            heap_sizes,
+            rows,
+        })
+    }
+
+    fn assembly_name(&self) -> ParseResult<AssemblyName> {
+        if self.rows[ASSEMBLY_TABLE] == 0 {
+            return Err("missing assembly table".into());
+        }
+        let row = self.tables
+            + (0..ASSEMBLY_TABLE)
+                .map(|table| self.rows[table] as usize * self.row_size(table))
+                .sum::<usize>();
+
+        // HashAlgId, then the four version parts, Flags and PublicKey
+        let version = AssemblyVersion {
+            major: read_u16(self.data, row + 4)?,
+            minor: read_u16(self.data, row + 6)?,
+            build: read_u16(self.data, row + 8)?,
+            revision: read_u16(self.data, row + 10)?,
+        };
+        let name_index = row + 16 + self.blob_size();
+        let name = match self.string_size() {
+            4 => read_u32(self.data, name_index)?,
+            _ => read_u16(self.data, name_index)? as u32,
+        };
+
+        Ok(AssemblyName {
+            name: self.string(name as usize)?,
+            version,
+        })
+    }
+
+    fn string(&self, index: usize) -> ParseResult<String> {
+        let start = self.strings + index;
+        let bytes = self.data.get(start..).ok_or("string index out of range")?;
+        let len = bytes
+            .iter()
+            .position(|&b| b == 0)
+            .ok_or("unterminated string")?;
+        Ok(String::from_utf8_lossy(&bytes[..len]).into_owned())
+    }
+
+    fn string_size(&self) -> usize {
+        if self.heap_sizes & 0x01 != 0 { 4 } else { 2 }
+    }
+
+    fn guid_size(&self) -> usize {
+        if self.heap_sizes & 0x02 != 0 { 4 } else { 2 }
+    }
+
+    fn blob_size(&self) -> usize {
+        if self.heap_sizes & 0x04 != 0 { 4 } else { 2 }
+    }
+
+    fn index_size(&self, table: usize) -> usize {
+        if self.rows[table] < 1 << 16 { 2 } else { 4 }
+    }
+
+    fn coded_index_size(&self, coded: CodedIndex) -> usize {
+        let (tag_bits, tables) = coded.tables();
+        let max_rows = tables.iter().map(|&t| self.rows[t]).max().unwrap_or(0);
+        if max_rows < 1 << (16 - tag_bits) {
+            2
+        } else {
+            4
+        }
+    }
+
+    /// Get the size of a row in one of the tables preceding the Assembly table.
+    fn row_size(&self, table: usize) -> usize {
+        use CodedIndex::*;
+        use Column::*;
+
+        let columns: &[Column] = match table {
+            // Module
+            0x00 => &[Fixed(2), Str, Guid, Guid, Guid],
+            // TypeRef
+            0x01 => &[Coded(ResolutionScope), Str, Str],
+            // TypeDef
+            0x02 => &[
+                Fixed(4),
+                Str,
+                Str,
+                Coded(TypeDefOrRef),
+                Table(0x04),
+                Table(0x06),
+            ],
+            // FieldPtr
+            0x03 => &[Table(0x04)],
+            // Field
+            0x04 => &[Fixed(2), Str, Blob],
+            // MethodPtr
+            0x05 => &[Table(0x06)],
+            // MethodDef
+            0x06 => &[Fixed(4), Fixed(2), Fixed(2), Str, Blob, Table(0x08)],
+            // ParamPtr
+            0x07 => &[Table(0x08)],
+            // Param
+            0x08 => &[Fixed(2), Fixed(2), Str],
+            // InterfaceImpl
+            0x09 => &[Table(0x02), Coded(TypeDefOrRef)],
+            // MemberRef
+            0x0a => &[Coded(MemberRefParent), Str, Blob],
+            // Constant
+            0x0b => &[Fixed(2), Coded(HasConstant), Blob],
+            // CustomAttribute
+            0x0c => &[Coded(HasCustomAttribute), Coded(CustomAttributeType), Blob],
+            // FieldMarshal
+            0x0d => &[Coded(HasFieldMarshal), Blob],
+            // DeclSecurity
+            0x0e => &[Fixed(2), Coded(HasDeclSecurity), Blob],
+            // ClassLayout
+            0x0f => &[Fixed(2), Fixed(4), Table(0x02)],
+            // FieldLayout
+            0x10 => &[Fixed(4), Table(0x04)],
+            // StandAloneSig
+            0x11 => &[Blob],
+            // EventMap
+            0x12 => &[Table(0x02), Table(0x14)],
+            // EventPtr
+            0x13 => &[Table(0x14)],
+            // Event
+            0x14 => &[Fixed(2), Str, Coded(TypeDefOrRef)],
+            // PropertyMap
+            0x15 => &[Table(0x02), Table(0x17)],
+            // PropertyPtr
+            0x16 => &[Table(0x17)],
+            // Property
+            0x17 => &[Fixed(2), Str, Blob],
+            // MethodSemantics
+            0x18 => &[Fixed(2), Table(0x06), Coded(HasSemantics)],
+            // MethodImpl
+            0x19 => &[Table(0x02), Coded(MethodDefOrRef), Coded(MethodDefOrRef)],
+            // ModuleRef
+            0x1a => &[Str],
+            // TypeSpec
+            0x1b => &[Blob],
+            // ImplMap
+            0x1c => &[Fixed(2), Coded(MemberForwarded), Str, Table(0x1a)],
+            // FieldRVA
+            0x1d => &[Fixed(4), Table(0x04)],
+            // ENCLog
+            0x1e => &[Fixed(4), Fixed(4)],
+            // ENCMap
+            0x1f => &[Fixed(4)],
+            _ => unreachable!("row size of table {table:#x} is not needed"),
+        };
+
+        columns
+            .iter()
+            .map(|column| match *column {
+                Fixed(size) => size,
+                Str => self.string_size(),
+                Guid => self.guid_size(),
+                Blob => self.blob_size(),
+                Table(table) => self.index_size(table),
+                Coded(coded) => self.coded_index_size(coded),
+            })
+            .sum()
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Column {
+    Fixed(usize),
+    Str,
+    Guid,
+    Blob,
+    Table(usize),
+    Coded(CodedIndex),
+}
+
+#[derive(Clone, Copy)]
+enum CodedIndex {
+    TypeDefOrRef,
+    HasConstant,
+    HasCustomAttribute,
+    HasFieldMarshal,
+    HasDeclSecurity,
+    MemberRefParent,
+    HasSemantics,
+    MethodDefOrRef,
+    MemberForwarded,
+    CustomAttributeType,
+    ResolutionScope,
+}
+
+impl CodedIndex {
+    /// Get the number of tag bits and the tables this index can refer to.
+    fn tables(self) -> (u32, &'static [usize]) {
+        match self {
+            CodedIndex::TypeDefOrRef => (2, &[0x02, 0x01, 0x1b]),
+            CodedIndex::HasConstant => (2, &[0x04, 0x08, 0x17]),
+            CodedIndex::HasCustomAttribute => (
+                5,
+                &[
+                    0x06, 0x04, 0x01, 0x02, 0x08, 0x09, 0x0a, 0x00, 0x0e, 0x17, 0x14, 0x11, 0x1a,
+                    0x1b, 0x20, 0x23, 0x26, 0x27, 0x28, 0x2a, 0x2c, 0x2b,
+                ],
+            ),
+            CodedIndex::HasFieldMarshal => (1, &[0x04, 0x08]),
+            CodedIndex::HasDeclSecurity => (2, &[0x02, 0x06, 0x20]),
+            CodedIndex::MemberRefParent => (3, &[0x02, 0x01, 0x1a, 0x06, 0x1b]),
+            CodedIndex::HasSemantics => (1, &[0x14, 0x17]),
+            CodedIndex::MethodDefOrRef => (1, &[0x06, 0x0a]),
+            CodedIndex::MemberForwarded => (1, &[0x04, 0x06]),
+            CodedIndex::CustomAttributeType => (3, &[0x06, 0x0a]),
+            CodedIndex::ResolutionScope => (2, &[0x00, 0x1a, 0x23, 0x01]),
+        }
+    }
+}
+
+fn read_u16(data: &[u8], offset: usize) -> ParseResult<u16> {
+    data.get(offset..offset + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .ok_or_else(|| format!("unexpected end of file at {offset:#x}"))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> ParseResult<u32> {
+    data.get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| format!("unexpected end of file at {offset:#x}"))
+}
+
+fn read_u64(data: &[u8], offset: usize) -> ParseResult<u64> {
+    data.get(offset..offset + 8)
+        .map(|b| u64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]))
+        .ok_or_else(|| format!("unexpected end of file at {offset:#x}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECTION_RVA: u32 = 0x2000;
+    const SECTION_OFFSET: usize = 0x200;
+    const SECTION_TABLE: usize = 0x138;
+    const CLI_HEADER_SIZE: usize = 72;
+
+    fn put(data: &mut [u8], offset: usize, bytes: &[u8]) {
+        data[offset..offset + bytes.len()].copy_from_slice(bytes);
+    }
+
+    fn pad(data: &mut Vec<u8>) {
+        data.resize(data.len().next_multiple_of(4), 0);
+    }
+
+    /// Build a PE32 image with a single section holding the CLI header and metadata.
+    ///
+    /// `tables` lists the tables before the Assembly table as (table, row count, row size), in
+    /// table order, and their rows are filled with junk.
+    fn build_image(heap_sizes: u8, tables: &[(usize, u32, usize)], name: &str) -> Vec<u8> {
+        let index = |value: u32, flag: u8| match heap_sizes & flag {
+            0 => (value as u16).to_le_bytes().to_vec(),
+            _ => value.to_le_bytes().to_vec(),
+        };
+
+        // tables stream: header, row counts, rows of the preceding tables, then the Assembly row
+        let mut stream = vec![0, 0, 0, 0, 2, 0, heap_sizes, 1];
+        let valid = tables
+            .iter()
+            .fold(1u64 << ASSEMBLY_TABLE, |valid, (table, _, _)| {
+                valid | 1 << table
+            });
+        stream.extend(valid.to_le_bytes());
+        stream.extend(0u64.to_le_bytes());
+        for (_, rows, _) in tables {
+            stream.extend(rows.to_le_bytes());
+        }
+        stream.extend(1u32.to_le_bytes());
+        if heap_sizes & 0x40 != 0 {
+            stream.extend([0; 4]);
+        }
+        for (_, rows, size) in tables {
+            stream.extend(vec![0xaa; *rows as usize * size]);
+        }
+        stream.extend(0x8004u32.to_le_bytes());
+        for part in [1u16, 2, 3, 4] {
+            stream.extend(part.to_le_bytes());
+        }
+        stream.extend(0u32.to_le_bytes());
+        stream.extend(index(0, 0x04));
+        stream.extend(index(1, 0x01));
+        stream.extend(index(0, 0x01));
+        pad(&mut stream);
+
+        let mut strings = vec![0];
+        strings.extend(name.as_bytes());
+        strings.push(0);
+        pad(&mut strings);
+
+        // metadata root with the #~ and #Strings stream headers
+        let mut metadata = METADATA_SIGNATURE.to_le_bytes().to_vec();
+        metadata.extend([1, 0, 1, 0, 0, 0, 0, 0]);
+        metadata.extend(12u32.to_le_bytes());
+        metadata.extend(b"v4.0.30319\0\0");
+        metadata.extend([0, 0]);
+        metadata.extend(2u16.to_le_bytes());
+        let tables_offset = metadata.len() + 12 + 20;
+        let strings_offset = tables_offset + stream.len();
+        metadata.extend((tables_offset as u32).to_le_bytes());
+        metadata.extend((stream.len() as u32).to_le_bytes());
+        metadata.extend(b"#~\0\0");
+        metadata.extend((strings_offset as u32).to_le_bytes());
+        metadata.extend((strings.len() as u32).to_le_bytes());
+        metadata.extend(b"#Strings\0\0\0\0");
+        metadata.extend(stream);
+        metadata.extend(strings);
+
+        let mut section = vec![0; CLI_HEADER_SIZE];
+        put(&mut section, 0, &(CLI_HEADER_SIZE as u32).to_le_bytes());
+        put(
+            &mut section,
+            8,
+            &(SECTION_RVA + CLI_HEADER_SIZE as u32).to_le_bytes(),
+        );
+        put(&mut section, 12, &(metadata.len() as u32).to_le_bytes());
+        section.extend(metadata);
+
+        // DOS header, PE signature, COFF header, PE32 optional header and the section table
+        let mut data = vec![0; SECTION_OFFSET];
+        put(&mut data, 0, b"MZ");
+        put(&mut data, 0x3c, &0x40u32.to_le_bytes());
+        put(&mut data, 0x40, b"PE\0\0");
+        put(&mut data, 0x46, &1u16.to_le_bytes());
+        put(&mut data, 0x54, &224u16.to_le_bytes());
+        put(&mut data, 0x58, &0x10bu16.to_le_bytes());
+        put(&mut data, 0x58 + 92, &16u32.to_le_bytes());
+        let cli_directory = 0x58 + 96 + CLI_HEADER_DIRECTORY * 8;
+        put(&mut data, cli_directory, &SECTION_RVA.to_le_bytes());
+        put(
+            &mut data,
+            cli_directory + 4,
+            &(CLI_HEADER_SIZE as u32).to_le_bytes(),
+        );
+        let section_size = (section.len() as u32).to_le_bytes();
+        put(&mut data, SECTION_TABLE + 8, &section_size);
+        put(&mut data, SECTION_TABLE + 12, &SECTION_RVA.to_le_bytes());
+        put(&mut data, SECTION_TABLE + 16, &section_size);
+        put(
+            &mut data,
+            SECTION_TABLE + 20,
+            &(SECTION_OFFSET as u32).to_le_bytes(),
+        );
+        data.extend(section);
+        data
+    }
+
+    fn version(major: u16, minor: u16, build: u16, revision: u16) -> AssemblyVersion {
+        AssemblyVersion {
+            major,
+            minor,
+            build,
+            revision,
+        }
+    }
+
+    #[test]
+    fn test_parse_assembly_name() {
+        let data = build_image(0, &[], "HugsLib");
+        assert_eq!(
+            AssemblyName::parse(&data).unwrap(),
+            AssemblyName {
+                name: "HugsLib".into(),
+                version: version(1, 2, 3, 4),
+            }
+        );
+        assert_eq!(version(1, 2, 3, 4).to_string(), "1.2.3.4");
+    }
+
+    #[test]
+    fn test_parse_small_heaps() {
+        // Module: flags, name and three guids, TypeRef: resolution scope and two names,
+        // TypeDef: flags, two names, extends, field and method lists
+        let tables = [(0x00, 1, 10), (0x01, 5, 6), (0x02, 3, 14)];
+        let data = build_image(0, &tables, "0Harmony");
+        let assembly = AssemblyName::parse(&data).unwrap();
+        assert_eq!(assembly.name, "0Harmony");
+        assert_eq!(assembly.version, version(1, 2, 3, 4));
+    }
+
+    #[test]
+    fn test_parse_large_heaps() {
+        // four byte string, guid and blob indices, plus the extra data after the row counts
+        let tables = [(0x00, 1, 18), (0x02, 2, 18), (0x04, 4, 10)];
+        let data = build_image(0x01 | 0x02 | 0x04 | 0x40, &tables, "Mod");
+        let assembly = AssemblyName::parse(&data).unwrap();
+        assert_eq!(assembly.name, "Mod");
+        assert_eq!(assembly.version, version(1, 2, 3, 4));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            AssemblyName::parse(b"not a dll").unwrap_err(),
+            "missing DOS header"
+        );
+
+        let data = build_image(0, &[], "Mod");
+        assert!(AssemblyName::parse(&data[..SECTION_OFFSET + 100]).is_err());
+
+        let mut native = data.clone();
+        put(&mut native, 0x58 + 96 + CLI_HEADER_DIRECTORY * 8, &[0; 4]);
+        assert_eq!(
+            AssemblyName::parse(&native).unwrap_err(),
+            "not a .NET assembly"
+        );
+    }
+
+    #[test]
+    fn test_offset_overflow() {
+        let mut data = build_image(0, &[], "Mod");
+        put(&mut data, SECTION_TABLE + 20, &(u32::MAX - 8).to_le_bytes());
+        let image = PeImage::parse(&data).unwrap();
+        assert_eq!(
+            image.offset(SECTION_RVA + 4).unwrap(),
+            u32::MAX as usize - 4
+        );
+        assert!(image.offset(SECTION_RVA + 16).is_err());
+        assert!(image.offset(SECTION_RVA - 1).is_err());
+    }
+}
@@ -0,0 +1,385 @@
+//! Indexer for the Defs a mod adds, read from the `Defs` folder of each content folder.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs::File,
+    io::{BufReader, Read},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use xml::reader::{EventReader, XmlEvent as ReaderEvent};
+
+use crate::game::{mods::latest_modified, xml::*};
+
+const DEFS_DIR: &str = "Defs";
+
+/// A Def declared in a mod's XML files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DefEntry {
+    /// element name of the def, e.g. `ThingDef`
+    pub def_type: String,
+    pub def_name: String,
+    pub label: Option<String>,
+    /// content folder the def is in, relative to the mod directory
+    pub folder: PathBuf,
+    /// file the def is in, relative to the content folder
+    pub file: PathBuf,
+}
+
+/// Read the Defs in each content folder of a mod.
+///
+/// Abstract defs and defs without a defName are skipped, as they cannot collide.
+pub fn load_defs(mod_dir: &Path, folders: &[PathBuf]) -> Vec<DefEntry> {
+    let mut defs = Vec::new();
+    for folder in folders {
+        let content_dir = mod_dir.join(folder);
+        let files = find_xml_files(&content_dir.join(DEFS_DIR));
+
+        for path in files {
+            let file = path
+                .strip_prefix(&content_dir)
+                .unwrap_or(&path)
+                .to_path_buf();
+            match load_defs_file(&path) {
+                Ok(file_defs) => defs.extend(file_defs.into_iter().map(
+                    |(def_type, def_name, label)| DefEntry {
+                        def_type,
+                        def_name,
+                        label,
+                        folder: folder.clone(),
+                        file: file.clone(),
+                    },
+                )),
+                Err(e) => log::warn!("skipping defs file: {e}"),
+            }
+        }
+    }
+    defs
+}
+
+/// Get the latest modified time of anything in the `Defs` folder of each content folder.
+pub fn defs_modified(mod_dir: &Path, folders: &[PathBuf]) -> Option<SystemTime> {
+    folders
+        .iter()
+        .filter_map(|folder| latest_modified(&mod_dir.join(folder).join(DEFS_DIR)))
+        .max()
+}
+
+/// Get the defs the game loads from the given content folders, highest priority first.
+///
+/// When the same file exists in several folders, only the highest priority copy is loaded.
+pub fn effective_defs<'a>(defs: &'a [DefEntry], folders: &[PathBuf]) -> Vec<&'a DefEntry> {
+    let mut seen_files = HashSet::new();
+    let mut effective = Vec::new();
+    for folder in folders {
+        let mut folder_files = HashSet::new();
+        for def in defs.iter().filter(|def| &def.folder == folder) {
+            let file = def.file.to_string_lossy().to_ascii_lowercase();
+            if seen_files.contains(&file) {
+                continue;
+            }
+            folder_files.insert(file);
+            effective.push(def);
+        }
+        seen_files.extend(folder_files);
+    }
+    effective
+}
+
+type DefTuple = (String, String, Option<String>);
+
+fn load_defs_file(path: &Path) -> ParseResult<Vec<DefTuple>> {
+    let file = File::open(path).map_err(|e| format!("opening file {path:?}: {e}"))?;
+    let events = create_reader(BufReader::new(file));
+    parse_defs(events, path)
+}
+
+fn parse_defs<R: Read>(mut events: EventReader<R>, path: &Path) -> ParseResult<Vec<DefTuple>> {
+    let mut defs = Vec::new();
+    loop {
+        match events.next() {
+            Ok(ReaderEvent::EndDocument) => break,
+            Ok(ReaderEvent::StartElement { name, .. })
+                if name.local_name.eq_ignore_ascii_case("Defs") =>
+            {
+                parse_defs_container(&mut events, path, &mut defs)?;
+            }
+            Ok(ReaderEvent::StartElement { .. }) => {
+                log::trace!("unexpected root element in {path:?}");
+                skip_element(&mut events)?;
+            }
+            Ok(_) => {}
+            Err(e) => return Err(format!("error parsing defs from {path:?}: {e}")),
+        }
+    }
+    Ok(defs)
+}
+
+fn parse_defs_container<R: Read>(
+    events: &mut EventReader<R>,
+    path: &Path,
+    defs: &mut Vec<DefTuple>,
+) -> ParseResult<()> {
+    loop {
+        match events.next() {
+            Ok(ReaderEvent::StartElement {
+                name, attributes, ..
+            }) => {
+                let is_abstract = attributes.iter().any(|attribute| {
+                    attribute.name.local_name.eq_ignore_ascii_case("Abstract")
+                        && attribute.value.trim().eq_ignore_ascii_case("true")
+                });
+                let (def_name, label) = parse_def(events, path, &name.local_name)?;
+                if let Some(def_name) = def_name.filter(|_| !is_abstract) {
+                    defs.push((name.local_name, def_name, label));
+                }
+            }
+            Ok(ReaderEvent::EndElement { .. }) => break,
+            Ok(_) => {}
+            Err(e) => return Err(format!("error parsing defs from {path:?}: {e}")),
+        }
+    }
+    Ok(())
+}
+
+/// Parses a single def, returning its defName and label if present.
+fn parse_def<R: Read>(
+    events: &mut EventReader<R>,
+    path: &Path,
+    def_type: &str,
+) -> ParseResult<(Option<String>, Option<String>)> {
+    let mut def_name = None;
+    let mut label = None;
+    loop {
+        match events.next() {
+            Ok(ReaderEvent::StartElement { name, .. })
+                if name.local_name.eq_ignore_ascii_case("defName") =>
+            {
+                let text = parse_text_element(events, path, &name.local_name)?;
+                def_name = Some(text.trim().to_string()).filter(|s| !s.is_empty());
+            }
+            Ok(ReaderEvent::StartElement { name, .. })
+                if name.local_name.eq_ignore_ascii_case("label") =>
+            {
+                let text = parse_text_element(events, path, &name.local_name)?;
+                label = Some(text.trim().to_string()).filter(|s| !s.is_empty());
+            }
+            Ok(ReaderEvent::StartElement { .. }) => skip_element(events)?,
+            Ok(ReaderEvent::EndElement { .. }) => break,
+            Ok(_) => {}
+            Err(e) => return Err(format!("error parsing {def_type} from {path:?}: {e}")),
+        }
+    }
+    Ok((def_name, label))
+}
+
+/// A mod defining a conflicting def.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DefSource {
+    pub mod_id: String,
+    pub mod_name: String,
+    /// file the def is in, relative to the mod directory
+    pub file: PathBuf,
+}
+
+/// A def defined by more than one active mod.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DefConflict {
+    pub def_type: String,
+    pub def_name: String,
+    /// mods defining the def, in load order
+    pub sources: Vec<DefSource>,
+}
+
+impl DefConflict {
+    /// Get the mod whose def is used, which is the last one loaded.
+    pub fn winner(&self) -> &DefSource {
+        self.sources
+            .last()
+            .expect("conflicts have at least two sources")
+    }
+
+    /// Check if a mod defines this def (case-insensitive).
+    pub fn involves(&self, mod_id: &str) -> bool {
+        self.sources
+            .iter()
+            .any(|source| source.mod_id.eq_ignore_ascii_case(mod_id))
+    }
+}
+
+/// Find defs with the same type and defName in more than one mod.
+///
+/// Mods must be given in load order, each with its effective defs.
+pub fn find_def_conflicts<'a, I>(mods: I) -> Vec<DefConflict>
+where
+    I: IntoIterator<Item = (&'a str, &'a str, Vec<&'a DefEntry>)>,
+{
+    let mut sources: HashMap<(&str, &str), Vec<DefSource>> = HashMap::new();
+    for (mod_id, mod_name, defs) in mods {
+        for def in defs {
+            let def_sources = sources
+                .entry((def.def_type.as_str(), def.def_name.as_str()))
+                .or_default();
+            // duplicates within one mod are not conflicts between mods
+            if def_sources.last().is_some_and(|s| s.mod_id == mod_id) {
+                continue;
+            }
+            def_sources.push(DefSource {
+                mod_id: mod_id.to_string(),
+                mod_name: mod_name.to_string(),
+                file: def.folder.join(&def.file),
+            });
+        }
+    }
+
+    let mut conflicts: Vec<DefConflict> = sources
+        .into_iter()
+        .filter(|(_, sources)| sources.len() > 1)
+        .map(|((def_type, def_name), sources)| DefConflict {
+            def_type: def_type.to_string(),
+            def_name: def_name.to_string(),
+            sources,
+        })
+        .collect();
+    conflicts.sort_by(|a, b| (&a.def_type, &a.def_name).cmp(&(&b.def_type, &b.def_name)));
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(xml: &str) -> Vec<DefTuple> {
+        parse_defs(create_reader(xml.as_bytes()), Path::new("Defs.xml")).unwrap()
+    }
+
+    fn def(def_type: &str, def_name: &str, folder: &str, file: &str) -> DefEntry {
+        DefEntry {
+            def_type: def_type.into(),
+            def_name: def_name.into(),
+            label: None,
+            folder: PathBuf::from(folder),
+            file: PathBuf::from(file),
+        }
+    }
+
+    fn names(defs: &[&DefEntry]) -> Vec<String> {
+        defs.iter()
+            .map(|def| format!("{}:{}", def.folder.display(), def.def_name))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_defs() {
+        let defs = parse(
+            r#"
+            <Defs>
+              <ThingDef Name="BaseGun" Abstract="True">
+                <defName>Abstract_Gun</defName>
+              </ThingDef>
+              <ThingDef ParentName="BaseGun">
+                <defName>Gun_Revolver</defName>
+                <label>revolver</label>
+                <comps><li><label>ignored</label></li></comps>
+              </ThingDef>
+              <ThingDef ParentName="BaseGun">
+                <label>nameless</label>
+              </ThingDef>
+              <RecipeDef Abstract="false">
+                <defName> Make_Revolver </defName>
+              </RecipeDef>
+            </Defs>
+            "#,
+        );
+        assert_eq!(
+            defs,
+            vec![
+                (
+                    "ThingDef".to_string(),
+                    "Gun_Revolver".to_string(),
+                    Some("revolver".to_string())
+                ),
+                ("RecipeDef".to_string(), "Make_Revolver".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_defs_error() {
+        let result = parse_defs(
+            create_reader("<Defs><ThingDef>".as_bytes()),
+            Path::new("Defs.xml"),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_effective_defs() {
+        let defs = vec![
+            def("ThingDef", "Root_A", "", "Defs/Things.xml"),
+            def("ThingDef", "Root_B", "", "Defs/Other.xml"),
+            def("ThingDef", "Version_A", "1.5", "Defs/things.xml"),
+            def("ThingDef", "Common_C", "Common", "Defs/Common.xml"),
+        ];
+        let folders = [
+            PathBuf::from("1.5"),
+            PathBuf::from("Common"),
+            PathBuf::new(),
+        ];
+        // the version folder overrides the root's copy of the same file
+        assert_eq!(
+            names(&effective_defs(&defs, &folders)),
+            vec!["1.5:Version_A", "Common:Common_C", ":Root_B"]
+        );
+        // folders that are not loaded are left out
+        assert_eq!(
+            names(&effective_defs(&defs, &[PathBuf::new()])),
+            vec![":Root_A", ":Root_B"]
+        );
+    }
+
+    #[test]
+    fn test_find_def_conflicts() {
+        let core = [
+            def("ThingDef", "Steel", "", "Defs/Items.xml"),
+            def("ThingDef", "Gun_Revolver", "", "Defs/Weapons.xml"),
+        ];
+        let patch = [
+            def("ThingDef", "Gun_Revolver", "1.5", "Defs/Weapons.xml"),
+            // duplicates within a mod are not conflicts
+            def("ThingDef", "Gun_Revolver", "1.5", "Defs/More.xml"),
+            def("RecipeDef", "Steel", "1.5", "Defs/Recipes.xml"),
+        ];
+        let other = [def("ThingDef", "Gun_Revolver", "", "Defs/Guns.xml")];
+        let conflicts = find_def_conflicts([
+            ("ludeon.rimworld", "Core", core.iter().collect()),
+            ("a.patch", "Patch", patch.iter().collect()),
+            ("b.other", "Other", other.iter().collect()),
+        ]);
+
+        assert_eq!(conflicts.len(), 1);
+        let conflict = &conflicts[0];
+        assert_eq!(
+            (conflict.def_type.as_str(), conflict.def_name.as_str()),
+            ("ThingDef", "Gun_Revolver")
+        );
+        let sources: Vec<(&str, PathBuf)> = conflict
+            .sources
+            .iter()
+            .map(|source| (source.mod_id.as_str(), source.file.clone()))
+            .collect();
+        assert_eq!(
+            sources,
+            vec![
+                ("ludeon.rimworld", PathBuf::from("Defs/Weapons.xml")),
+                ("a.patch", PathBuf::from("1.5/Defs/Weapons.xml")),
+                ("b.other", PathBuf::from("Defs/Guns.xml")),
+            ]
+        );
+        // the last mod in load order wins
+        assert_eq!(conflict.winner().mod_id, "b.other");
+        assert!(conflict.involves("A.Patch"));
+        assert!(!conflict.involves("c.unrelated"));
+    }
+}
@@ -0,0 +1,169 @@
+//! Launching the game and tracking its process.
+
+use std::{
+    path::Path,
+    process::{Child, Command, ExitStatus},
+};
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+
+/// Steam app id of the game.
+pub const STEAM_APP_ID: &str = "294100";
+
+/// Argument setting the folder the game reads its config and saves from.
+pub const SAVE_DATA_FOLDER_ARG: &str = "-savedatafolder=";
+
+/// A running game process started by rimru.
+#[derive(Debug)]
+pub struct GameProcess {
+    child: Child,
+    pub started: DateTime<Utc>,
+}
+
+impl GameProcess {
+    /// Start the game executable with arguments, from the directory it is in.
+    pub fn spawn(executable: &Path, args: &[String]) -> anyhow::Result<Self> {
+        let mut command = Command::new(executable);
+        command.args(args);
+        if let Some(dir) = executable.parent().filter(|dir| dir.is_dir()) {
+            command.current_dir(dir);
+        }
+        let child = command
+            .spawn()
+            .with_context(|| format!("Failed to start game executable {executable:?}"))?;
+        Ok(Self {
+            child,
+            started: Utc::now(),
+        })
+    }
+
+    pub fn pid(&self) -> u32 {
+        self.child.id()
+    }
+
+    /// Check if the process exited, without waiting for it.
+    pub fn try_wait(&mut self) -> anyhow::Result<Option<ExitStatus>> {
+        self.child
+            .try_wait()
+            .with_context(|| format!("Failed to check game process {}", self.pid()))
+    }
+}
+
+/// Get the Steam URL launching the game with arguments.
+///
+/// Steam splits the arguments again like a command line, so arguments containing whitespace
+/// are quoted, and the argument string is percent-encoded to stay within its URL segment.
+pub fn steam_launch_url(args: &[String]) -> String {
+    let args = args
+        .iter()
+        .map(|arg| match arg.contains(char::is_whitespace) {
+            true => format!("\"{arg}\""),
+            false => arg.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!("steam://run/{STEAM_APP_ID}//{}/", percent_encode(&args))
+}
+
+/// Percent-encode every byte of a string except unreserved URL characters.
+fn percent_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            byte => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+/// Split user-configured launch arguments on whitespace, keeping double-quoted text together.
+pub fn split_args(args: &str) -> Vec<String> {
+    let mut result = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut has_arg = false;
+    for c in args.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                has_arg = true;
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if has_arg {
+                    result.push(std::mem::take(&mut current));
+                    has_arg = false;
+                }
+            }
+            c => {
+                current.push(c);
+                has_arg = true;
+            }
+        }
+    }
+    if has_arg {
+        result.push(current);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use super::*;
+
+    #[test]
+    fn test_split_args() {
+        assert!(split_args("").is_empty());
+        assert!(split_args("   ").is_empty());
+        assert_eq!(
+            split_args("-popupwindow  -logfile /tmp/rimworld.log"),
+            vec!["-popupwindow", "-logfile", "/tmp/rimworld.log"]
+        );
+        assert_eq!(
+            split_args(r#"-savedatafolder="C:\My Saves" -popupwindow"#),
+            vec![r"-savedatafolder=C:\My Saves", "-popupwindow"]
+        );
+        assert_eq!(split_args(r#"-logfile """#), vec!["-logfile", ""]);
+    }
+
+    #[test]
+    fn test_steam_launch_url() {
+        assert_eq!(steam_launch_url(&[]), "steam://run/294100///");
+        assert_eq!(
+            steam_launch_url(&["-popupwindow".to_string(), "-logfile".to_string()]),
+            "steam://run/294100//-popupwindow%20-logfile/"
+        );
+        assert_eq!(
+            steam_launch_url(&split_args(
+                r#"-savedatafolder="C:/My Saves/100%" -logfile"#
+            )),
+            "steam://run/294100//%22-savedatafolder%3DC%3A%2FMy%20Saves%2F100%25%22%20-logfile/"
+        );
+    }
+
+    #[test]
+    fn test_spawn_missing_executable() {
+        let result = GameProcess::spawn(Path::new("/nonexistent/RimWorld"), &[]);
+        assert!(result.is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_spawn_stand_in_executable() {
+        let args = vec!["-c".to_string(), "exit 3".to_string()];
+        let mut process = GameProcess::spawn(Path::new("/bin/sh"), &args).unwrap();
+
+        let status = loop {
+            if let Some(status) = process.try_wait().unwrap() {
+                break status;
+            }
+            thread::sleep(Duration::from_millis(10));
+        };
+        assert_eq!(status.code(), Some(3));
+    }
+}
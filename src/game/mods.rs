@@ -1,9 +1,35 @@
+mod assemblies;
+mod bisect;
 mod config;
+mod expansions;
 mod issues;
+mod languages;
+mod load_folders;
 mod meta;
+mod mod_settings;
 mod sort;
+mod stats;
+mod textures;
+mod workshop;
 
+pub use assemblies::*;
+pub use bisect::*;
 pub use config::*;
+pub use expansions::*;
 pub use issues::*;
+pub use languages::*;
+pub use load_folders::*;
 pub use meta::*;
+pub use mod_settings::*;
 pub use sort::*;
+pub use stats::*;
+pub use textures::*;
+pub use workshop::*;
+
+/// writes a file (and its parent folders) below `dir` for tests
+#[cfg(test)]
+fn write_test_file(dir: &std::path::Path, relative_path: &str, contents: impl AsRef<[u8]>) {
+    let path = dir.join(relative_path);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, contents).unwrap();
+}
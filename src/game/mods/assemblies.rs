@@ -0,0 +1,78 @@
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::game::assembly::{AssemblyName, AssemblyVersion};
+
+const ASSEMBLIES_DIR: &str = "Assemblies";
+
+/// A .NET assembly shipped by a mod.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModAssembly {
+    /// content folder the assembly is in, relative to the mod directory
+    pub folder: PathBuf,
+    /// file name of the assembly
+    pub file_name: String,
+    pub name: String,
+    pub version: AssemblyVersion,
+}
+
+/// Read the assemblies in the `Assemblies` folder of each content folder of a mod.
+///
+/// Native libraries without CLI metadata are skipped.
+pub fn load_assemblies(mod_dir: &Path, folders: &[PathBuf]) -> Vec<ModAssembly> {
+    let mut assemblies = Vec::new();
+
+    for folder in folders {
+        let assemblies_dir = mod_dir.join(folder).join(ASSEMBLIES_DIR);
+        let Ok(entries) = fs::read_dir(&assemblies_dir) else {
+            continue;
+        };
+
+        let mut paths: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.is_file()
+                    && path
+                        .extension()
+                        .is_some_and(|ext| ext.eq_ignore_ascii_case("dll"))
+            })
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            match AssemblyName::read(&path) {
+                Ok(assembly) => assemblies.push(ModAssembly {
+                    folder: folder.clone(),
+                    file_name: file_name.to_string(),
+                    name: assembly.name,
+                    version: assembly.version,
+                }),
+                Err(e) => log::debug!("skipping assembly: {e}"),
+            }
+        }
+    }
+
+    assemblies
+}
+
+/// Get the assemblies the game loads from the given content folders, highest priority first.
+///
+/// When the same file exists in several folders, only the highest priority copy is loaded.
+pub fn effective_assemblies<'a>(
+    assemblies: &'a [ModAssembly],
+    folders: &[PathBuf],
+) -> Vec<&'a ModAssembly> {
+    let mut seen = HashSet::new();
+    folders
+        .iter()
+        .flat_map(|folder| assemblies.iter().filter(move |a| &a.folder == folder))
+        .filter(|a| seen.insert(a.file_name.to_ascii_lowercase()))
+        .collect()
+}
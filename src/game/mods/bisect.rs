@@ -0,0 +1,384 @@
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+    fmt::Display,
+};
+
+use anyhow::bail;
+use rusqlite::{
+    ToSql,
+    types::{ToSqlOutput, Value},
+};
+
+pub const PINNED_ROLE: &str = "pinned";
+pub const SUSPECT_ROLE: &str = "suspect";
+pub const CLEARED_ROLE: &str = "cleared";
+
+pub const GOOD_OUTCOME: &str = "good";
+pub const BAD_OUTCOME: &str = "bad";
+
+/// Role of a mod in a bisect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BisectRole {
+    /// always active, e.g. Core and DLCs
+    Pinned,
+    /// may still be causing the problem
+    Suspect,
+    /// ruled out by an earlier step
+    Cleared,
+}
+
+impl AsRef<str> for BisectRole {
+    fn as_ref(&self) -> &str {
+        match self {
+            BisectRole::Pinned => PINNED_ROLE,
+            BisectRole::Suspect => SUSPECT_ROLE,
+            BisectRole::Cleared => CLEARED_ROLE,
+        }
+    }
+}
+
+impl Display for BisectRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+impl ToSql for BisectRole {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::Owned(Value::Text(self.as_ref().to_owned())))
+    }
+}
+
+impl TryFrom<&str> for BisectRole {
+    type Error = anyhow::Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            PINNED_ROLE => Ok(BisectRole::Pinned),
+            SUSPECT_ROLE => Ok(BisectRole::Suspect),
+            CLEARED_ROLE => Ok(BisectRole::Cleared),
+            _ => bail!("Unknown bisect role: {}", s),
+        }
+    }
+}
+
+/// Result of testing a bisect step in the game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BisectOutcome {
+    /// the problem did not happen
+    Good,
+    /// the problem happened
+    Bad,
+}
+
+impl AsRef<str> for BisectOutcome {
+    fn as_ref(&self) -> &str {
+        match self {
+            BisectOutcome::Good => GOOD_OUTCOME,
+            BisectOutcome::Bad => BAD_OUTCOME,
+        }
+    }
+}
+
+impl Display for BisectOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+impl ToSql for BisectOutcome {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::Owned(Value::Text(self.as_ref().to_owned())))
+    }
+}
+
+impl TryFrom<&str> for BisectOutcome {
+    type Error = anyhow::Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            GOOD_OUTCOME => Ok(BisectOutcome::Good),
+            BAD_OUTCOME => Ok(BisectOutcome::Bad),
+            _ => bail!("Unknown bisect outcome: {}", s),
+        }
+    }
+}
+
+/// A mod that was active when the bisect started.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BisectMod {
+    pub id: String,
+    pub role: BisectRole,
+    /// active in the current step
+    pub testing: bool,
+}
+
+/// A guided binary search for the mod causing a problem.
+///
+/// Each step activates the pinned mods and part of the suspects, with the dependencies
+/// of those suspects, and the outcome reported for the step rules out either the tested
+/// suspects or the others. The bisect is finished when the suspects cannot be split.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Bisect {
+    /// mods active when the bisect started, in load order
+    pub mods: Vec<BisectMod>,
+    /// outcomes reported for each step so far
+    pub outcomes: Vec<BisectOutcome>,
+}
+
+impl Bisect {
+    /// Start a bisect of the active mods, where the problem happens with all of them.
+    ///
+    /// `pinned` and `dependencies` are keyed by lowercase mod id.
+    pub fn start(
+        active_ids: &[String],
+        pinned: &HashSet<String>,
+        dependencies: &HashMap<String, Vec<String>>,
+    ) -> Self {
+        let mods = active_ids
+            .iter()
+            .map(|id| BisectMod {
+                id: id.clone(),
+                role: match pinned.contains(&id.to_ascii_lowercase()) {
+                    true => BisectRole::Pinned,
+                    false => BisectRole::Suspect,
+                },
+                testing: false,
+            })
+            .collect();
+        let mut bisect = Self {
+            mods,
+            outcomes: Vec::new(),
+        };
+        bisect.plan_step(dependencies);
+        bisect
+    }
+
+    /// Get the number of the current step, starting at 1.
+    pub fn step(&self) -> usize {
+        self.outcomes.len() + 1
+    }
+
+    /// Get the ids of the mods that may still be causing the problem, in load order.
+    pub fn suspects(&self) -> Vec<&str> {
+        self.mods
+            .iter()
+            .filter(|m| m.role == BisectRole::Suspect)
+            .map(|m| m.id.as_str())
+            .collect()
+    }
+
+    /// Get the ids of the suspects active in the current step, in load order.
+    pub fn testing_suspects(&self) -> Vec<&str> {
+        self.mods
+            .iter()
+            .filter(|m| m.role == BisectRole::Suspect && m.testing)
+            .map(|m| m.id.as_str())
+            .collect()
+    }
+
+    /// Check if the suspects cannot be split any further.
+    pub fn is_finished(&self) -> bool {
+        !self.mods.iter().any(|m| m.testing)
+    }
+
+    /// Get the ids of the mods to activate for the current step, in load order.
+    pub fn active_ids(&self) -> Vec<String> {
+        self.mods
+            .iter()
+            .filter(|m| m.role == BisectRole::Pinned || m.testing)
+            .map(|m| m.id.clone())
+            .collect()
+    }
+
+    /// Get the ids of the mods active when the bisect started, in load order.
+    pub fn original_ids(&self) -> Vec<String> {
+        self.mods.iter().map(|m| m.id.clone()).collect()
+    }
+
+    /// Rule out suspects from the outcome of the current step, then plan the next step.
+    pub fn report(&mut self, outcome: BisectOutcome, dependencies: &HashMap<String, Vec<String>>) {
+        if self.is_finished() {
+            return;
+        }
+        for m in self.mods.iter_mut() {
+            let cleared = match outcome {
+                BisectOutcome::Good => m.testing,
+                BisectOutcome::Bad => !m.testing,
+            };
+            if m.role == BisectRole::Suspect && cleared {
+                m.role = BisectRole::Cleared;
+            }
+        }
+        self.outcomes.push(outcome);
+        self.plan_step(dependencies);
+    }
+
+    /// Choose the mods tested in the next step, which must leave out at least one suspect.
+    fn plan_step(&mut self, dependencies: &HashMap<String, Vec<String>>) {
+        for m in self.mods.iter_mut() {
+            m.testing = false;
+        }
+
+        let suspects: Vec<String> = self
+            .suspects()
+            .iter()
+            .map(|id| id.to_ascii_lowercase())
+            .collect();
+        if suspects.len() < 2 {
+            return;
+        }
+
+        let (first, second) = suspects.split_at(suspects.len() / 2);
+        for half in [first, second] {
+            let testing = self.with_dependencies(half, dependencies);
+            let tested_suspects = suspects.iter().filter(|id| testing.contains(*id)).count();
+            if tested_suspects < suspects.len() {
+                for m in self.mods.iter_mut() {
+                    m.testing = m.role != BisectRole::Pinned
+                        && testing.contains(&m.id.to_ascii_lowercase());
+                }
+                return;
+            }
+        }
+    }
+
+    /// Get the lowercase ids of the given mods and the bisected mods they depend on.
+    fn with_dependencies(
+        &self,
+        mod_ids: &[String],
+        dependencies: &HashMap<String, Vec<String>>,
+    ) -> HashSet<String> {
+        let known: HashSet<String> = self
+            .mods
+            .iter()
+            .map(|m| m.id.to_ascii_lowercase())
+            .collect();
+        let mut result: HashSet<String> = HashSet::new();
+        let mut queue: Vec<String> = mod_ids.to_vec();
+        while let Some(mod_id) = queue.pop() {
+            if !result.insert(mod_id.clone()) {
+                continue;
+            }
+            for dep_id in dependencies.get(&mod_id).into_iter().flatten() {
+                let dep_id = dep_id.to_ascii_lowercase();
+                if known.contains(&dep_id) && !result.contains(&dep_id) {
+                    queue.push(dep_id);
+                }
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    fn pinned() -> HashSet<String> {
+        HashSet::from(["ludeon.rimworld".to_string()])
+    }
+
+    /// Report outcomes until finished, where the problem happens if `culprit` is active.
+    fn run(bisect: &mut Bisect, culprit: &str, dependencies: &HashMap<String, Vec<String>>) {
+        while !bisect.is_finished() {
+            let outcome = match bisect.active_ids().iter().any(|id| id == culprit) {
+                true => BisectOutcome::Bad,
+                false => BisectOutcome::Good,
+            };
+            bisect.report(outcome, dependencies);
+        }
+    }
+
+    #[test]
+    fn test_start_splits_suspects() {
+        let active = ids(&["Ludeon.RimWorld", "a", "b", "c", "d"]);
+        let bisect = Bisect::start(&active, &pinned(), &HashMap::new());
+
+        assert_eq!(bisect.step(), 1);
+        assert_eq!(bisect.suspects(), vec!["a", "b", "c", "d"]);
+        assert_eq!(bisect.testing_suspects(), vec!["a", "b"]);
+        assert_eq!(bisect.active_ids(), ids(&["Ludeon.RimWorld", "a", "b"]));
+        assert!(!bisect.is_finished());
+    }
+
+    #[test]
+    fn test_report_rules_out_suspects() {
+        let active = ids(&["Ludeon.RimWorld", "a", "b", "c", "d"]);
+        let mut bisect = Bisect::start(&active, &pinned(), &HashMap::new());
+
+        bisect.report(BisectOutcome::Good, &HashMap::new());
+        assert_eq!(bisect.suspects(), vec!["c", "d"]);
+        assert_eq!(bisect.testing_suspects(), vec!["c"]);
+
+        bisect.report(BisectOutcome::Bad, &HashMap::new());
+        assert_eq!(bisect.suspects(), vec!["c"]);
+        assert_eq!(
+            bisect.outcomes,
+            vec![BisectOutcome::Good, BisectOutcome::Bad]
+        );
+        assert!(bisect.is_finished());
+    }
+
+    #[test]
+    fn test_finds_culprit() {
+        let active: Vec<String> = std::iter::once("Ludeon.RimWorld".to_string())
+            .chain((0..300).map(|i| format!("mod{i}")))
+            .collect();
+        for culprit in ["mod0", "mod137", "mod299"] {
+            let mut bisect = Bisect::start(&active, &pinned(), &HashMap::new());
+            run(&mut bisect, culprit, &HashMap::new());
+            assert_eq!(bisect.suspects(), vec![culprit]);
+            assert!(bisect.outcomes.len() <= 9);
+        }
+    }
+
+    #[test]
+    fn test_keeps_pinned_mods_active() {
+        let active = ids(&["Ludeon.RimWorld", "a", "b", "c"]);
+        let mut bisect = Bisect::start(&active, &pinned(), &HashMap::new());
+        while !bisect.is_finished() {
+            assert!(bisect.active_ids().contains(&"Ludeon.RimWorld".to_string()));
+            bisect.report(BisectOutcome::Good, &HashMap::new());
+        }
+        assert_eq!(bisect.suspects(), vec!["c"]);
+    }
+
+    #[test]
+    fn test_activates_dependencies() {
+        let active = ids(&["Ludeon.RimWorld", "lib", "a", "b", "c"]);
+        let dependencies = HashMap::from([
+            ("b".to_string(), ids(&["lib"])),
+            ("c".to_string(), ids(&["lib", "ludeon.rimworld"])),
+        ]);
+        let mut bisect = Bisect::start(&active, &pinned(), &dependencies);
+        assert_eq!(bisect.testing_suspects(), vec!["lib", "a"]);
+
+        bisect.report(BisectOutcome::Good, &dependencies);
+        assert_eq!(bisect.suspects(), vec!["b", "c"]);
+        assert_eq!(bisect.active_ids(), ids(&["Ludeon.RimWorld", "lib", "b"]));
+
+        run(&mut bisect, "c", &dependencies);
+        assert_eq!(bisect.suspects(), vec!["c"]);
+    }
+
+    #[test]
+    fn test_finishes_with_minimal_set() {
+        let active = ids(&["Ludeon.RimWorld", "a", "b"]);
+        let dependencies = HashMap::from([
+            ("a".to_string(), ids(&["b"])),
+            ("b".to_string(), ids(&["a"])),
+        ]);
+        let bisect = Bisect::start(&active, &pinned(), &dependencies);
+
+        assert!(bisect.is_finished());
+        assert_eq!(bisect.suspects(), vec!["a", "b"]);
+        assert_eq!(bisect.active_ids(), ids(&["Ludeon.RimWorld"]));
+        assert_eq!(bisect.original_ids(), active);
+    }
+}
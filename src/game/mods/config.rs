@@ -10,7 +10,7 @@ use xml::{
     writer::{EmitterConfig, EventWriter, XmlEvent as WriterEvent},
 };
 
-use crate::game::xml::*;
+use crate::game::{version::minor_version, xml::*};
 
 #[derive(Debug, Clone, Default)]
 pub struct ModsConfigData {
@@ -30,11 +30,7 @@ impl ModsConfigData {
     }
 
     pub fn minor_version(&self) -> String {
-        self.version
-            .split('.')
-            .take(2)
-            .collect::<Vec<_>>()
-            .join(".")
+        minor_version(&self.version)
     }
 }
 
@@ -0,0 +1,180 @@
+//! The official expansions of the game, installed as mods in the game's `Data` folder.
+
+use std::fmt::Display;
+
+use crate::game::mods::ModMetaData;
+
+/// Package id of the base game.
+pub const CORE_ID: &str = "ludeon.rimworld";
+
+/// Prefix of the package ids of official content.
+const OFFICIAL_ID_PREFIX: &str = "ludeon.rimworld.";
+
+/// An official expansion, recognized by its package id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Expansion {
+    Royalty,
+    Ideology,
+    Biotech,
+    Anomaly,
+}
+
+impl Expansion {
+    /// Expansions in the order the game loads them, after Core.
+    pub const ALL: [Expansion; 4] = [
+        Expansion::Royalty,
+        Expansion::Ideology,
+        Expansion::Biotech,
+        Expansion::Anomaly,
+    ];
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            Expansion::Royalty => "ludeon.rimworld.royalty",
+            Expansion::Ideology => "ludeon.rimworld.ideology",
+            Expansion::Biotech => "ludeon.rimworld.biotech",
+            Expansion::Anomaly => "ludeon.rimworld.anomaly",
+        }
+    }
+
+    /// Find the expansion with a package id (case-insensitive).
+    pub fn from_id(mod_id: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|expansion| expansion.id().eq_ignore_ascii_case(mod_id))
+    }
+}
+
+impl Display for Expansion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expansion::Royalty => write!(f, "Royalty"),
+            Expansion::Ideology => write!(f, "Ideology"),
+            Expansion::Biotech => write!(f, "Biotech"),
+            Expansion::Anomaly => write!(f, "Anomaly"),
+        }
+    }
+}
+
+/// Get the position of official content in the load order: Core, then the known expansions in
+/// release order, then any other official content.
+pub fn official_rank(mod_id: &str) -> Option<usize> {
+    if mod_id.eq_ignore_ascii_case(CORE_ID) {
+        return Some(0);
+    }
+    if let Some(expansion) = Expansion::from_id(mod_id) {
+        return Expansion::ALL
+            .iter()
+            .position(|e| *e == expansion)
+            .map(|ix| ix + 1);
+    }
+    mod_id
+        .to_ascii_lowercase()
+        .starts_with(OFFICIAL_ID_PREFIX)
+        .then_some(Expansion::ALL.len() + 1)
+}
+
+/// Get the display name of official content, e.g. `Core` or `Biotech`.
+pub fn official_name(mod_id: &str) -> String {
+    if mod_id.eq_ignore_ascii_case(CORE_ID) {
+        return "Core".to_string();
+    }
+    match Expansion::from_id(mod_id) {
+        Some(expansion) => expansion.to_string(),
+        None => mod_id.rsplit('.').next().unwrap_or(mod_id).to_string(),
+    }
+}
+
+/// Move the expansions and other official content to right after Core, in canonical order.
+///
+/// Core itself is left in place, so mods that load before it, such as Harmony, stay there.
+pub fn pin_official_mods(mods: &mut Vec<ModMetaData>) {
+    let mut official: Vec<(usize, ModMetaData)> = Vec::new();
+    mods.retain(|m| match official_rank(&m.id) {
+        Some(rank) if rank > 0 => {
+            official.push((rank, m.clone()));
+            false
+        }
+        _ => true,
+    });
+    official.sort_by_key(|(rank, _)| *rank);
+
+    let insert_index = mods
+        .iter()
+        .position(|m| m.id.eq_ignore_ascii_case(CORE_ID))
+        .map_or(0, |ix| ix + 1);
+    mods.splice(
+        insert_index..insert_index,
+        official.into_iter().map(|(_, m)| m),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_mods(ids: &[&str]) -> Vec<ModMetaData> {
+        ids.iter()
+            .map(|id| ModMetaData {
+                id: id.to_string(),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    fn ids(mods: &[ModMetaData]) -> Vec<&str> {
+        mods.iter().map(|m| m.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_expansion_from_id() {
+        assert_eq!(
+            Expansion::from_id("Ludeon.RimWorld.Biotech"),
+            Some(Expansion::Biotech)
+        );
+        assert_eq!(Expansion::from_id("ludeon.rimworld"), None);
+        assert_eq!(Expansion::from_id("someone.biotech"), None);
+    }
+
+    #[test]
+    fn test_official_name() {
+        assert_eq!(official_name("Ludeon.RimWorld"), "Core");
+        assert_eq!(official_name("Ludeon.RimWorld.Ideology"), "Ideology");
+        assert_eq!(official_name("Ludeon.RimWorld.Odyssey"), "Odyssey");
+    }
+
+    #[test]
+    fn test_pin_official_mods() {
+        let mut mods = make_mods(&[
+            "brrainz.harmony",
+            "Ludeon.RimWorld.Anomaly",
+            "Ludeon.RimWorld",
+            "a",
+            "Ludeon.RimWorld.Royalty",
+            "Ludeon.RimWorld.Odyssey",
+            "b",
+            "Ludeon.RimWorld.Biotech",
+        ]);
+        pin_official_mods(&mut mods);
+        assert_eq!(
+            ids(&mods),
+            vec![
+                "brrainz.harmony",
+                "Ludeon.RimWorld",
+                "Ludeon.RimWorld.Royalty",
+                "Ludeon.RimWorld.Biotech",
+                "Ludeon.RimWorld.Anomaly",
+                "Ludeon.RimWorld.Odyssey",
+                "a",
+                "b",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pin_official_mods_without_core() {
+        let mut mods = make_mods(&["a", "Ludeon.RimWorld.Ideology", "b"]);
+        pin_official_mods(&mut mods);
+        assert_eq!(ids(&mods), vec!["Ludeon.RimWorld.Ideology", "a", "b"]);
+    }
+}
@@ -4,9 +4,15 @@ use std::fmt::Display;
 pub struct ModIssues {
     pub mod_id: String,
     pub unsupported_game_version: Option<String>, // e.g. "1.0"
+    pub missing_version_folder: Option<String>,   // game version without a content folder
     pub missing_dependencies: Vec<String>,
+    pub missing_expansions: Vec<String>, // DLCs required but not installed or not active
     pub load_order_violations: Vec<String>, // mods violating load_after/before rules
-    pub incompatible_with: Vec<String>,     // mods that are incompatible with this mod
+    pub incompatible_with: Vec<String>,  // mods that are incompatible with this mod
+    pub texture_warnings: Vec<String>,   // oversized or uncompressed textures
+    pub assembly_conflicts: Vec<String>, // assemblies loaded in other versions by other mods
+    pub patch_warnings: Vec<String>,     // conflicting patches or patches for inactive mods
+    pub log_errors: Vec<String>,         // errors in the game log attributed to this mod
 }
 
 impl ModIssues {
@@ -14,9 +20,15 @@ impl ModIssues {
         Self {
             mod_id,
             unsupported_game_version: None,
+            missing_version_folder: None,
             missing_dependencies: Vec::new(),
+            missing_expansions: Vec::new(),
             load_order_violations: Vec::new(),
             incompatible_with: Vec::new(),
+            texture_warnings: Vec::new(),
+            assembly_conflicts: Vec::new(),
+            patch_warnings: Vec::new(),
+            log_errors: Vec::new(),
         }
     }
 
@@ -24,10 +36,18 @@ impl ModIssues {
         self.unsupported_game_version = Some(version);
     }
 
+    pub fn add_missing_version_folder(&mut self, version: String) {
+        self.missing_version_folder = Some(version);
+    }
+
     pub fn add_missing_dependency(&mut self, dependency: String) {
         self.missing_dependencies.push(dependency);
     }
 
+    pub fn add_missing_expansion(&mut self, expansion: String) {
+        self.missing_expansions.push(expansion);
+    }
+
     pub fn add_load_order_violation(&mut self, violation: String) {
         self.load_order_violations.push(violation);
     }
@@ -36,18 +56,40 @@ impl ModIssues {
         self.incompatible_with.push(mod_id);
     }
 
+    pub fn add_texture_warning(&mut self, warning: String) {
+        self.texture_warnings.push(warning);
+    }
+
+    pub fn add_assembly_conflict(&mut self, conflict: String) {
+        self.assembly_conflicts.push(conflict);
+    }
+
+    pub fn add_patch_warning(&mut self, warning: String) {
+        self.patch_warnings.push(warning);
+    }
+
+    pub fn add_log_error(&mut self, error: String) {
+        self.log_errors.push(error);
+    }
+
     pub fn has_issues(&self) -> bool {
         self.has_warnings() || self.has_errors()
     }
 
     pub fn has_warnings(&self) -> bool {
-        !self.load_order_violations.is_empty()
+        self.missing_version_folder.is_some()
+            || !self.load_order_violations.is_empty()
+            || !self.texture_warnings.is_empty()
+            || !self.patch_warnings.is_empty()
     }
 
     pub fn has_errors(&self) -> bool {
         self.unsupported_game_version.is_some()
             || !self.missing_dependencies.is_empty()
+            || !self.missing_expansions.is_empty()
             || !self.incompatible_with.is_empty()
+            || !self.assembly_conflicts.is_empty()
+            || !self.log_errors.is_empty()
     }
 }
 
@@ -59,6 +101,10 @@ impl Display for ModIssues {
             sections.push(format!("Unsupported game version: {version}"));
         }
 
+        if let Some(version) = &self.missing_version_folder {
+            sections.push(format!("No content folder for game version: {version}"));
+        }
+
         if !self.missing_dependencies.is_empty() {
             sections.push(format!(
                 "Missing dependencies:\n- {}",
@@ -66,6 +112,13 @@ impl Display for ModIssues {
             ));
         }
 
+        if !self.missing_expansions.is_empty() {
+            sections.push(format!(
+                "Missing DLCs:\n- {}",
+                self.missing_expansions.join("\n- ")
+            ));
+        }
+
         if !self.load_order_violations.is_empty() {
             sections.push(format!(
                 "Load order violations:\n- {}",
@@ -80,6 +133,34 @@ impl Display for ModIssues {
             ));
         }
 
+        if !self.assembly_conflicts.is_empty() {
+            sections.push(format!(
+                "Assembly conflicts:\n- {}",
+                self.assembly_conflicts.join("\n- ")
+            ));
+        }
+
+        if !self.log_errors.is_empty() {
+            sections.push(format!(
+                "Player.log errors:\n- {}",
+                self.log_errors.join("\n- ")
+            ));
+        }
+
+        if !self.patch_warnings.is_empty() {
+            sections.push(format!(
+                "Patch warnings:\n- {}",
+                self.patch_warnings.join("\n- ")
+            ));
+        }
+
+        if !self.texture_warnings.is_empty() {
+            sections.push(format!(
+                "Texture warnings:\n- {}",
+                self.texture_warnings.join("\n- ")
+            ));
+        }
+
         write!(f, "{}", sections.join("\n\n"))
     }
 }
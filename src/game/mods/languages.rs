@@ -0,0 +1,314 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs::{self, File},
+    io::BufReader,
+    path::{Path, PathBuf},
+};
+
+use xml::reader::XmlEvent as ReaderEvent;
+
+use crate::game::{defs::DefEntry, xml::*};
+
+const LANGUAGES_DIR: &str = "Languages";
+const KEYED_DIR: &str = "Keyed";
+const DEF_INJECTED_DIR: &str = "DefInjected";
+const DEFS_DIR: &str = "Defs";
+const ASSEMBLIES_DIR: &str = "Assemblies";
+
+/// Name of the language the game and most mods are written in.
+pub const ENGLISH: &str = "English";
+
+/// Translation entries a mod provides for a language.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LanguageEntries {
+    pub keyed: usize,
+    pub def_injected: usize,
+    /// number of DefInjected entries for each def, by def type and defName
+    pub def_injected_targets: HashMap<(String, String), usize>,
+    /// the language is packed in a tar archive, so its entries are not counted
+    pub packed: bool,
+}
+
+impl LanguageEntries {
+    pub fn total(&self) -> usize {
+        self.keyed + self.def_injected
+    }
+
+    /// Estimate the share of a baseline number of entries that is translated.
+    pub fn coverage(&self, baseline: usize) -> Option<f32> {
+        if self.packed || baseline == 0 {
+            return None;
+        }
+        Some((self.total() as f32 / baseline as f32).min(1.0))
+    }
+
+    /// Get the entries that apply to a mod with the given defs, keeping only the DefInjected
+    /// entries that target them, as translation mods often cover several mods.
+    pub fn for_defs(&self, defs: &[DefEntry]) -> LanguageEntries {
+        let defs: HashSet<(&str, &str)> = defs
+            .iter()
+            .map(|def| (def.def_type.as_str(), def.def_name.as_str()))
+            .collect();
+        let def_injected_targets: HashMap<(String, String), usize> = self
+            .def_injected_targets
+            .iter()
+            .filter(|((def_type, def_name), _)| {
+                defs.contains(&(def_type.as_str(), def_name.as_str()))
+            })
+            .map(|(target, count)| (target.clone(), *count))
+            .collect();
+        LanguageEntries {
+            keyed: self.keyed,
+            def_injected: def_injected_targets.values().sum(),
+            def_injected_targets,
+            packed: self.packed,
+        }
+    }
+}
+
+/// A language available for a mod, from the mod itself or a translation mod.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanguageCoverage {
+    pub language: String,
+    pub entries: LanguageEntries,
+    /// name of the translation mod providing the language, if not the mod itself
+    pub provider: Option<String>,
+    /// estimated share of the English entries that is translated
+    pub coverage: Option<f32>,
+}
+
+/// Read the languages in the `Languages` folder of each content folder of a mod.
+///
+/// Languages are keyed by name without the native name suffix, e.g. `French` for
+/// `French (Français)`. When the same file exists in several folders, only the
+/// highest priority copy is counted.
+pub fn load_languages(mod_dir: &Path, folders: &[PathBuf]) -> BTreeMap<String, LanguageEntries> {
+    let mut languages: BTreeMap<String, LanguageEntries> = BTreeMap::new();
+    let mut seen_files = HashSet::new();
+
+    for folder in folders {
+        let languages_dir = mod_dir.join(folder).join(LANGUAGES_DIR);
+        let Ok(entries) = fs::read_dir(&languages_dir) else {
+            continue;
+        };
+
+        let mut folder_files = HashSet::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                let Some(dir_name) = path.file_name().and_then(|name| name.to_str()) else {
+                    continue;
+                };
+                let name = language_name(dir_name);
+                let language = languages.entry(name.clone()).or_default();
+                for dir in [KEYED_DIR, DEF_INJECTED_DIR] {
+                    let dir_path = path.join(dir);
+                    for file in find_xml_files(&dir_path) {
+                        let relative = file.strip_prefix(&path).unwrap_or(&file);
+                        let relative =
+                            format!("{name}/{}", relative.to_string_lossy()).to_ascii_lowercase();
+                        if seen_files.contains(&relative) {
+                            continue;
+                        }
+                        folder_files.insert(relative);
+                        let entry_names = match read_entry_names(&file) {
+                            Ok(entry_names) => entry_names,
+                            Err(e) => {
+                                log::debug!("skipping language file: {e}");
+                                continue;
+                            }
+                        };
+                        if dir == KEYED_DIR {
+                            language.keyed += entry_names.len();
+                            continue;
+                        }
+                        language.def_injected += entry_names.len();
+                        let Some(def_type) = def_injected_type(&dir_path, &file) else {
+                            continue;
+                        };
+                        for entry_name in entry_names {
+                            // entries are named after the defName and the injected field,
+                            // e.g. `Gun_Revolver.label`
+                            let def_name = entry_name.split('.').next().unwrap_or_default();
+                            *language
+                                .def_injected_targets
+                                .entry((def_type.clone(), def_name.to_string()))
+                                .or_default() += 1;
+                        }
+                    }
+                }
+            } else if path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("tar"))
+                && let Some(stem) = path.file_stem().and_then(|stem| stem.to_str())
+            {
+                languages.entry(language_name(stem)).or_default().packed = true;
+            }
+        }
+        seen_files.extend(folder_files);
+    }
+
+    languages
+}
+
+/// Check if a mod only provides translations, without defs or assemblies of its own.
+pub fn is_translation_mod(mod_dir: &Path, folders: &[PathBuf]) -> bool {
+    !folders.is_empty()
+        && folders.iter().all(|folder| {
+            let content_dir = mod_dir.join(folder);
+            !content_dir.join(DEFS_DIR).is_dir() && !content_dir.join(ASSEMBLIES_DIR).is_dir()
+        })
+}
+
+/// Get the language name from a language folder name, e.g. `French` from `French (Français)`.
+fn language_name(folder_name: &str) -> String {
+    folder_name
+        .split_once('(')
+        .map_or(folder_name, |(name, _)| name)
+        .trim()
+        .to_string()
+}
+
+/// Get the def type of a DefInjected file from the folder it is in, e.g. `ThingDef` for
+/// `DefInjected/ThingDef/Weapons.xml`.
+fn def_injected_type(def_injected_dir: &Path, file: &Path) -> Option<String> {
+    let relative = file.strip_prefix(def_injected_dir).ok()?;
+    let mut components = relative.components();
+    let def_type = components.next()?.as_os_str().to_str()?;
+    // files directly in the DefInjected folder have no def type
+    components.next()?;
+    Some(def_type.to_string())
+}
+
+/// Read the names of the entries in a Keyed or DefInjected file, which are the children of its
+/// root element.
+fn read_entry_names(path: &Path) -> ParseResult<Vec<String>> {
+    let file = File::open(path).map_err(|e| format!("opening file {path:?}: {e}"))?;
+    let mut events = create_reader(BufReader::new(file));
+    let mut depth = 0;
+    let mut names = Vec::new();
+    loop {
+        match events.next() {
+            Ok(ReaderEvent::EndDocument) => break,
+            Ok(ReaderEvent::StartElement { name, .. }) => {
+                depth += 1;
+                if depth == 2 {
+                    names.push(name.local_name);
+                }
+            }
+            Ok(ReaderEvent::EndElement { .. }) => depth -= 1,
+            Ok(_) => {}
+            Err(e) => return Err(format!("error parsing language data from {path:?}: {e}")),
+        }
+    }
+    Ok(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::mods::write_test_file;
+
+    fn def(def_type: &str, def_name: &str) -> DefEntry {
+        DefEntry {
+            def_type: def_type.into(),
+            def_name: def_name.into(),
+            label: None,
+            folder: PathBuf::new(),
+            file: PathBuf::from("Things.xml"),
+        }
+    }
+
+    #[test]
+    fn test_load_languages() {
+        let dir = tempfile::tempdir().unwrap();
+        write_test_file(
+            dir.path(),
+            "Languages/English/Keyed/Mod.xml",
+            "<LanguageData><A>a</A><B>b</B></LanguageData>",
+        );
+        write_test_file(
+            dir.path(),
+            "1.5/Languages/French (Français)/Keyed/Mod.xml",
+            "<LanguageData><A>a</A></LanguageData>",
+        );
+        // overridden by the higher priority copy in 1.5
+        write_test_file(
+            dir.path(),
+            "Languages/French/Keyed/Mod.xml",
+            "<LanguageData><A>a</A><B>b</B><C>c</C></LanguageData>",
+        );
+        write_test_file(
+            dir.path(),
+            "Languages/French/DefInjected/ThingDef/Things.xml",
+            "<LanguageData>
+                <Gun_Rifle.label>fusil</Gun_Rifle.label>
+                <Gun_Rifle.description>un fusil</Gun_Rifle.description>
+                <Gun_Pistol.label>pistolet</Gun_Pistol.label>
+            </LanguageData>",
+        );
+        write_test_file(
+            dir.path(),
+            "Languages/French/DefInjected/Loose.xml",
+            "<LanguageData><X.label>x</X.label></LanguageData>",
+        );
+        write_test_file(
+            dir.path(),
+            "Languages/French/Keyed/Broken.xml",
+            "<LanguageData><A>",
+        );
+        write_test_file(dir.path(), "Languages/German (Deutsch).tar", "");
+
+        let languages = load_languages(dir.path(), &[PathBuf::from("1.5"), PathBuf::new()]);
+        assert_eq!(
+            languages.keys().collect::<Vec<_>>(),
+            ["English", "French", "German"]
+        );
+        assert_eq!(languages["English"].keyed, 2);
+        assert!(languages["German"].packed);
+
+        let french = &languages["French"];
+        assert_eq!(french.keyed, 1);
+        assert_eq!(french.def_injected, 4);
+        assert_eq!(
+            french.def_injected_targets,
+            HashMap::from([
+                (("ThingDef".to_string(), "Gun_Rifle".to_string()), 2),
+                (("ThingDef".to_string(), "Gun_Pistol".to_string()), 1),
+            ])
+        );
+
+        let covered =
+            french.for_defs(&[def("ThingDef", "Gun_Rifle"), def("RecipeDef", "Gun_Pistol")]);
+        assert_eq!(covered.keyed, 1);
+        assert_eq!(covered.def_injected, 2);
+        assert_eq!(covered.coverage(6), Some(0.5));
+        assert_eq!(covered.coverage(0), None);
+    }
+
+    #[test]
+    fn test_is_translation_mod() {
+        let dir = tempfile::tempdir().unwrap();
+        write_test_file(
+            dir.path(),
+            "Languages/French/Keyed/Mod.xml",
+            "<LanguageData/>",
+        );
+        write_test_file(dir.path(), "1.5/Defs/Things.xml", "<Defs/>");
+        write_test_file(dir.path(), "1.4/Assemblies/Mod.dll", "");
+
+        assert!(is_translation_mod(dir.path(), &[PathBuf::new()]));
+        assert!(!is_translation_mod(
+            dir.path(),
+            &[PathBuf::from("1.5"), PathBuf::new()]
+        ));
+        assert!(!is_translation_mod(dir.path(), &[PathBuf::from("1.4")]));
+        assert!(!is_translation_mod(dir.path(), &[]));
+    }
+
+    #[test]
+    fn test_language_name() {
+        assert_eq!(language_name("French (Français)"), "French");
+        assert_eq!(language_name("ChineseSimplified"), "ChineseSimplified");
+    }
+}
@@ -0,0 +1,354 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    fs::{self, File},
+    io::{BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+use xml::reader::{EventReader, XmlEvent as ReaderEvent};
+
+use crate::game::xml::*;
+
+const COMMON_DIR: &str = "Common";
+const DEFAULT_KEY: &str = "default";
+
+/// A content folder listed in LoadFolders.xml, with its activation conditions.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LoadFolder {
+    /// path relative to the mod directory, empty for the mod root
+    pub path: PathBuf,
+    /// mod ids (lowercase), at least one of which must be active
+    pub if_mod_active: Vec<String>,
+    /// mod ids (lowercase), none of which may be active
+    pub if_mod_not_active: Vec<String>,
+}
+
+impl LoadFolder {
+    /// Check if the folder loads with the given active mod ids (lowercase).
+    pub fn is_enabled(&self, active_ids: &HashSet<String>) -> bool {
+        (self.if_mod_active.is_empty()
+            || self.if_mod_active.iter().any(|id| active_ids.contains(id)))
+            && !self
+                .if_mod_not_active
+                .iter()
+                .any(|id| active_ids.contains(id))
+    }
+}
+
+/// Content folders by game version, parsed from a mod's LoadFolders.xml.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LoadFolders {
+    /// folders keyed by game version (e.g. `1.5`) or `default`, in file order
+    pub versions: BTreeMap<String, Vec<LoadFolder>>,
+}
+
+impl LoadFolders {
+    /// Load LoadFolders.xml from a path.
+    pub fn load(path: &Path) -> ParseResult<Self> {
+        let file = File::open(path).map_err(|e| format!("opening file {path:?}: {e}"))?;
+        let events = create_reader(BufReader::new(file));
+        parse_load_folders(events, path)
+    }
+
+    /// Get the folders listed for a game version, falling back to the default list.
+    pub fn for_version(&self, game_version: Option<&str>) -> Option<&Vec<LoadFolder>> {
+        game_version
+            .and_then(|version| self.versions.get(version))
+            .or_else(|| self.versions.get(DEFAULT_KEY))
+    }
+}
+
+/// Get the folders the game loads content from for a mod, highest priority first.
+///
+/// Paths are relative to the mod directory, with the mod root as an empty path.
+/// Folders from LoadFolders.xml are filtered by `active_ids` (lowercase), or all
+/// kept when it is `None`. Without LoadFolders.xml the game uses the folder for
+/// the closest game version not newer than the current one, then `Common`, then the mod root.
+pub fn content_folders(
+    mod_dir: &Path,
+    load_folders: Option<&LoadFolders>,
+    version_folders: &BTreeSet<String>,
+    game_version: Option<&str>,
+    active_ids: Option<&HashSet<String>>,
+) -> Vec<PathBuf> {
+    if let Some(folders) = load_folders.and_then(|lf| lf.for_version(game_version))
+        && !folders.is_empty()
+    {
+        // later folders in the file override earlier ones
+        let mut seen = HashSet::new();
+        return folders
+            .iter()
+            .rev()
+            .filter(|folder| active_ids.is_none_or(|ids| folder.is_enabled(ids)))
+            .map(|folder| folder.path.clone())
+            .filter(|path| seen.insert(path.clone()))
+            .collect();
+    }
+
+    let mut folders = Vec::new();
+    if let Some(version_dir) =
+        game_version.and_then(|version| version_dir(version_folders, version))
+    {
+        folders.push(PathBuf::from(version_dir));
+    }
+    if mod_dir.join(COMMON_DIR).is_dir() {
+        folders.push(PathBuf::from(COMMON_DIR));
+    }
+    folders.push(PathBuf::new());
+    folders
+}
+
+/// List the top level folders of a mod named after a game version, e.g. `1.5`.
+pub fn version_folders(mod_dir: &Path) -> BTreeSet<String> {
+    let Ok(entries) = fs::read_dir(mod_dir) else {
+        return BTreeSet::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .filter(|name| parse_version(name).is_some())
+        .collect()
+}
+
+/// Find the version folder matching the game version, or the newest older one.
+fn version_dir<'a>(version_folders: &'a BTreeSet<String>, game_version: &str) -> Option<&'a str> {
+    let game_version = parse_version(game_version)?;
+    version_folders
+        .iter()
+        .filter_map(|name| Some((parse_version(name)?, name.as_str())))
+        .filter(|(version, _)| *version <= game_version)
+        .max()
+        .map(|(_, name)| name)
+}
+
+/// Parse a `major.minor` version, e.g. `1.5`.
+fn parse_version(version: &str) -> Option<(u32, u32)> {
+    let (major, minor) = version.trim().split_once('.')?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+fn parse_load_folders<R: Read>(
+    mut events: EventReader<R>,
+    path: &Path,
+) -> ParseResult<LoadFolders> {
+    let mut load_folders = LoadFolders::default();
+    loop {
+        match events.next() {
+            Ok(ReaderEvent::EndDocument) => break,
+            Ok(ReaderEvent::StartDocument { .. }) => {}
+            Ok(ReaderEvent::StartElement { name, .. })
+                if name.local_name.eq_ignore_ascii_case("loadFolders") =>
+            {
+                load_folders.versions =
+                    parse_map_of_maps(&mut events, path, &name.local_name, parse_folder_list)?
+                        .into_iter()
+                        .map(|(key, folders)| {
+                            let key = key.strip_prefix(['v', 'V']).unwrap_or(&key);
+                            (key.to_ascii_lowercase(), folders)
+                        })
+                        .collect();
+            }
+            Ok(event) => {
+                log::trace!("unexpected root event {event:?} from {path:?}");
+                if let ReaderEvent::StartElement { .. } = event {
+                    skip_element(&mut events)?;
+                }
+            }
+            Err(e) => {
+                return Err(format!("error parsing root event from {path:?}: {e}"));
+            }
+        }
+    }
+    Ok(load_folders)
+}
+
+/// Parses a list of <li> folders, keeping their IfModActive and IfModNotActive attributes.
+fn parse_folder_list<R: Read>(
+    events: &mut EventReader<R>,
+    path: &Path,
+    container_name: &str,
+) -> ParseResult<Vec<LoadFolder>> {
+    let mut folders = Vec::new();
+    loop {
+        match events.next() {
+            Ok(ReaderEvent::StartElement {
+                name, attributes, ..
+            }) if name.local_name.eq_ignore_ascii_case("li") => {
+                let mut folder = LoadFolder::default();
+                for attribute in attributes {
+                    let ids = || {
+                        attribute
+                            .value
+                            .split(',')
+                            .map(|id| id.trim().to_ascii_lowercase())
+                            .filter(|id| !id.is_empty())
+                            .collect()
+                    };
+                    match attribute.name.local_name.as_str() {
+                        "IfModActive" => folder.if_mod_active = ids(),
+                        "IfModNotActive" => folder.if_mod_not_active = ids(),
+                        other => log::debug!("ignoring attribute {other} in {path:?}"),
+                    }
+                }
+                let text = parse_text_element(events, path, &name.local_name)?;
+                folder.path = PathBuf::from(text.trim().replace('\\', "/").trim_matches('/'));
+                folders.push(folder);
+            }
+            Ok(ReaderEvent::EndElement { name })
+                if name.local_name.eq_ignore_ascii_case(container_name) =>
+            {
+                break;
+            }
+            Ok(ReaderEvent::Characters(chars)) => {
+                if !chars.trim().is_empty() {
+                    log::warn!("unexpected characters {chars} in {container_name} from {path:?}");
+                }
+            }
+            Ok(ReaderEvent::Whitespace(_)) => {}
+            Ok(event) => {
+                log::warn!("unexpected event {event:?} in {container_name} from {path:?}");
+                if let ReaderEvent::StartElement { .. } = event {
+                    skip_element(events)?;
+                }
+            }
+            Err(e) => {
+                return Err(format!("error parsing {container_name} from {path:?}: {e}"));
+            }
+        }
+    }
+    Ok(folders)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOAD_FOLDERS: &str = r#"
+<loadFolders>
+  <v1.4>
+    <li>/</li>
+    <li>1.4</li>
+  </v1.4>
+  <v1.5>
+    <li>/</li>
+    <li>Common</li>
+    <li IfModActive="Ludeon.RimWorld.Biotech, Ludeon.RimWorld.Ideology">Mods\DLC</li>
+    <li IfModNotActive="CETeam.CombatExtended">Mods/Vanilla/</li>
+    <li>1.5</li>
+    <li>Common</li>
+  </v1.5>
+  <default>
+    <li>/</li>
+  </default>
+</loadFolders>
+"#;
+
+    fn parse(xml: &str) -> LoadFolders {
+        parse_load_folders(create_reader(xml.as_bytes()), Path::new("LoadFolders.xml")).unwrap()
+    }
+
+    fn paths(folders: &[&str]) -> Vec<PathBuf> {
+        folders.iter().map(PathBuf::from).collect()
+    }
+
+    fn ids(ids: &[&str]) -> HashSet<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_load_folders() {
+        let load_folders = parse(LOAD_FOLDERS);
+        assert_eq!(
+            load_folders.versions.keys().collect::<Vec<_>>(),
+            vec!["1.4", "1.5", "default"]
+        );
+
+        let folders = &load_folders.versions["1.5"];
+        assert_eq!(folders.len(), 6);
+        assert_eq!(folders[0].path, PathBuf::new());
+        assert_eq!(
+            folders[2],
+            LoadFolder {
+                path: PathBuf::from("Mods/DLC"),
+                if_mod_active: vec![
+                    "ludeon.rimworld.biotech".into(),
+                    "ludeon.rimworld.ideology".into()
+                ],
+                if_mod_not_active: Vec::new(),
+            }
+        );
+        assert_eq!(folders[3].path, PathBuf::from("Mods/Vanilla"));
+        assert_eq!(folders[3].if_mod_not_active, vec!["ceteam.combatextended"]);
+    }
+
+    #[test]
+    fn test_for_version() {
+        let load_folders = parse(LOAD_FOLDERS);
+        assert_eq!(load_folders.for_version(Some("1.4")).unwrap().len(), 2);
+        assert_eq!(load_folders.for_version(Some("1.6")).unwrap().len(), 1);
+        assert_eq!(load_folders.for_version(None).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_is_enabled() {
+        let folder = LoadFolder {
+            path: PathBuf::from("Mods/DLC"),
+            if_mod_active: vec!["a".into(), "b".into()],
+            if_mod_not_active: vec!["c".into()],
+        };
+        assert!(folder.is_enabled(&ids(&["b"])));
+        assert!(!folder.is_enabled(&ids(&[])));
+        assert!(!folder.is_enabled(&ids(&["a", "c"])));
+        assert!(LoadFolder::default().is_enabled(&ids(&[])));
+    }
+
+    #[test]
+    fn test_content_folders_from_load_folders() {
+        let load_folders = parse(LOAD_FOLDERS);
+        let content = |active_ids: Option<&HashSet<String>>| {
+            content_folders(
+                Path::new("mod"),
+                Some(&load_folders),
+                &BTreeSet::new(),
+                Some("1.5"),
+                active_ids,
+            )
+        };
+
+        // later folders override earlier ones, and each folder is listed once
+        assert_eq!(
+            content(None),
+            paths(&["Common", "1.5", "Mods/Vanilla", "Mods/DLC", ""])
+        );
+        assert_eq!(
+            content(Some(&ids(&["ceteam.combatextended"]))),
+            paths(&["Common", "1.5", ""])
+        );
+        assert_eq!(
+            content(Some(&ids(&["ludeon.rimworld.ideology"]))),
+            paths(&["Common", "1.5", "Mods/Vanilla", "Mods/DLC", ""])
+        );
+    }
+
+    #[test]
+    fn test_content_folders_without_load_folders() {
+        let dir = tempfile::tempdir().unwrap();
+        for folder in ["1.3", "1.4", "Common", "Textures"] {
+            fs::create_dir(dir.path().join(folder)).unwrap();
+        }
+        let version_folders = version_folders(dir.path());
+        assert_eq!(
+            version_folders.iter().collect::<Vec<_>>(),
+            vec!["1.3", "1.4"]
+        );
+
+        let content =
+            |game_version| content_folders(dir.path(), None, &version_folders, game_version, None);
+        // the closest version not newer than the game is used
+        assert_eq!(content(Some("1.5")), paths(&["1.4", "Common", ""]));
+        assert_eq!(content(Some("1.3")), paths(&["1.3", "Common", ""]));
+        assert_eq!(content(Some("1.2")), paths(&["Common", ""]));
+        assert_eq!(content(None), paths(&["Common", ""]));
+    }
+}
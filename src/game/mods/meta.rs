@@ -1,12 +1,16 @@
 use std::{
-    collections::{BTreeMap, BTreeSet},
+    collections::{BTreeMap, BTreeSet, HashSet},
     fs::{File, metadata},
     io::BufReader,
     path::{Path, PathBuf},
     time::SystemTime,
 };
 
-use crate::game::{paths, xml::create_reader};
+use crate::game::{
+    mods::{LoadFolders, ModStats, WorkshopItem, content_folders, version_folders},
+    paths,
+    xml::create_reader,
+};
 
 mod parser;
 mod source;
@@ -14,6 +18,8 @@ mod source;
 use parser::*;
 pub use source::*;
 
+const STEAM_WORKSHOP_URL: &str = "https://steamcommunity.com/sharedfiles/filedetails/?id=";
+
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct ModMetaData {
     pub id: String,
@@ -41,6 +47,10 @@ pub struct ModMetaData {
     pub source: Source,
     pub created: Option<SystemTime>,
     pub modified: Option<SystemTime>,
+    pub workshop: Option<WorkshopItem>,
+    pub stats: Option<ModStats>,
+    pub load_folders: Option<LoadFolders>,
+    pub version_folders: BTreeSet<String>,
 }
 
 impl ModMetaData {
@@ -71,6 +81,15 @@ impl ModMetaData {
         let events = create_reader(reader);
         parse_mod_metadata(events, &mut mod_meta)?;
 
+        let load_folders_file = paths::mod_load_folders_file(path);
+        if load_folders_file.is_file() {
+            match LoadFolders::load(&load_folders_file) {
+                Ok(load_folders) => mod_meta.load_folders = Some(load_folders),
+                Err(e) => log::error!("loading load folders: {e}"),
+            }
+        }
+        mod_meta.version_folders = version_folders(path);
+
         Ok(mod_meta)
     }
 
@@ -115,6 +134,41 @@ impl ModMetaData {
         }
     }
 
+    /// Get the folders the game loads content from, highest priority first.
+    ///
+    /// Conditional folders are filtered by `active_ids` (lowercase), or all kept when it is `None`.
+    pub fn content_folders(
+        &self,
+        game_version: Option<&str>,
+        active_ids: Option<&HashSet<String>>,
+    ) -> Vec<PathBuf> {
+        content_folders(
+            &self.path,
+            self.load_folders.as_ref(),
+            &self.version_folders,
+            game_version,
+            active_ids,
+        )
+    }
+
+    /// Check if the mod has content specifically for a game version, e.g. `1.5`.
+    ///
+    /// Mods without LoadFolders.xml or version folders load everything from their root.
+    pub fn has_version_folder(&self, game_version: &str) -> bool {
+        match &self.load_folders {
+            Some(load_folders) => load_folders.versions.contains_key(game_version),
+            None => self.version_folders.is_empty() || self.version_folders.contains(game_version),
+        }
+    }
+
+    /// Get the Steam workshop page for this mod, if it has a Steam id.
+    pub fn steam_workshop_url(&self) -> Option<String> {
+        self.steam_app_id
+            .as_ref()
+            .filter(|id| !id.is_empty())
+            .map(|id| format!("{STEAM_WORKSHOP_URL}{id}"))
+    }
+
     pub fn is_official(&self) -> bool {
         self.source.is_official()
     }
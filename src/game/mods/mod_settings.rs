@@ -0,0 +1,313 @@
+//! Settings files mods write to the game's Config folder, named `Mod_<packageId>_<Class>.xml`.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use chrono::{NaiveDateTime, Utc};
+
+use crate::game::mods::ModMetaData;
+
+const MOD_SETTINGS_PREFIX: &str = "Mod_";
+const MOD_SETTINGS_EXTENSION: &str = "xml";
+const MOD_SETTINGS_BACKUP_DIR: &str = "mod_settings";
+/// Name format of backup snapshot directories, which sorts by time.
+const SNAPSHOT_DIR_FORMAT: &str = "%Y-%m-%dT%H-%M-%S%.3f";
+/// File in a snapshot listing the active mods it was backed up with, one id per line.
+const SNAPSHOT_MOD_LIST_FILE: &str = "mod_list.txt";
+
+/// A settings file written by a mod.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModSettingsFile {
+    pub path: PathBuf,
+    /// packageId or folder name of the mod, as written in the file name
+    pub owner: String,
+    /// name of the settings class of the mod
+    pub class: String,
+}
+
+impl ModSettingsFile {
+    pub fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+}
+
+/// Find the mod settings files in the config directory, sorted by name.
+pub fn find_mod_settings(config_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(config_dir) else {
+        log::warn!("unable to read config directory {config_dir:?}");
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && settings_stem(path).is_some())
+        .collect();
+    files.sort();
+    files
+}
+
+/// Link settings files to the mods that wrote them, by packageId or folder name.
+///
+/// Linked files are keyed by mod id (lowercase), and files without an installed mod are
+/// returned as orphans. When several mods match, the longest name wins, e.g. a `_steam` copy
+/// over the mod it duplicates.
+pub fn link_mod_settings(
+    files: &[PathBuf],
+    mods: &[ModMetaData],
+) -> (HashMap<String, Vec<ModSettingsFile>>, Vec<ModSettingsFile>) {
+    let mut owners: Vec<(String, String)> = mods
+        .iter()
+        .flat_map(|mod_meta| {
+            let mod_id = mod_meta.id.to_ascii_lowercase();
+            let folder = mod_meta
+                .path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_ascii_lowercase());
+            std::iter::once((mod_id.clone(), mod_id.clone()))
+                .chain(folder.map(|folder| (folder, mod_id)))
+        })
+        .collect();
+    owners.sort_by_key(|(name, _)| std::cmp::Reverse(name.len()));
+
+    let mut linked: HashMap<String, Vec<ModSettingsFile>> = HashMap::new();
+    let mut orphans = Vec::new();
+    for path in files {
+        let Some(stem) = settings_stem(path) else {
+            continue;
+        };
+        let lowercase = stem.to_ascii_lowercase();
+        let owner = owners.iter().find(|(name, _)| {
+            lowercase
+                .strip_prefix(name.as_str())
+                .and_then(|rest| rest.strip_prefix('_'))
+                .is_some_and(|class| !class.is_empty())
+        });
+        match owner {
+            Some((name, mod_id)) => {
+                linked
+                    .entry(mod_id.clone())
+                    .or_default()
+                    .push(ModSettingsFile {
+                        path: path.clone(),
+                        owner: stem[..name.len()].to_string(),
+                        class: stem[name.len() + 1..].to_string(),
+                    });
+            }
+            None => {
+                let (owner, class) = stem.rsplit_once('_').unwrap_or((stem, ""));
+                orphans.push(ModSettingsFile {
+                    path: path.clone(),
+                    owner: owner.to_string(),
+                    class: class.to_string(),
+                });
+            }
+        }
+    }
+    (linked, orphans)
+}
+
+/// A backup of mod settings files, together with the mod list profile they were used with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModSettingsSnapshot {
+    pub path: PathBuf,
+    /// time the snapshot was taken (UTC)
+    pub created: Option<NaiveDateTime>,
+    /// ids of the mods active when the snapshot was taken, in load order
+    pub active_mods: Vec<String>,
+    /// settings files in the snapshot
+    pub files: Vec<PathBuf>,
+}
+
+impl ModSettingsSnapshot {
+    /// Read a snapshot directory, which is only a snapshot if it holds settings files.
+    fn read(path: &Path) -> Option<Self> {
+        let files = find_mod_settings(path);
+        if files.is_empty() {
+            return None;
+        }
+        let name = path.file_name()?.to_string_lossy();
+        let active_mods = fs::read_to_string(path.join(SNAPSHOT_MOD_LIST_FILE))
+            .map(|mod_list| mod_list.lines().map(str::to_string).collect())
+            .unwrap_or_default();
+        Some(Self {
+            path: path.to_path_buf(),
+            created: NaiveDateTime::parse_and_remainder(&name, SNAPSHOT_DIR_FORMAT)
+                .ok()
+                .map(|(created, _)| created),
+            active_mods,
+            files,
+        })
+    }
+}
+
+/// Get the directory mod settings are backed up to for an instance, which holds a snapshot
+/// directory for each backup.
+pub fn mod_settings_backup_dir(data_dir: &Path, instance: &str) -> PathBuf {
+    data_dir.join(MOD_SETTINGS_BACKUP_DIR).join(instance)
+}
+
+/// Get the snapshots in a backup directory, newest first.
+pub fn mod_settings_backups(backup_dir: &Path) -> Vec<ModSettingsSnapshot> {
+    let Ok(entries) = fs::read_dir(backup_dir) else {
+        return Vec::new();
+    };
+    let mut snapshots: Vec<ModSettingsSnapshot> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .filter_map(|path| ModSettingsSnapshot::read(&path))
+        .collect();
+    snapshots.sort_by(|a, b| b.path.cmp(&a.path));
+    snapshots
+}
+
+/// Copy settings files into a new snapshot in a backup directory, along with the active mod
+/// list, keeping earlier snapshots, and return the snapshot directory.
+pub fn backup_mod_settings(
+    files: &[ModSettingsFile],
+    active_mods: &[String],
+    backup_dir: &Path,
+) -> anyhow::Result<PathBuf> {
+    let timestamp = Utc::now().format(SNAPSHOT_DIR_FORMAT).to_string();
+    // backups made within the same millisecond get a suffix
+    let snapshot_dir = (0..)
+        .map(|n| match n {
+            0 => backup_dir.join(&timestamp),
+            n => backup_dir.join(format!("{timestamp}-{n}")),
+        })
+        .find(|dir| !dir.exists())
+        .expect("an unused snapshot directory");
+    fs::create_dir_all(&snapshot_dir)
+        .with_context(|| format!("Failed to create backup directory {snapshot_dir:?}"))?;
+    let mod_list_file = snapshot_dir.join(SNAPSHOT_MOD_LIST_FILE);
+    fs::write(&mod_list_file, active_mods.join("\n"))
+        .with_context(|| format!("Failed to write mod list {mod_list_file:?}"))?;
+    for file in files {
+        let target = snapshot_dir.join(file.file_name());
+        fs::copy(&file.path, &target)
+            .with_context(|| format!("Failed to back up {:?} to {target:?}", file.path))?;
+    }
+    Ok(snapshot_dir)
+}
+
+/// Copy settings files from a backup snapshot into the config directory.
+pub fn restore_mod_settings(files: &[ModSettingsFile], config_dir: &Path) -> anyhow::Result<()> {
+    for file in files {
+        let target = config_dir.join(file.file_name());
+        fs::copy(&file.path, &target)
+            .with_context(|| format!("Failed to restore {:?} to {target:?}", file.path))?;
+    }
+    Ok(())
+}
+
+/// Remove settings files, so mods write their defaults the next time the game runs.
+pub fn remove_mod_settings(files: &[ModSettingsFile]) -> anyhow::Result<()> {
+    for file in files {
+        fs::remove_file(&file.path)
+            .with_context(|| format!("Failed to remove mod settings {:?}", file.path))?;
+    }
+    Ok(())
+}
+
+/// Get the file stem without the `Mod_` prefix, if the path is a mod settings file.
+fn settings_stem(path: &Path) -> Option<&str> {
+    path.extension()
+        .filter(|ext| ext.eq_ignore_ascii_case(MOD_SETTINGS_EXTENSION))?;
+    path.file_stem()?
+        .to_str()?
+        .strip_prefix(MOD_SETTINGS_PREFIX)
+        .filter(|stem| !stem.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings_file(dir: &Path, name: &str, contents: &str) -> ModSettingsFile {
+        let path = dir.join(name);
+        fs::write(&path, contents).unwrap();
+        let stem = settings_stem(&path).unwrap();
+        let (owner, class) = stem.rsplit_once('_').unwrap();
+        ModSettingsFile {
+            owner: owner.to_string(),
+            class: class.to_string(),
+            path,
+        }
+    }
+
+    #[test]
+    fn test_find_mod_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in [
+            "Mod_b.mod_Settings.xml",
+            "Mod_a.mod_Settings.XML",
+            "Prefs.xml",
+            "Mod_.xml",
+        ] {
+            fs::write(dir.path().join(name), "").unwrap();
+        }
+        assert_eq!(
+            find_mod_settings(dir.path()),
+            [
+                dir.path().join("Mod_a.mod_Settings.XML"),
+                dir.path().join("Mod_b.mod_Settings.xml"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_backup_and_restore() {
+        let config_dir = tempfile::tempdir().unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        let backup_dir = mod_settings_backup_dir(data_dir.path(), "default");
+        assert!(mod_settings_backups(&backup_dir).is_empty());
+
+        let file = settings_file(config_dir.path(), "Mod_a.mod_Settings.xml", "first");
+        let other = settings_file(config_dir.path(), "Mod_b.mod_Settings.xml", "other");
+        let first = backup_mod_settings(
+            &[file.clone(), other.clone()],
+            &["a.mod".to_string(), "b.mod".to_string()],
+            &backup_dir,
+        )
+        .unwrap();
+        fs::write(&file.path, "second").unwrap();
+        let second = backup_mod_settings(
+            std::slice::from_ref(&file),
+            &["a.mod".to_string()],
+            &backup_dir,
+        )
+        .unwrap();
+
+        // each backup is kept in its own snapshot with its mod list, newest first
+        let snapshots = mod_settings_backups(&backup_dir);
+        assert_eq!(
+            snapshots.iter().map(|s| &s.path).collect::<Vec<_>>(),
+            [&second, &first]
+        );
+        assert!(snapshots.iter().all(|s| s.created.is_some()));
+        assert_eq!(snapshots[0].active_mods, ["a.mod"]);
+        assert_eq!(snapshots[1].active_mods, ["a.mod", "b.mod"]);
+        assert_eq!(snapshots[1].files.len(), 2);
+
+        // restoring the older snapshot for one mod leaves the other settings alone
+        fs::write(&other.path, "changed").unwrap();
+        let mods = [ModMetaData {
+            id: "a.mod".into(),
+            ..Default::default()
+        }];
+        let (linked, _) = link_mod_settings(&snapshots[1].files, &mods);
+        restore_mod_settings(&linked["a.mod"], config_dir.path()).unwrap();
+        assert_eq!(fs::read_to_string(&file.path).unwrap(), "first");
+        assert_eq!(fs::read_to_string(&other.path).unwrap(), "changed");
+
+        remove_mod_settings(std::slice::from_ref(&file)).unwrap();
+        assert!(!file.path.exists());
+    }
+}
@@ -17,7 +17,14 @@ pub enum Order {
     Dependencies,
     /// Sort topologically by dependency
     Topological,
-    // todo: sort by steam details like subscribed, uploaded etc
+    /// Sort by date last updated on the Steam workshop
+    WorkshopUpdated,
+    /// Sort by date last touched by Steam, such as when subscribing
+    WorkshopTouched,
+    /// Sort by size on disk reported by Steam
+    WorkshopSize,
+    /// Sort by computed size on disk
+    DiskSize,
 }
 
 impl Order {
@@ -29,6 +36,10 @@ impl Order {
             Order::Modified => cmp_modified,
             Order::Dependencies => cmp_dependencies,
             Order::Topological => cmp_topological,
+            Order::WorkshopUpdated => cmp_workshop_updated,
+            Order::WorkshopTouched => cmp_workshop_touched,
+            Order::WorkshopSize => cmp_workshop_size,
+            Order::DiskSize => cmp_disk_size,
         }
     }
 }
@@ -42,6 +53,10 @@ impl Display for Order {
             Order::Modified => write!(f, "Date Modified"),
             Order::Dependencies => write!(f, "Dependencies"),
             Order::Topological => write!(f, "Topological"),
+            Order::WorkshopUpdated => write!(f, "Workshop Updated"),
+            Order::WorkshopTouched => write!(f, "Workshop Subscribed"),
+            Order::WorkshopSize => write!(f, "Workshop Size"),
+            Order::DiskSize => write!(f, "Size on Disk"),
         }
     }
 }
@@ -84,6 +99,38 @@ pub(crate) fn cmp_modified(a: &ModMetaData, b: &ModMetaData) -> Ordering {
     }
 }
 
+pub(crate) fn cmp_workshop_updated(a: &ModMetaData, b: &ModMetaData) -> Ordering {
+    let updated = |m: &ModMetaData| m.workshop.as_ref().and_then(|w| w.time_updated);
+    match updated(a).cmp(&updated(b)) {
+        Ordering::Equal => cmp_id(a, b),
+        other => other,
+    }
+}
+
+pub(crate) fn cmp_workshop_touched(a: &ModMetaData, b: &ModMetaData) -> Ordering {
+    let touched = |m: &ModMetaData| m.workshop.as_ref().and_then(|w| w.time_touched);
+    match touched(a).cmp(&touched(b)) {
+        Ordering::Equal => cmp_id(a, b),
+        other => other,
+    }
+}
+
+pub(crate) fn cmp_workshop_size(a: &ModMetaData, b: &ModMetaData) -> Ordering {
+    let size = |m: &ModMetaData| m.workshop.as_ref().and_then(|w| w.size);
+    match size(a).cmp(&size(b)) {
+        Ordering::Equal => cmp_id(a, b),
+        other => other,
+    }
+}
+
+pub(crate) fn cmp_disk_size(a: &ModMetaData, b: &ModMetaData) -> Ordering {
+    let size = |m: &ModMetaData| m.stats.map(|s| s.disk_size);
+    match size(a).cmp(&size(b)) {
+        Ordering::Equal => cmp_id(a, b),
+        other => other,
+    }
+}
+
 pub(crate) fn cmp_dependencies(a: &ModMetaData, b: &ModMetaData) -> Ordering {
     // Force load-after conflict
     let force_after_a = a.force_load_after(&b.id);
@@ -173,7 +220,7 @@ pub(crate) mod tests {
         time::{Duration, SystemTime},
     };
 
-    use crate::game::mods::ModDependency;
+    use crate::game::mods::{ModDependency, ModStats, WorkshopItem};
 
     use super::*;
 
@@ -305,6 +352,60 @@ pub(crate) mod tests {
         assert_eq!(cmp_modified(&b, &b), Ordering::Equal);
     }
 
+    #[test]
+    fn test_cmp_workshop() {
+        let workshop_mod = |id: &str, value: u64| ModMetaData {
+            id: id.to_string(),
+            workshop: Some(WorkshopItem {
+                id: id.to_string(),
+                size: Some(value),
+                time_updated: Some(value),
+                time_touched: Some(value),
+            }),
+            ..Default::default()
+        };
+        let local = make_mod("a");
+        let old = workshop_mod("c", 10);
+        let new = workshop_mod("b", 20);
+
+        for cmp in [
+            cmp_workshop_updated,
+            cmp_workshop_touched,
+            cmp_workshop_size,
+        ] {
+            assert_eq!(cmp(&local, &old), Ordering::Less);
+            assert_eq!(cmp(&old, &new), Ordering::Less);
+            assert_eq!(cmp(&new, &old), Ordering::Greater);
+            assert_eq!(cmp(&new, &new), Ordering::Equal);
+        }
+    }
+
+    #[test]
+    fn test_cmp_disk_size() {
+        let unknown = make_mod("a");
+        let small = ModMetaData {
+            id: "c".into(),
+            stats: Some(ModStats {
+                disk_size: 10,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let large = ModMetaData {
+            id: "b".into(),
+            stats: Some(ModStats {
+                disk_size: 20,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(cmp_disk_size(&unknown, &small), Ordering::Less);
+        assert_eq!(cmp_disk_size(&small, &large), Ordering::Less);
+        assert_eq!(cmp_disk_size(&large, &small), Ordering::Greater);
+        assert_eq!(cmp_disk_size(&large, &large), Ordering::Equal);
+    }
+
     fn make_mod(id: &str) -> ModMetaData {
         ModMetaData {
             id: id.into(),
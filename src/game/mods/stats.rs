@@ -0,0 +1,227 @@
+use std::{
+    ffi::OsStr,
+    fs,
+    iter::Sum,
+    ops::{Add, AddAssign},
+    path::Path,
+    time::SystemTime,
+};
+
+const TEXTURES_DIR: &str = "Textures";
+const ASSEMBLIES_DIR: &str = "Assemblies";
+const DEFS_DIR: &str = "Defs";
+const PATCHES_DIR: &str = "Patches";
+const TEXTURE_EXTENSIONS: &[&str] = &["png", "dds", "jpg", "jpeg", "psd"];
+
+/// Size and content statistics for a mod directory.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ModStats {
+    pub disk_size: u64,
+    pub file_count: u64,
+    pub texture_count: u64,
+    pub texture_bytes: u64,
+    pub assembly_count: u64,
+    pub def_file_count: u64,
+    pub patch_file_count: u64,
+}
+
+impl ModStats {
+    /// Walk a mod directory and collect its statistics.
+    pub fn compute(mod_dir: &Path) -> Self {
+        let mut stats = Self::default();
+        stats.visit_dir(mod_dir, mod_dir);
+        stats
+    }
+
+    fn visit_dir(&mut self, mod_dir: &Path, dir: &Path) {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                log::warn!("error reading directory {dir:?}: {e}");
+                return;
+            }
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            // symlink metadata avoids following links out of (or back into) the mod
+            let Ok(metadata) = fs::symlink_metadata(&path) else {
+                continue;
+            };
+            if metadata.is_dir() {
+                self.visit_dir(mod_dir, &path);
+            } else if metadata.is_file() {
+                self.add_file(path.strip_prefix(mod_dir).unwrap_or(&path), metadata.len());
+            }
+        }
+    }
+
+    fn add_file(&mut self, relative_path: &Path, size: u64) {
+        self.disk_size += size;
+        self.file_count += 1;
+
+        let in_dir = |name: &str| {
+            relative_path
+                .parent()
+                .is_some_and(|parent| parent.iter().any(|c| c.eq_ignore_ascii_case(name)))
+        };
+        let extension = relative_path
+            .extension()
+            .and_then(OsStr::to_str)
+            .map(str::to_ascii_lowercase)
+            .unwrap_or_default();
+
+        if in_dir(TEXTURES_DIR) && TEXTURE_EXTENSIONS.contains(&extension.as_str()) {
+            self.texture_count += 1;
+            self.texture_bytes += size;
+        } else if in_dir(ASSEMBLIES_DIR) && extension == "dll" {
+            self.assembly_count += 1;
+        } else if in_dir(DEFS_DIR) && extension == "xml" {
+            self.def_file_count += 1;
+        } else if in_dir(PATCHES_DIR) && extension == "xml" {
+            self.patch_file_count += 1;
+        }
+    }
+}
+
+/// Get the latest modified time of a directory and everything below it.
+///
+/// Unlike the directory's own modified time, this changes when a nested file is edited.
+pub fn latest_modified(dir: &Path) -> Option<SystemTime> {
+    let mut latest = fs::symlink_metadata(dir).and_then(|m| m.modified()).ok();
+    for entry in fs::read_dir(dir).ok()?.flatten() {
+        let path = entry.path();
+        let Ok(metadata) = fs::symlink_metadata(&path) else {
+            continue;
+        };
+        let modified = match metadata.is_dir() {
+            true => latest_modified(&path),
+            false => metadata.modified().ok(),
+        };
+        latest = latest.max(modified);
+    }
+    latest
+}
+
+impl Add for ModStats {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            disk_size: self.disk_size + other.disk_size,
+            file_count: self.file_count + other.file_count,
+            texture_count: self.texture_count + other.texture_count,
+            texture_bytes: self.texture_bytes + other.texture_bytes,
+            assembly_count: self.assembly_count + other.assembly_count,
+            def_file_count: self.def_file_count + other.def_file_count,
+            patch_file_count: self.patch_file_count + other.patch_file_count,
+        }
+    }
+}
+
+impl AddAssign for ModStats {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sum for ModStats {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
+
+/// Format a byte count for display, e.g. `1.5 MB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    match unit {
+        0 => format!("{bytes} B"),
+        _ => format!("{size:.1} {}", UNITS[unit]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::game::mods::write_test_file;
+
+    #[test]
+    fn test_compute() {
+        let dir = tempfile::tempdir().unwrap();
+        write_test_file(dir.path(), "About/About.xml", [0; 100]);
+        write_test_file(dir.path(), "Textures/Things/Item.png", [0; 1000]);
+        write_test_file(dir.path(), "1.5/Textures/UI/Icon.DDS", [0; 500]);
+        write_test_file(dir.path(), "Textures/readme.txt", [0; 10]);
+        write_test_file(dir.path(), "1.5/Assemblies/Mod.dll", [0; 2000]);
+        write_test_file(dir.path(), "Assemblies/Mod.pdb", [0; 300]);
+        write_test_file(dir.path(), "Defs/ThingDefs.xml", [0; 50]);
+        write_test_file(dir.path(), "1.5/Defs/More/Defs.XML", [0; 60]);
+        write_test_file(dir.path(), "Patches/Patch.xml", [0; 70]);
+
+        let stats = ModStats::compute(dir.path());
+        assert_eq!(
+            stats,
+            ModStats {
+                disk_size: 4090,
+                file_count: 9,
+                texture_count: 2,
+                texture_bytes: 1500,
+                assembly_count: 1,
+                def_file_count: 2,
+                patch_file_count: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_compute_missing_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let stats = ModStats::compute(&dir.path().join("missing"));
+        assert_eq!(stats, ModStats::default());
+    }
+
+    #[test]
+    fn test_latest_modified() {
+        let dir = tempfile::tempdir().unwrap();
+        write_test_file(dir.path(), "About/About.xml", [0; 10]);
+        write_test_file(dir.path(), "Defs/Things/Item.xml", [0; 10]);
+        let before = latest_modified(dir.path()).unwrap();
+
+        // editing a nested file does not touch the top-level directory
+        let edited = before + Duration::from_secs(60);
+        fs::File::options()
+            .write(true)
+            .open(dir.path().join("Defs/Things/Item.xml"))
+            .unwrap()
+            .set_modified(edited)
+            .unwrap();
+        assert_eq!(latest_modified(dir.path()), Some(edited));
+        assert!(latest_modified(&dir.path().join("missing")).is_none());
+    }
+
+    #[test]
+    fn test_sum() {
+        let stats = ModStats {
+            disk_size: 10,
+            file_count: 2,
+            ..Default::default()
+        };
+        let total: ModStats = [stats, stats].into_iter().sum();
+        assert_eq!(total.disk_size, 20);
+        assert_eq!(total.file_count, 4);
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MB");
+    }
+}
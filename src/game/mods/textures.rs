@@ -0,0 +1,338 @@
+use std::{
+    collections::HashSet,
+    ffi::OsStr,
+    fs::{self, File},
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+const TEXTURES_DIR: &str = "Textures";
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+const DDS_MAGIC: &[u8] = b"DDS ";
+const DDS_HEADER_SIZE: u64 = 128;
+const DDS_DX10_HEADER_SIZE: u64 = 20;
+
+/// Default maximum texture resolution before a texture is reported as oversized.
+pub const DEFAULT_MAX_TEXTURE_RESOLUTION: u32 = 2048;
+
+/// PNGs at least this large should ship with a compressed DDS sibling.
+pub const DDS_RECOMMENDED_RESOLUTION: u32 = 1024;
+
+/// Image format of a texture file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextureFormat {
+    Png,
+    Dds,
+}
+
+/// Header information for a single texture file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextureInfo {
+    /// path relative to the mod directory
+    pub path: PathBuf,
+    pub format: TextureFormat,
+    pub width: u32,
+    pub height: u32,
+    /// estimated GPU memory once loaded by the game
+    pub memory: u64,
+}
+
+impl TextureInfo {
+    /// Read the header of a PNG or DDS file, returning None for other files.
+    pub fn read(path: &Path, relative_path: &Path) -> Option<Self> {
+        let mut header = [0; DDS_HEADER_SIZE as usize];
+        let mut file = File::open(path)
+            .map_err(|e| log::warn!("error opening texture {path:?}: {e}"))
+            .ok()?;
+        let len = read_header(&mut file, &mut header);
+        let header = &header[..len];
+
+        let (format, width, height, memory) = if header.starts_with(PNG_SIGNATURE) {
+            let (width, height) = png_dimensions(header)?;
+            // the game decodes PNGs to RGBA32 and generates mipmaps, adding a third
+            let memory = u64::from(width) * u64::from(height) * 4 * 4 / 3;
+            (TextureFormat::Png, width, height, memory)
+        } else if header.starts_with(DDS_MAGIC) {
+            let (width, height, is_dx10) = dds_dimensions(header)?;
+            // dds payloads are uploaded as is, so the file size is a good estimate
+            let mut header_size = DDS_HEADER_SIZE;
+            if is_dx10 {
+                header_size += DDS_DX10_HEADER_SIZE;
+            }
+            let file_size = file.metadata().map(|m| m.len()).unwrap_or_default();
+            (
+                TextureFormat::Dds,
+                width,
+                height,
+                file_size.saturating_sub(header_size),
+            )
+        } else {
+            return None;
+        };
+
+        Some(Self {
+            path: relative_path.to_path_buf(),
+            format,
+            width,
+            height,
+            memory,
+        })
+    }
+
+    /// Get the largest dimension of the texture.
+    pub fn resolution(&self) -> u32 {
+        self.width.max(self.height)
+    }
+}
+
+/// Read as much of the header as is available, ignoring short files.
+fn read_header(file: &mut File, buf: &mut [u8]) -> usize {
+    let mut len = 0;
+    while len < buf.len() {
+        match file.read(&mut buf[len..]) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => len += n,
+        }
+    }
+    len
+}
+
+/// Read width and height from the IHDR chunk, which must come first in a PNG.
+fn png_dimensions(header: &[u8]) -> Option<(u32, u32)> {
+    let ihdr = header.get(PNG_SIGNATURE.len()..PNG_SIGNATURE.len() + 16)?;
+    if &ihdr[4..8] != b"IHDR" {
+        return None;
+    }
+    Some((read_u32_be(&ihdr[8..12]), read_u32_be(&ihdr[12..16])))
+}
+
+/// Read width and height from a DDS header, and whether a DX10 header follows.
+fn dds_dimensions(header: &[u8]) -> Option<(u32, u32, bool)> {
+    if header.len() < DDS_HEADER_SIZE as usize {
+        return None;
+    }
+    let height = read_u32_le(&header[12..16]);
+    let width = read_u32_le(&header[16..20]);
+    let four_cc = &header[84..88];
+    Some((width, height, four_cc == b"DX10"))
+}
+
+fn read_u32_be(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+fn read_u32_le(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+/// Results of analyzing the textures in a mod.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TextureReport {
+    /// textures larger than the maximum resolution
+    pub oversized: Vec<TextureInfo>,
+    /// large PNGs without a DDS file next to them
+    pub missing_dds: Vec<TextureInfo>,
+    /// number of textures analyzed
+    pub texture_count: u64,
+    /// estimated GPU memory for all textures, preferring DDS over PNG where both exist
+    pub memory: u64,
+}
+
+impl TextureReport {
+    /// Scan all `Textures` folders in a mod directory.
+    pub fn analyze(mod_dir: &Path, max_resolution: u32) -> Self {
+        let mut report = Self::default();
+        report.visit_dir(mod_dir, mod_dir, false, max_resolution);
+        report
+    }
+
+    /// Check if the report contains anything worth warning about.
+    pub fn has_warnings(&self) -> bool {
+        !self.oversized.is_empty() || !self.missing_dds.is_empty()
+    }
+
+    fn visit_dir(&mut self, mod_dir: &Path, dir: &Path, in_textures: bool, max_resolution: u32) {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                log::warn!("error reading directory {dir:?}: {e}");
+                return;
+            }
+        };
+
+        let mut files = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(metadata) = fs::symlink_metadata(&path) else {
+                continue;
+            };
+            if metadata.is_dir() {
+                let is_textures = path
+                    .file_name()
+                    .is_some_and(|name| name.eq_ignore_ascii_case(TEXTURES_DIR));
+                self.visit_dir(mod_dir, &path, in_textures || is_textures, max_resolution);
+            } else if in_textures && metadata.is_file() {
+                files.push(path);
+            }
+        }
+
+        let dds_stems: HashSet<String> = files
+            .iter()
+            .filter(|path| has_extension(path, "dds"))
+            .filter_map(|path| path.file_stem()?.to_str())
+            .map(str::to_ascii_lowercase)
+            .collect();
+
+        for path in files {
+            let relative_path = path.strip_prefix(mod_dir).unwrap_or(&path);
+            let Some(texture) = TextureInfo::read(&path, relative_path) else {
+                continue;
+            };
+            self.texture_count += 1;
+
+            let has_dds = texture.format == TextureFormat::Png
+                && path
+                    .file_stem()
+                    .and_then(OsStr::to_str)
+                    .is_some_and(|stem| dds_stems.contains(&stem.to_ascii_lowercase()));
+            // the game loads the dds instead, so the png costs nothing
+            if !has_dds {
+                self.memory += texture.memory;
+            }
+
+            if texture.resolution() > max_resolution {
+                self.oversized.push(texture.clone());
+            }
+            if texture.format == TextureFormat::Png
+                && !has_dds
+                && texture.resolution() >= DDS_RECOMMENDED_RESOLUTION
+            {
+                self.missing_dds.push(texture);
+            }
+        }
+    }
+}
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::mods::write_test_file;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut data = PNG_SIGNATURE.to_vec();
+        data.extend(13u32.to_be_bytes());
+        data.extend(b"IHDR");
+        data.extend(width.to_be_bytes());
+        data.extend(height.to_be_bytes());
+        data.extend([8, 6, 0, 0, 0]);
+        data
+    }
+
+    fn dds(width: u32, height: u32, four_cc: &[u8; 4], payload: usize) -> Vec<u8> {
+        let mut data = vec![0; DDS_HEADER_SIZE as usize];
+        data[..4].copy_from_slice(DDS_MAGIC);
+        data[12..16].copy_from_slice(&height.to_le_bytes());
+        data[16..20].copy_from_slice(&width.to_le_bytes());
+        data[84..88].copy_from_slice(four_cc);
+        if four_cc == b"DX10" {
+            data.extend([0; DDS_DX10_HEADER_SIZE as usize]);
+        }
+        data.extend(vec![0; payload]);
+        data
+    }
+
+    #[test]
+    fn test_png_dimensions() {
+        assert_eq!(png_dimensions(&png(1024, 512)), Some((1024, 512)));
+        // truncated header
+        assert_eq!(png_dimensions(&png(1024, 512)[..20]), None);
+        // first chunk is not IHDR
+        let mut data = png(1024, 512);
+        data[12..16].copy_from_slice(b"IDAT");
+        assert_eq!(png_dimensions(&data), None);
+    }
+
+    #[test]
+    fn test_dds_dimensions() {
+        assert_eq!(
+            dds_dimensions(&dds(256, 128, b"DXT5", 0)),
+            Some((256, 128, false))
+        );
+        assert_eq!(
+            dds_dimensions(&dds(4096, 4096, b"DX10", 0)),
+            Some((4096, 4096, true))
+        );
+        assert_eq!(dds_dimensions(&dds(256, 128, b"DXT5", 0)[..64]), None);
+    }
+
+    #[test]
+    fn test_read_texture_info() {
+        let dir = tempfile::tempdir().unwrap();
+        write_test_file(dir.path(), "a.png", png(64, 32));
+        write_test_file(dir.path(), "b.dds", dds(64, 64, b"DX10", 1000));
+        write_test_file(dir.path(), "c.jpg", b"\xff\xd8\xff\xe0");
+
+        let info = TextureInfo::read(&dir.path().join("a.png"), Path::new("a.png")).unwrap();
+        assert_eq!(info.format, TextureFormat::Png);
+        assert_eq!((info.width, info.height), (64, 32));
+        assert_eq!(info.memory, 64 * 32 * 4 * 4 / 3);
+        assert_eq!(info.resolution(), 64);
+
+        let info = TextureInfo::read(&dir.path().join("b.dds"), Path::new("b.dds")).unwrap();
+        assert_eq!(info.format, TextureFormat::Dds);
+        assert_eq!(info.memory, 1000);
+
+        assert!(TextureInfo::read(&dir.path().join("c.jpg"), Path::new("c.jpg")).is_none());
+    }
+
+    #[test]
+    fn test_analyze() {
+        let dir = tempfile::tempdir().unwrap();
+        write_test_file(dir.path(), "Textures/Big.png", png(4096, 1024));
+        write_test_file(dir.path(), "1.5/Textures/UI/Large.png", png(1024, 1024));
+        write_test_file(dir.path(), "Textures/Compressed.png", png(2048, 2048));
+        write_test_file(
+            dir.path(),
+            "Textures/Compressed.dds",
+            dds(2048, 2048, b"DXT5", 500),
+        );
+        write_test_file(dir.path(), "Textures/Small.png", png(64, 64));
+        // outside a Textures folder
+        write_test_file(dir.path(), "About/Preview.png", png(4096, 4096));
+
+        let report = TextureReport::analyze(dir.path(), 2048);
+        assert_eq!(report.texture_count, 5);
+        assert!(report.has_warnings());
+
+        let paths = |textures: &[TextureInfo]| {
+            let mut paths: Vec<PathBuf> = textures.iter().map(|t| t.path.clone()).collect();
+            paths.sort();
+            paths
+        };
+        assert_eq!(
+            paths(&report.oversized),
+            vec![PathBuf::from("Textures/Big.png")]
+        );
+        assert_eq!(
+            paths(&report.missing_dds),
+            vec![
+                PathBuf::from("1.5/Textures/UI/Large.png"),
+                PathBuf::from("Textures/Big.png"),
+            ]
+        );
+        // the png with a dds next to it is not counted
+        let png_memory = |width: u64, height: u64| width * height * 4 * 4 / 3;
+        assert_eq!(
+            report.memory,
+            png_memory(4096, 1024) + png_memory(1024, 1024) + 500 + png_memory(64, 64)
+        );
+
+        let report = TextureReport::analyze(dir.path(), 4096);
+        assert!(report.oversized.is_empty());
+    }
+}
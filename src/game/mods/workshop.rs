@@ -0,0 +1,128 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use crate::game::{
+    vdf::{self, Object},
+    xml::ParseResult,
+};
+
+/// Steam workshop details for an installed mod, from `appworkshop_294100.acf`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WorkshopItem {
+    /// published file id, also the mod's directory name
+    pub id: String,
+    /// size on disk in bytes
+    pub size: Option<u64>,
+    /// unix time the item was last updated on the workshop
+    pub time_updated: Option<u64>,
+    /// unix time the item was last touched locally, set when subscribing
+    pub time_touched: Option<u64>,
+}
+
+impl WorkshopItem {
+    /// Load all workshop items from an app workshop manifest, keyed by published file id.
+    pub fn load_all(path: &Path) -> ParseResult<HashMap<String, WorkshopItem>> {
+        let content = fs::read_to_string(path).map_err(|e| format!("reading {path:?}: {e}"))?;
+        let root = vdf::parse(&content).map_err(|e| format!("parsing {path:?}: {e}"))?;
+        let app = root
+            .get_object("AppWorkshop")
+            .ok_or_else(|| format!("missing AppWorkshop in {path:?}"))?;
+
+        let mut items: HashMap<String, WorkshopItem> = HashMap::new();
+        // installed items carry the size, item details carry the touched time
+        for section in ["WorkshopItemsInstalled", "WorkshopItemDetails"] {
+            let Some(section) = app.get_object(section) else {
+                continue;
+            };
+            for (id, value) in section.iter() {
+                let Some(details) = value.as_object() else {
+                    continue;
+                };
+                let item = items.entry(id.to_string()).or_insert_with(|| WorkshopItem {
+                    id: id.to_string(),
+                    ..Default::default()
+                });
+                item.size = item.size.or(parse_number(details, "size"));
+                item.time_updated = item.time_updated.or(parse_number(details, "timeupdated"));
+                item.time_touched = item.time_touched.or(parse_number(details, "timetouched"));
+            }
+        }
+        Ok(items)
+    }
+}
+
+fn parse_number(object: &Object, key: &str) -> Option<u64> {
+    object.get_str(key).and_then(|value| value.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+"AppWorkshop"
+{
+	"appid"		"294100"
+	"SizeOnDisk"		"3072"
+	"WorkshopItemsInstalled"
+	{
+		"2009463077"
+		{
+			"size"		"2048"
+			"timeupdated"		"1717171717"
+			"manifest"		"123456789"
+		}
+		"818773962"
+		{
+			"size"		"1024"
+			"timeupdated"		"1700000000"
+		}
+	}
+	"WorkshopItemDetails"
+	{
+		"2009463077"
+		{
+			"manifest"		"123456789"
+			"timeupdated"		"1717171717"
+			"timetouched"		"1718000000"
+		}
+		"1234"
+		{
+			"timetouched"		"1600000000"
+		}
+	}
+}
+"#;
+
+    #[test]
+    fn test_load_all() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("appworkshop_294100.acf");
+        fs::write(&path, MANIFEST).unwrap();
+
+        let items = WorkshopItem::load_all(&path).unwrap();
+        assert_eq!(items.len(), 3);
+        assert_eq!(
+            items["2009463077"],
+            WorkshopItem {
+                id: "2009463077".into(),
+                size: Some(2048),
+                time_updated: Some(1717171717),
+                time_touched: Some(1718000000),
+            }
+        );
+        assert_eq!(items["818773962"].time_touched, None);
+        assert_eq!(items["1234"].size, None);
+        assert_eq!(items["1234"].time_touched, Some(1600000000));
+    }
+
+    #[test]
+    fn test_load_all_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("appworkshop_294100.acf");
+        assert!(WorkshopItem::load_all(&path).is_err());
+
+        fs::write(&path, r#""AppState" { "appid" "294100" }"#).unwrap();
+        let error = WorkshopItem::load_all(&path).unwrap_err();
+        assert!(error.starts_with("missing AppWorkshop"));
+    }
+}
@@ -0,0 +1,555 @@
+//! Parser for the XML patch operations in a mod's `Patches` folders.
+
+use std::{
+    collections::HashSet,
+    fmt::Display,
+    fs::File,
+    io::{BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+use xml::{
+    attribute::OwnedAttribute,
+    reader::{EventReader, XmlEvent as ReaderEvent},
+};
+
+use crate::game::xml::*;
+
+const PATCHES_DIR: &str = "Patches";
+const CLASS_PREFIX: &str = "PatchOperation";
+
+/// The type of a patch operation, from its `Class` attribute.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PatchOperationKind {
+    Add,
+    Insert,
+    Replace,
+    Remove,
+    AddModExtension,
+    SetName,
+    AttributeAdd,
+    AttributeSet,
+    AttributeRemove,
+    Sequence,
+    FindMod,
+    Conditional,
+    Test,
+    /// operation classes from other mods, e.g. `XmlExtensions.PatchOperationSafeAdd`
+    Other(String),
+}
+
+impl PatchOperationKind {
+    fn from_class(class: &str) -> Self {
+        match class.strip_prefix(CLASS_PREFIX).unwrap_or(class) {
+            "Add" => Self::Add,
+            "Insert" => Self::Insert,
+            "Replace" => Self::Replace,
+            "Remove" => Self::Remove,
+            "AddModExtension" => Self::AddModExtension,
+            "SetName" => Self::SetName,
+            "AttributeAdd" => Self::AttributeAdd,
+            "AttributeSet" => Self::AttributeSet,
+            "AttributeRemove" => Self::AttributeRemove,
+            "Sequence" => Self::Sequence,
+            "FindMod" => Self::FindMod,
+            "Conditional" => Self::Conditional,
+            "Test" => Self::Test,
+            _ => Self::Other(class.to_string()),
+        }
+    }
+
+    /// Check if the operation overwrites or deletes its target.
+    pub fn is_destructive(&self) -> bool {
+        matches!(self, Self::Replace | Self::Remove)
+    }
+}
+
+impl Display for PatchOperationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Other(class) => write!(f, "{class}"),
+            kind => write!(f, "{kind:?}"),
+        }
+    }
+}
+
+/// A patch operation, with any nested operations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatchOperation {
+    pub kind: PatchOperationKind,
+    pub xpath: Option<String>,
+    /// mod ids (lowercase) that must all be active for the operation to run
+    pub may_require: Vec<String>,
+    /// mod names checked by FindMod
+    pub mods: Vec<String>,
+    /// operations run by Sequence
+    pub operations: Vec<PatchOperation>,
+    /// operation run by FindMod or Conditional on a match
+    pub on_match: Option<Box<PatchOperation>>,
+    /// operation run by FindMod or Conditional without a match
+    pub on_no_match: Option<Box<PatchOperation>>,
+}
+
+impl PatchOperation {
+    fn new(kind: PatchOperationKind) -> Self {
+        Self {
+            kind,
+            xpath: None,
+            may_require: Vec::new(),
+            mods: Vec::new(),
+            operations: Vec::new(),
+            on_match: None,
+            on_no_match: None,
+        }
+    }
+
+    /// Collect the operations with an xpath that may run with the given active mods.
+    ///
+    /// FindMod is resolved against active mod names. Conditional depends on the
+    /// patched XML, so both of its branches are collected.
+    pub fn collect_targets<'a>(
+        &'a self,
+        active_ids: &HashSet<String>,
+        active_names: &HashSet<&str>,
+        targets: &mut Vec<&'a PatchOperation>,
+    ) {
+        if !self.may_require.iter().all(|id| active_ids.contains(id)) {
+            return;
+        }
+        match self.kind {
+            PatchOperationKind::Sequence => {
+                for operation in &self.operations {
+                    operation.collect_targets(active_ids, active_names, targets);
+                }
+            }
+            PatchOperationKind::FindMod => {
+                let found = self
+                    .mods
+                    .iter()
+                    .any(|name| active_names.contains(name.as_str()));
+                let branch = if found {
+                    &self.on_match
+                } else {
+                    &self.on_no_match
+                };
+                if let Some(operation) = branch {
+                    operation.collect_targets(active_ids, active_names, targets);
+                }
+            }
+            PatchOperationKind::Conditional => {
+                for operation in self.on_match.iter().chain(&self.on_no_match) {
+                    operation.collect_targets(active_ids, active_names, targets);
+                }
+            }
+            _ if self.xpath.is_some() => targets.push(self),
+            _ => {}
+        }
+    }
+
+    /// Get the xpath with whitespace removed and quotes unified, for comparing targets.
+    pub fn normalized_xpath(&self) -> Option<String> {
+        self.xpath.as_ref().map(|xpath| {
+            xpath
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .map(|c| if c == '\'' { '"' } else { c })
+                .collect()
+        })
+    }
+
+    /// Get the defNames the xpath selects, e.g. `X` from `Defs/ThingDef[defName="X"]`.
+    pub fn target_def_names(&self) -> Vec<&str> {
+        let Some(xpath) = &self.xpath else {
+            return Vec::new();
+        };
+        let mut names = Vec::new();
+        let mut rest = xpath.as_str();
+        while let Some(start) = rest.find("defName") {
+            rest = rest[start + "defName".len()..].trim_start();
+            let Some(value) = rest.strip_prefix('=') else {
+                continue;
+            };
+            let value = value.trim_start();
+            let Some(quote) = value.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+                continue;
+            };
+            let value = &value[1..];
+            let Some(end) = value.find(quote) else {
+                break;
+            };
+            names.push(&value[..end]);
+            rest = &value[end + 1..];
+        }
+        names
+    }
+}
+
+/// A patch file and its top level operations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Patch {
+    /// content folder the patch is in, relative to the mod directory
+    pub folder: PathBuf,
+    /// file the patch is in, relative to the content folder
+    pub file: PathBuf,
+    pub operations: Vec<PatchOperation>,
+}
+
+/// Read the patches in each content folder of a mod.
+pub fn load_patches(mod_dir: &Path, folders: &[PathBuf]) -> Vec<Patch> {
+    let mut patches = Vec::new();
+    for folder in folders {
+        let content_dir = mod_dir.join(folder);
+        let files = find_xml_files(&content_dir.join(PATCHES_DIR));
+
+        for path in files {
+            match load_patch_file(&path) {
+                Ok(operations) => patches.push(Patch {
+                    folder: folder.clone(),
+                    file: path
+                        .strip_prefix(&content_dir)
+                        .unwrap_or(&path)
+                        .to_path_buf(),
+                    operations,
+                }),
+                Err(e) => log::warn!("skipping patch file: {e}"),
+            }
+        }
+    }
+    patches
+}
+
+/// Get the patches the game loads from the given content folders, highest priority first.
+///
+/// When the same file exists in several folders, only the highest priority copy is loaded.
+pub fn effective_patches<'a>(patches: &'a [Patch], folders: &[PathBuf]) -> Vec<&'a Patch> {
+    let mut seen = HashSet::new();
+    folders
+        .iter()
+        .flat_map(|folder| patches.iter().filter(move |p| &p.folder == folder))
+        .filter(|p| seen.insert(p.file.to_string_lossy().to_ascii_lowercase()))
+        .collect()
+}
+
+fn load_patch_file(path: &Path) -> ParseResult<Vec<PatchOperation>> {
+    let file = File::open(path).map_err(|e| format!("opening file {path:?}: {e}"))?;
+    let mut events = create_reader(BufReader::new(file));
+    let mut operations = Vec::new();
+    loop {
+        match events.next() {
+            Ok(ReaderEvent::EndDocument) => break,
+            Ok(ReaderEvent::StartElement { name, .. })
+                if name.local_name.eq_ignore_ascii_case("Patch") =>
+            {
+                operations = parse_operation_list(&mut events, path, &name.local_name)?;
+            }
+            Ok(ReaderEvent::StartElement { .. }) => {
+                log::trace!("unexpected root element in {path:?}");
+                skip_element(&mut events)?;
+            }
+            Ok(_) => {}
+            Err(e) => return Err(format!("error parsing patch from {path:?}: {e}")),
+        }
+    }
+    Ok(operations)
+}
+
+/// Parses a list of operations, as `<Operation>` children of `<Patch>` or `<li>` children of `<operations>`.
+fn parse_operation_list<R: Read>(
+    events: &mut EventReader<R>,
+    path: &Path,
+    container_name: &str,
+) -> ParseResult<Vec<PatchOperation>> {
+    let mut operations = Vec::new();
+    loop {
+        match events.next() {
+            Ok(ReaderEvent::StartElement {
+                name, attributes, ..
+            }) => {
+                if let Some(operation) =
+                    parse_operation(events, path, &name.local_name, &attributes)?
+                {
+                    operations.push(operation);
+                }
+            }
+            Ok(ReaderEvent::EndElement { .. }) => break,
+            Ok(_) => {}
+            Err(e) => {
+                return Err(format!("error parsing {container_name} from {path:?}: {e}"));
+            }
+        }
+    }
+    Ok(operations)
+}
+
+/// Parses a single operation element, returning None if it has no `Class` attribute.
+fn parse_operation<R: Read>(
+    events: &mut EventReader<R>,
+    path: &Path,
+    element_name: &str,
+    attributes: &[OwnedAttribute],
+) -> ParseResult<Option<PatchOperation>> {
+    let attribute = |key: &str| {
+        attributes
+            .iter()
+            .find(|attribute| attribute.name.local_name.eq_ignore_ascii_case(key))
+            .map(|attribute| attribute.value.trim())
+    };
+    let Some(class) = attribute("Class") else {
+        log::trace!("skipping {element_name} without class in {path:?}");
+        skip_element(events)?;
+        return Ok(None);
+    };
+
+    let mut operation = PatchOperation::new(PatchOperationKind::from_class(class));
+    if let Some(may_require) = attribute("MayRequire") {
+        operation.may_require = may_require
+            .split(',')
+            .map(|id| id.trim().to_ascii_lowercase())
+            .filter(|id| !id.is_empty())
+            .collect();
+    }
+
+    loop {
+        match events.next() {
+            Ok(ReaderEvent::StartElement {
+                name, attributes, ..
+            }) => match name.local_name.as_str() {
+                "xpath" => {
+                    let xpath = parse_text_element(events, path, &name.local_name)?;
+                    operation.xpath = Some(xpath.trim().to_string());
+                }
+                "mods" => {
+                    operation.mods = parse_string_collection(events, path, &name.local_name)?;
+                }
+                "operations" => {
+                    operation.operations = parse_operation_list(events, path, &name.local_name)?;
+                }
+                "match" => {
+                    operation.on_match =
+                        parse_operation(events, path, "match", &attributes)?.map(Box::new);
+                }
+                "nomatch" => {
+                    operation.on_no_match =
+                        parse_operation(events, path, "nomatch", &attributes)?.map(Box::new);
+                }
+                _ => skip_element(events)?,
+            },
+            Ok(ReaderEvent::EndElement { .. }) => break,
+            Ok(_) => {}
+            Err(e) => {
+                return Err(format!("error parsing {element_name} from {path:?}: {e}"));
+            }
+        }
+    }
+    Ok(Some(operation))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATCH: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<Patch>
+  <Operation Class="PatchOperationReplace">
+    <xpath>Defs/ThingDef[defName="Steel"]/label</xpath>
+    <value><label>steel</label></value>
+  </Operation>
+  <Operation Class="PatchOperationSequence" MayRequire="Ludeon.RimWorld.Biotech">
+    <operations>
+      <li Class="PatchOperationAdd">
+        <xpath>Defs/ThingDef[defName='Gun_Revolver']/comps</xpath>
+      </li>
+      <li Class="PatchOperationRemove" MayRequire="CETeam.CombatExtended">
+        <xpath>Defs/ThingDef[defName="Gun_Revolver"]/verbs</xpath>
+      </li>
+    </operations>
+  </Operation>
+  <Operation Class="PatchOperationFindMod">
+    <mods>
+      <li>Combat Extended</li>
+    </mods>
+    <match Class="PatchOperationRemove">
+      <xpath>Defs/ThingDef[defName = "Gun_Autopistol"]/tools</xpath>
+    </match>
+    <nomatch Class="XmlExtensions.PatchOperationSafeAdd">
+      <xpath>Defs/ThingDef[defName="Gun_Autopistol" or defName='Gun_Shotgun']</xpath>
+    </nomatch>
+  </Operation>
+  <Operation Class="PatchOperationConditional">
+    <xpath>Defs/ThingDef[defName="Wood"]/stuffProps</xpath>
+    <match>
+      <xpath>ignored without a class</xpath>
+    </match>
+    <nomatch Class="PatchOperationAdd">
+      <xpath>Defs/ThingDef[defName="Wood"]</xpath>
+    </nomatch>
+  </Operation>
+  <Operation>
+    <xpath>Defs/ThingDef[defName="NoClass"]</xpath>
+  </Operation>
+</Patch>
+"#;
+
+    fn load(content: &str) -> Vec<PatchOperation> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Patch.xml");
+        std::fs::write(&path, content).unwrap();
+        load_patch_file(&path).unwrap()
+    }
+
+    fn operation(xpath: &str) -> PatchOperation {
+        PatchOperation {
+            xpath: Some(xpath.to_string()),
+            ..PatchOperation::new(PatchOperationKind::Add)
+        }
+    }
+
+    fn targets<'a>(
+        operations: &'a [PatchOperation],
+        active_ids: &[&str],
+        active_names: &[&'static str],
+    ) -> Vec<&'a str> {
+        let active_ids = active_ids.iter().map(|id| id.to_string()).collect();
+        let active_names = active_names.iter().copied().collect();
+        let mut targets = Vec::new();
+        for operation in operations {
+            operation.collect_targets(&active_ids, &active_names, &mut targets);
+        }
+        targets
+            .into_iter()
+            .flat_map(|operation| operation.target_def_names())
+            .collect()
+    }
+
+    #[test]
+    fn test_load_patch_file() {
+        let operations = load(PATCH);
+        assert_eq!(operations.len(), 4);
+
+        let kinds: Vec<&PatchOperationKind> = operations.iter().map(|o| &o.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                &PatchOperationKind::Replace,
+                &PatchOperationKind::Sequence,
+                &PatchOperationKind::FindMod,
+                &PatchOperationKind::Conditional,
+            ]
+        );
+        assert!(operations[0].kind.is_destructive());
+
+        let sequence = &operations[1];
+        assert_eq!(sequence.may_require, vec!["ludeon.rimworld.biotech"]);
+        assert_eq!(sequence.operations.len(), 2);
+        assert_eq!(
+            sequence.operations[1].may_require,
+            vec!["ceteam.combatextended"]
+        );
+
+        let find_mod = &operations[2];
+        assert_eq!(find_mod.mods, vec!["Combat Extended"]);
+        assert_eq!(
+            find_mod.on_no_match.as_ref().unwrap().kind,
+            PatchOperationKind::Other("XmlExtensions.PatchOperationSafeAdd".into())
+        );
+
+        // a match branch without a class is skipped
+        let conditional = &operations[3];
+        assert!(conditional.on_match.is_none());
+        assert!(conditional.on_no_match.is_some());
+    }
+
+    #[test]
+    fn test_collect_targets() {
+        let operations = load(PATCH);
+        assert_eq!(
+            targets(&operations, &[], &[]),
+            vec!["Steel", "Gun_Autopistol", "Gun_Shotgun", "Wood"]
+        );
+        assert_eq!(
+            targets(
+                &operations,
+                &["ludeon.rimworld.biotech"],
+                &["Combat Extended"]
+            ),
+            vec!["Steel", "Gun_Revolver", "Gun_Autopistol", "Wood"]
+        );
+        assert_eq!(
+            targets(
+                &operations,
+                &["ludeon.rimworld.biotech", "ceteam.combatextended"],
+                &[]
+            ),
+            vec![
+                "Steel",
+                "Gun_Revolver",
+                "Gun_Revolver",
+                "Gun_Autopistol",
+                "Gun_Shotgun",
+                "Wood"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_target_def_names() {
+        assert_eq!(
+            operation(r#"Defs/ThingDef[defName="Steel"]"#).target_def_names(),
+            vec!["Steel"]
+        );
+        assert_eq!(
+            operation("Defs/ThingDef[defName='Steel']").target_def_names(),
+            vec!["Steel"]
+        );
+        assert_eq!(
+            operation(r#"Defs/ThingDef[defName = "A" or defName='B']/comps"#).target_def_names(),
+            vec!["A", "B"]
+        );
+        assert!(
+            operation("Defs/ThingDef[@Name=\"BaseGun\"]")
+                .target_def_names()
+                .is_empty()
+        );
+        assert!(
+            operation("Defs/ThingDef[defName]/label")
+                .target_def_names()
+                .is_empty()
+        );
+        assert!(
+            operation(r#"Defs/ThingDef[defName="Unterminated]"#)
+                .target_def_names()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_normalized_xpath() {
+        assert_eq!(
+            operation("Defs/ThingDef[ defName = 'Steel' ]").normalized_xpath(),
+            Some(r#"Defs/ThingDef[defName="Steel"]"#.to_string())
+        );
+    }
+
+    #[test]
+    fn test_effective_patches() {
+        let patch = |folder: &str, file: &str| Patch {
+            folder: PathBuf::from(folder),
+            file: PathBuf::from(file),
+            operations: Vec::new(),
+        };
+        let patches = vec![
+            patch("", "Patches/A.xml"),
+            patch("", "Patches/B.xml"),
+            patch("1.5", "Patches/a.xml"),
+        ];
+        let effective = effective_patches(&patches, &[PathBuf::from("1.5"), PathBuf::new()]);
+        let files: Vec<PathBuf> = effective.iter().map(|p| p.folder.join(&p.file)).collect();
+        assert_eq!(
+            files,
+            vec![
+                PathBuf::from("1.5/Patches/a.xml"),
+                PathBuf::from("Patches/B.xml")
+            ]
+        );
+    }
+}
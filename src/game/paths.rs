@@ -8,12 +8,18 @@ const STEAM_MODS_DIR: &str =
     "~/Library/Application Support/Steam/steamapps/workshop/content/294100";
 #[cfg(target_os = "macos")]
 const CONFIG_DIR: &str = "~/Library/Application Support/Rimworld/Config";
+#[cfg(target_os = "macos")]
+const GAME_EXECUTABLE: &str = "Contents/MacOS/RimWorld by Ludeon Studios";
+#[cfg(target_os = "macos")]
+const LOG_DIR: &str = "~/Library/Logs/Ludeon Studios/RimWorld by Ludeon Studios";
 
 #[cfg(target_os = "windows")]
 const GAME_DIR: &str = "C:\\Program Files (x86)\\Steam\\steamapps\\common\\RimWorld";
 #[cfg(target_os = "windows")]
 const STEAM_MODS_DIR: &str = "C:\\Program Files (x86)\\Steam\\steamapps\\workshop\\content\\294100";
 #[cfg(target_os = "windows")]
+const GAME_EXECUTABLE: &str = "RimWorldWin64.exe";
+#[cfg(target_os = "windows")]
 const CONFIG_DIR: &str = "~\\AppData\\LocalLow\\Ludeon Studios\\RimWorld by Ludeon Studios\\Config";
 
 const LOCAL_MODS_DIR: &str = "Mods";
@@ -23,6 +29,11 @@ const MOD_ABOUT_DIR: &str = "About";
 const MOD_ABOUT_FILE: &str = "About.xml";
 const MOD_PREVIEW_FILE: &str = "Preview.png";
 const MOD_ICON_FILE: &str = "ModIcon.png";
+const MOD_LOAD_FOLDERS_FILE: &str = "LoadFolders.xml";
+const WORKSHOP_MANIFEST_FILE: &str = "appworkshop_294100.acf";
+const PLAYER_LOG_FILE: &str = "Player.log";
+const PLAYER_PREV_LOG_FILE: &str = "Player-prev.log";
+const VERSION_FILE: &str = "Version.txt";
 
 pub fn default_game_dir() -> PathBuf {
     PathBuf::from(shellexpand::tilde(GAME_DIR).as_ref())
@@ -36,6 +47,14 @@ pub fn default_config_dir() -> PathBuf {
     PathBuf::from(shellexpand::tilde(CONFIG_DIR).as_ref())
 }
 
+pub fn game_executable(game_dir: &Path) -> PathBuf {
+    game_dir.join(GAME_EXECUTABLE)
+}
+
+pub fn version_file(game_dir: &Path) -> PathBuf {
+    game_dir.join(VERSION_FILE)
+}
+
 pub fn local_mods_dir(game_dir: &Path) -> PathBuf {
     game_dir.join(LOCAL_MODS_DIR)
 }
@@ -48,6 +67,34 @@ pub fn mods_config_file(config_dir: &Path) -> PathBuf {
     config_dir.join(MODS_CONFIG_FILE)
 }
 
+/// Get the directory the game writes Player.log to.
+#[cfg(target_os = "macos")]
+pub fn log_dir(_config_dir: &Path) -> PathBuf {
+    PathBuf::from(shellexpand::tilde(LOG_DIR).as_ref())
+}
+
+/// Get the directory the game writes Player.log to, which is the parent of the config directory.
+#[cfg(not(target_os = "macos"))]
+pub fn log_dir(config_dir: &Path) -> PathBuf {
+    config_dir.parent().unwrap_or(config_dir).to_path_buf()
+}
+
+pub fn player_log_file(log_dir: &Path) -> PathBuf {
+    log_dir.join(PLAYER_LOG_FILE)
+}
+
+pub fn player_prev_log_file(log_dir: &Path) -> PathBuf {
+    log_dir.join(PLAYER_PREV_LOG_FILE)
+}
+
+/// Get the workshop manifest from the Steam mods directory (`steamapps/workshop/content/294100`).
+pub fn workshop_manifest_file(steam_mods_dir: &Path) -> Option<PathBuf> {
+    steam_mods_dir
+        .parent()
+        .and_then(Path::parent)
+        .map(|workshop_dir| workshop_dir.join(WORKSHOP_MANIFEST_FILE))
+}
+
 pub fn mod_about_file(mod_dir: &Path) -> PathBuf {
     mod_dir.join(MOD_ABOUT_DIR).join(MOD_ABOUT_FILE)
 }
@@ -59,3 +106,7 @@ pub fn mod_preview_file(mod_dir: &Path) -> PathBuf {
 pub fn mod_icon_file(mod_dir: &Path) -> PathBuf {
     mod_dir.join(MOD_ABOUT_DIR).join(MOD_ICON_FILE)
 }
+
+pub fn mod_load_folders_file(mod_dir: &Path) -> PathBuf {
+    mod_dir.join(MOD_LOAD_FOLDERS_FILE)
+}
@@ -0,0 +1,459 @@
+//! Parser for the errors in the game's `Player.log`, and attribution of errors to mods.
+
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::game::paths;
+
+/// Marker of an unresolved def reference, logged while loading XML.
+const CROSS_REFERENCE_MARKER: &str = "Could not resolve cross-reference";
+/// Reference id the game appends to exception messages, which differs between occurrences.
+const REF_PREFIX: &str = "[Ref ";
+
+/// The type of an error found in the log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LogErrorKind {
+    Exception,
+    HarmonyPatch,
+    CrossReference,
+}
+
+impl Display for LogErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LogErrorKind::Exception => write!(f, "Exception"),
+            LogErrorKind::HarmonyPatch => write!(f, "Harmony patch error"),
+            LogErrorKind::CrossReference => write!(f, "Unresolved cross-reference"),
+        }
+    }
+}
+
+/// An error from the log, with repeated occurrences counted once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogError {
+    pub kind: LogErrorKind,
+    /// first line of the error
+    pub message: String,
+    /// remaining lines of the error, including any stack trace
+    pub details: Vec<String>,
+    /// number of times the error was logged
+    pub count: usize,
+    /// log file the error was read from
+    pub file: PathBuf,
+}
+
+/// A mod that errors can be attributed to.
+#[derive(Debug, Clone)]
+pub struct LogMod<'a> {
+    pub id: &'a str,
+    pub name: &'a str,
+    pub path: &'a Path,
+    /// names of the assemblies the mod loads
+    pub assemblies: Vec<&'a str>,
+}
+
+impl LogError {
+    /// Check if the error was read from the log of the previous game session.
+    pub fn is_from_previous_session(&self) -> bool {
+        self.file
+            .parent()
+            .is_some_and(|log_dir| self.file == paths::player_prev_log_file(log_dir))
+    }
+
+    /// Find the mod responsible for the error, returning its index in `mods`.
+    ///
+    /// Mods are matched by file path, packageId, `[Name]` message prefix, then by the
+    /// namespaces in the stack trace against assembly names, topmost frame first.
+    pub fn attribute(&self, mods: &[LogMod]) -> Option<usize> {
+        let text = std::iter::once(&self.message)
+            .chain(&self.details)
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join("\n");
+        let lowercase = text.to_lowercase().replace('\\', "/");
+
+        // prefer the most specific match, e.g. a DLC id over the Core id it starts with
+        let by_path = longest_match(mods, |m| {
+            let path = m.path.to_string_lossy().to_lowercase().replace('\\', "/");
+            let workshop_dir = m
+                .path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .filter(|name| name.chars().all(|c| c.is_ascii_digit()));
+            let matches = (!path.is_empty() && lowercase.contains(&path))
+                || workshop_dir.is_some_and(|dir| lowercase.contains(&format!("/{dir}/")));
+            matches.then_some(path.len())
+        });
+        if by_path.is_some() {
+            return by_path;
+        }
+
+        let by_id = longest_match(mods, |m| {
+            let id = m.id.to_lowercase();
+            (id.contains('.') && lowercase.contains(&id)).then_some(id.len())
+        });
+        if by_id.is_some() {
+            return by_id;
+        }
+
+        if let Some(prefix) = self
+            .message
+            .strip_prefix('[')
+            .and_then(|rest| rest.split_once(']'))
+            .map(|(prefix, _)| prefix.trim())
+        {
+            let by_prefix = mods.iter().position(|m| {
+                m.name.eq_ignore_ascii_case(prefix)
+                    || m.assemblies.iter().any(|a| a.eq_ignore_ascii_case(prefix))
+            });
+            if by_prefix.is_some() {
+                return by_prefix;
+            }
+        }
+
+        self.details
+            .iter()
+            .filter_map(|line| frame_type(line))
+            .find_map(|type_name| {
+                mods.iter().position(|m| {
+                    m.assemblies.iter().any(|assembly| {
+                        let type_name = type_name.to_lowercase();
+                        let assembly = assembly.to_lowercase();
+                        type_name == assembly || type_name.starts_with(&format!("{assembly}."))
+                    })
+                })
+            })
+    }
+}
+
+/// Find the mod with the longest match, returning its index in `mods`.
+fn longest_match(mods: &[LogMod], f: impl Fn(&LogMod) -> Option<usize>) -> Option<usize> {
+    mods.iter()
+        .enumerate()
+        .filter_map(|(ix, m)| f(m).map(|len| (len, ix)))
+        .max_by_key(|(len, _)| *len)
+        .map(|(_, ix)| ix)
+}
+
+/// Get the log files the game writes, the latest first.
+pub fn player_log_files(log_dir: &Path) -> Vec<PathBuf> {
+    [
+        paths::player_log_file(log_dir),
+        paths::player_prev_log_file(log_dir),
+    ]
+    .into_iter()
+    .filter(|path| path.is_file())
+    .collect()
+}
+
+/// Read the errors from each log file, skipping files that cannot be read.
+pub fn load_player_logs(files: &[PathBuf]) -> Vec<LogError> {
+    files
+        .iter()
+        .flat_map(|path| match fs::read(path) {
+            Ok(bytes) => parse_player_log(&String::from_utf8_lossy(&bytes), path),
+            Err(e) => {
+                log::warn!("skipping log file {path:?}: {e}");
+                Vec::new()
+            }
+        })
+        .collect()
+}
+
+/// Parse the errors in a log, which are written as blocks of lines separated by blank lines.
+pub fn parse_player_log(text: &str, file: &Path) -> Vec<LogError> {
+    let mut errors: Vec<LogError> = Vec::new();
+    let mut seen: HashMap<(LogErrorKind, String), usize> = HashMap::new();
+
+    let mut lines = text.lines().peekable();
+    while lines.peek().is_some() {
+        let block: Vec<&str> = lines
+            .by_ref()
+            .skip_while(|line| line.trim().is_empty())
+            .take_while(|line| !line.trim().is_empty())
+            .collect();
+        let Some((first, rest)) = block.split_first() else {
+            continue;
+        };
+        let Some(kind) = classify(&block) else {
+            continue;
+        };
+
+        let message = strip_ref(first.trim());
+        let key = (kind, message.clone());
+        if let Some(&ix) = seen.get(&key) {
+            errors[ix].count += 1;
+            continue;
+        }
+        seen.insert(key, errors.len());
+        errors.push(LogError {
+            kind,
+            message,
+            details: rest
+                .iter()
+                .map(|line| line.trim().to_string())
+                .filter(|line| !line.starts_with(REF_PREFIX))
+                .collect(),
+            count: 1,
+            file: file.to_path_buf(),
+        });
+    }
+    errors
+}
+
+fn classify(block: &[&str]) -> Option<LogErrorKind> {
+    let first = block.first()?;
+    if first.contains(CROSS_REFERENCE_MARKER) {
+        return Some(LogErrorKind::CrossReference);
+    }
+    let has_exception = block
+        .iter()
+        .take(2)
+        .any(|line| line.contains("Exception:") || line.contains("Exception "));
+    if !has_exception {
+        return None;
+    }
+    let is_harmony = block.iter().take(2).any(|line| {
+        line.contains("HarmonyException")
+            || (line.contains("Harmony") && line.to_lowercase().contains("patch"))
+    });
+    match is_harmony {
+        true => Some(LogErrorKind::HarmonyPatch),
+        false => Some(LogErrorKind::Exception),
+    }
+}
+
+/// Remove the `[Ref XXXXXXXX]` id from a message.
+fn strip_ref(message: &str) -> String {
+    match message.find(REF_PREFIX) {
+        Some(start) => {
+            let end = message[start..]
+                .find(']')
+                .map_or(message.len(), |end| start + end + 1);
+            format!("{}{}", &message[..start], &message[end..])
+                .trim()
+                .to_string()
+        }
+        None => message.to_string(),
+    }
+}
+
+/// Get the qualified type of a stack frame, e.g. `HugsLib.Utils.Helper` from
+/// `at HugsLib.Utils.Helper.Run () [0x00000] in <...>:0` or `HugsLib.Utils.Helper:Run ()`.
+fn frame_type(line: &str) -> Option<&str> {
+    let frame = line.trim();
+    let frame = frame.strip_prefix("at ").unwrap_or(frame);
+    if frame.starts_with('(') {
+        // wrapper frames of patched or generated methods
+        return None;
+    }
+    let method = frame.split([' ', '(']).next()?;
+    match method.split_once(':') {
+        Some((type_name, _)) => Some(type_name),
+        None => method.rsplit_once('.').map(|(type_name, _)| type_name),
+    }
+    .filter(|type_name| !type_name.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAYER_LOG: &str = "\
+Mono path[0] = 'C:/Games/RimWorld/RimWorldWin64_Data/Managed'
+Initialize engine version: 2022.3.35f1
+
+Exception ticking Pawn_Colonist12 (at (120, 0, 84)): System.NullReferenceException: Object reference not set to an instance of an object
+[Ref 1A2B3C4D]
+ at SmartMedicine.Stock.StockUpUtility.Tick () [0x00012] in <8f2c1e>:0
+ at Verse.ThingWithComps.Tick () [0x00000] in <a1b2c3>:0
+
+Exception ticking Pawn_Colonist12 (at (120, 0, 84)): System.NullReferenceException: Object reference not set to an instance of an object
+[Ref 5E6F7A8B]
+ at SmartMedicine.Stock.StockUpUtility.Tick () [0x00012] in <8f2c1e>:0
+
+HarmonyLib.HarmonyException: Patching exception in method static System.Void Verse.Pawn::Tick()
+ ---> System.Exception: Parameter \"__instance\" not found in method
+ at HarmonyLib.PatchFunctions.UpdateWrapper () [0x00000] in <d0e1f2>:0
+
+Could not resolve cross-reference to Verse.ThingDef named Gun_Missing (wanter=thingDef)
+
+Loaded file (Core) is 42 MB
+";
+
+    fn log_mod<'a>(
+        id: &'a str,
+        name: &'a str,
+        path: &'a str,
+        assemblies: &[&'a str],
+    ) -> LogMod<'a> {
+        LogMod {
+            id,
+            name,
+            path: Path::new(path),
+            assemblies: assemblies.to_vec(),
+        }
+    }
+
+    fn error(message: &str, details: &[&str]) -> LogError {
+        LogError {
+            kind: LogErrorKind::Exception,
+            message: message.to_string(),
+            details: details.iter().map(|line| line.to_string()).collect(),
+            count: 1,
+            file: PathBuf::from("Player.log"),
+        }
+    }
+
+    fn error_at(message: &str) -> LogError {
+        error(message, &[])
+    }
+
+    #[test]
+    fn test_parse_player_log() {
+        let errors = parse_player_log(PLAYER_LOG, Path::new("Player.log"));
+        assert_eq!(errors.len(), 3);
+
+        assert_eq!(errors[0].kind, LogErrorKind::Exception);
+        assert_eq!(
+            errors[0].message,
+            "Exception ticking Pawn_Colonist12 (at (120, 0, 84)): System.NullReferenceException: Object reference not set to an instance of an object"
+        );
+        assert_eq!(
+            errors[0].details,
+            [
+                "at SmartMedicine.Stock.StockUpUtility.Tick () [0x00012] in <8f2c1e>:0",
+                "at Verse.ThingWithComps.Tick () [0x00000] in <a1b2c3>:0",
+            ]
+        );
+        // the repeated exception only differs in its reference id
+        assert_eq!(errors[0].count, 2);
+
+        assert_eq!(errors[1].kind, LogErrorKind::HarmonyPatch);
+        assert_eq!(errors[1].details.len(), 2);
+
+        assert_eq!(errors[2].kind, LogErrorKind::CrossReference);
+        assert_eq!(
+            errors[2].message,
+            "Could not resolve cross-reference to Verse.ThingDef named Gun_Missing (wanter=thingDef)"
+        );
+        assert!(errors[2].details.is_empty());
+    }
+
+    #[test]
+    fn test_load_player_logs() {
+        let dir = tempfile::tempdir().unwrap();
+        let player_log = paths::player_log_file(dir.path());
+        let player_prev_log = paths::player_prev_log_file(dir.path());
+        fs::write(&player_log, PLAYER_LOG).unwrap();
+        fs::write(
+            &player_prev_log,
+            "System.Exception: Old error\n at Old.Mod.Run ()\n",
+        )
+        .unwrap();
+
+        let files = player_log_files(dir.path());
+        assert_eq!(files, [player_log.clone(), player_prev_log.clone()]);
+        let errors = load_player_logs(&files);
+        assert_eq!(errors.len(), 4);
+        assert!(!errors[0].is_from_previous_session());
+        assert_eq!(errors[3].file, player_prev_log);
+        assert!(errors[3].is_from_previous_session());
+    }
+
+    #[test]
+    fn test_attribute_by_path() {
+        let mods = [
+            log_mod(
+                "ludeon.rimworld",
+                "Core",
+                "C:/Games/RimWorld/Data/Core",
+                &[],
+            ),
+            log_mod("a.mod", "A", "C:/Games/RimWorld/Mods/A", &[]),
+            log_mod(
+                "b.mod",
+                "B",
+                "C:/Steam/steamapps/workshop/content/294100/1234567890",
+                &[],
+            ),
+        ];
+        let error = error_at("Exception reading C:\\Games\\RimWorld\\Mods\\A\\Defs\\Things.xml");
+        assert_eq!(error.attribute(&mods), Some(1));
+
+        // workshop mods are matched by their folder, wherever the workshop is
+        let error = error_at("Failed to load D:/Workshop/294100/1234567890/Textures/Item.png");
+        assert_eq!(error.attribute(&mods), Some(2));
+    }
+
+    #[test]
+    fn test_attribute_by_package_id() {
+        let mods = [
+            log_mod("ludeon.rimworld", "Core", "/core", &[]),
+            log_mod("ludeon.rimworld.royalty", "Royalty", "/royalty", &[]),
+            log_mod("nodot", "No Dot", "/nodot", &[]),
+        ];
+        assert_eq!(
+            error_at("Error in Ludeon.RimWorld.Royalty defs").attribute(&mods),
+            Some(1)
+        );
+        assert_eq!(
+            error_at("Error in ludeon.rimworld").attribute(&mods),
+            Some(0)
+        );
+        // ids without a dot are too generic to match
+        assert_eq!(error_at("nodot failed").attribute(&mods), None);
+    }
+
+    #[test]
+    fn test_attribute_by_prefix() {
+        let mods = [
+            log_mod("a.mod", "Smart Medicine", "/a", &[]),
+            log_mod("b.mod", "B", "/b", &["HugsLib"]),
+        ];
+        assert_eq!(
+            error_at("[Smart Medicine] Failed to stock up").attribute(&mods),
+            Some(0)
+        );
+        assert_eq!(
+            error_at("[HugsLib] Settings error").attribute(&mods),
+            Some(1)
+        );
+        assert_eq!(error_at("[Unknown] Error").attribute(&mods), None);
+    }
+
+    #[test]
+    fn test_attribute_by_stack_frame() {
+        let mods = [
+            log_mod("a.mod", "A", "/mods/a", &["SmartMedicine"]),
+            log_mod("b.mod", "B", "/mods/b", &["Verse.Extras"]),
+        ];
+        let errors = parse_player_log(PLAYER_LOG, Path::new("Player.log"));
+        assert_eq!(errors[0].attribute(&mods), Some(0));
+        // frames of the game and Harmony itself are not attributed
+        assert_eq!(errors[1].attribute(&mods), None);
+        assert_eq!(errors[2].attribute(&mods), None);
+
+        // the topmost frame wins, in either stack trace format
+        let error = error(
+            "System.Exception: Failed",
+            &[
+                "(wrapper dynamic-method) Verse.Pawn.Verse.Pawn.Tick_Patch1(Verse.Pawn)",
+                "Verse.Extras.Helper:Run ()",
+                "at SmartMedicine.Stock.Tick ()",
+            ],
+        );
+        assert_eq!(error.attribute(&mods), Some(1));
+    }
+
+    #[test]
+    fn test_strip_ref() {
+        assert_eq!(strip_ref("Error [Ref 1A2B3C4D] here"), "Error  here");
+        assert_eq!(strip_ref("Error [Ref 1A2B"), "Error");
+        assert_eq!(strip_ref("Error"), "Error");
+    }
+}
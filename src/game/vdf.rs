@@ -0,0 +1,228 @@
+//! Parser for Valve's text KeyValues format (VDF), used by Steam's `.acf` and `.vdf` files.
+
+use crate::game::xml::ParseResult;
+
+/// A KeyValues value, either a string or a nested object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    String(String),
+    Object(Object),
+}
+
+impl Value {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            Value::Object(_) => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&Object> {
+        match self {
+            Value::String(_) => None,
+            Value::Object(object) => Some(object),
+        }
+    }
+}
+
+/// An ordered list of key/value pairs. Keys are matched case-insensitively.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Object(Vec<(String, Value)>);
+
+impl Object {
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.0
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v)
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(Value::as_str)
+    }
+
+    pub fn get_object(&self, key: &str) -> Option<&Object> {
+        self.get(key).and_then(Value::as_object)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v))
+    }
+}
+
+/// Parse a KeyValues document into its top-level object.
+pub fn parse(input: &str) -> ParseResult<Object> {
+    let mut tokens = Tokenizer { input, pos: 0 };
+    parse_object(&mut tokens, false)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Token {
+    String(String),
+    Open,
+    Close,
+}
+
+fn parse_object(tokens: &mut Tokenizer, nested: bool) -> ParseResult<Object> {
+    let mut pairs = Vec::new();
+    loop {
+        let key = match tokens.next()? {
+            Some(Token::String(key)) => key,
+            Some(Token::Close) if nested => break,
+            None if !nested => break,
+            Some(Token::Close) => return Err(format!("unexpected '}}' at {}", tokens.pos)),
+            Some(Token::Open) => return Err(format!("unexpected '{{' at {}", tokens.pos)),
+            None => return Err("unexpected end of input, expected '}'".into()),
+        };
+        let value = match tokens.next()? {
+            Some(Token::String(value)) => Value::String(value),
+            Some(Token::Open) => Value::Object(parse_object(tokens, true)?),
+            _ => return Err(format!("missing value for key '{key}' at {}", tokens.pos)),
+        };
+        pairs.push((key, value));
+    }
+    Ok(Object(pairs))
+}
+
+struct Tokenizer<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl Tokenizer<'_> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    /// Skip whitespace, `//` comments and `[$PLATFORM]` conditionals.
+    fn skip_ignored(&mut self) {
+        loop {
+            let rest = &self.input[self.pos..];
+            if rest.starts_with("//") || rest.starts_with('[') {
+                let end = match rest.starts_with('[') {
+                    true => rest.find(']').map(|ix| ix + 1),
+                    false => rest.find('\n'),
+                };
+                self.pos += end.unwrap_or(rest.len());
+            } else if self.peek().is_some_and(char::is_whitespace) {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn next(&mut self) -> ParseResult<Option<Token>> {
+        self.skip_ignored();
+        let Some(c) = self.peek() else {
+            return Ok(None);
+        };
+        match c {
+            '{' => {
+                self.bump();
+                Ok(Some(Token::Open))
+            }
+            '}' => {
+                self.bump();
+                Ok(Some(Token::Close))
+            }
+            '"' => self.quoted().map(|s| Some(Token::String(s))),
+            _ => {
+                let start = self.pos;
+                while self
+                    .peek()
+                    .is_some_and(|c| !c.is_whitespace() && !matches!(c, '{' | '}' | '"'))
+                {
+                    self.bump();
+                }
+                Ok(Some(Token::String(self.input[start..self.pos].to_string())))
+            }
+        }
+    }
+
+    fn quoted(&mut self) -> ParseResult<String> {
+        let start = self.pos;
+        self.bump();
+        let mut value = String::new();
+        loop {
+            match self.bump() {
+                Some('"') => return Ok(value),
+                Some('\\') => match self.bump() {
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    Some(c) => value.push(c),
+                    None => break,
+                },
+                Some(c) => value.push(c),
+                None => break,
+            }
+        }
+        Err(format!("unterminated string starting at {start}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_nested() {
+        let root = parse(
+            r#"
+            "AppWorkshop"
+            {
+                "appid"     "294100"
+                "WorkshopItemsInstalled"
+                {
+                    "818773962"
+                    {
+                        "size"  "1024"
+                    }
+                }
+            }
+            "#,
+        )
+        .unwrap();
+        let app = root.get_object("appworkshop").unwrap();
+        assert_eq!(app.get_str("AppID"), Some("294100"));
+        let item = app
+            .get_object("WorkshopItemsInstalled")
+            .and_then(|items| items.get_object("818773962"))
+            .unwrap();
+        assert_eq!(item.get_str("size"), Some("1024"));
+        assert_eq!(app.get_str("WorkshopItemsInstalled"), None);
+        assert_eq!(root.get("missing"), None);
+    }
+
+    #[test]
+    fn test_parse_escapes_and_comments() {
+        let root = parse(
+            r#"
+            // a comment with "quotes" and {braces}
+            "path"  "C:\\Games\\RimWorld"
+            "text"  "say \"hi\"\n\tbye"
+            unquoted value [$WIN32]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(root.get_str("path"), Some(r"C:\Games\RimWorld"));
+        assert_eq!(root.get_str("text"), Some("say \"hi\"\n\tbye"));
+        assert_eq!(root.get_str("unquoted"), Some("value"));
+        assert_eq!(root.iter().count(), 3);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse(r#""key" "value"#).unwrap_err();
+        assert_eq!(error, "unterminated string starting at 6");
+        assert!(parse(r#""key" { "a" "b""#).is_err());
+        assert!(parse(r#""key" }"#).is_err());
+        assert!(parse(r#""key""#).is_err());
+    }
+}
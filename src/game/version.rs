@@ -0,0 +1,26 @@
+//! The version of the installed game, read from `Version.txt` in the game directory.
+
+use std::{fs, path::Path};
+
+use crate::game::paths;
+
+/// Read the installed game version, e.g. `1.5.4104 rev435`.
+pub fn load_installed_version(game_dir: &Path) -> Option<String> {
+    let path = paths::version_file(game_dir);
+    match fs::read_to_string(&path) {
+        Ok(text) => text
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map(str::to_string),
+        Err(e) => {
+            log::warn!("unable to read game version from {path:?}: {e}");
+            None
+        }
+    }
+}
+
+/// Get the minor version mods declare support for, e.g. `1.5` from `1.5.4104 rev435`.
+pub fn minor_version(version: &str) -> String {
+    version.split('.').take(2).collect::<Vec<_>>().join(".")
+}
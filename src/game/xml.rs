@@ -1,5 +1,9 @@
 use std::io::Read;
-use std::{collections::BTreeMap, path::Path};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
 
 use xml::reader::{EventReader, ParserConfig, XmlEvent as ReaderEvent};
 
@@ -17,6 +21,31 @@ pub fn create_reader<R: Read>(reader: R) -> EventReader<R> {
     EventReader::new_with_config(reader, parser_config)
 }
 
+/// Finds all XML files in a directory and its subdirectories, sorted by path.
+pub fn find_xml_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    collect_xml_files(dir, &mut files);
+    files.sort();
+    files
+}
+
+fn collect_xml_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_xml_files(&path, files);
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("xml"))
+        {
+            files.push(path);
+        }
+    }
+}
+
 /// Generic parser for mapping keys to maps of values (e.g., BTreeMap<String, BTreeMap<String, ModDependency>>).
 /// Each child element's tag is used as the key, and its value is parsed using the provided value_parser function.
 pub fn parse_map_of_maps<R: Read, V>(
@@ -4,6 +4,7 @@ pub mod db;
 pub mod game;
 pub mod menu;
 pub mod project;
+pub mod search;
 pub mod settings;
 pub mod theme;
 pub mod ui;
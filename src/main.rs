@@ -7,7 +7,7 @@ use rimru::{
     db, menu,
     project::Project,
     settings::Settings,
-    ui::{prelude::*, text_input::*},
+    ui::{context_menu::Dismiss, mod_list::*, prelude::*, text_input::*},
     workspace::Workspace,
 };
 
@@ -40,6 +40,21 @@ fn main() {
             KeyBinding::new("cmd-x", Cut, Some("TextInput")),
             KeyBinding::new("home", Home, Some("TextInput")),
             KeyBinding::new("end", End, Some("TextInput")),
+            KeyBinding::new("enter", Confirm, Some("TextInput")),
+            KeyBinding::new("escape", FocusList, Some("ModList > TextInput")),
+            KeyBinding::new("escape", Dismiss, Some("ContextMenu")),
+            KeyBinding::new("up", SelectPrevious, Some("ModListEntries")),
+            KeyBinding::new("down", SelectNext, Some("ModListEntries")),
+            KeyBinding::new("home", SelectFirst, Some("ModListEntries")),
+            KeyBinding::new("end", SelectLast, Some("ModListEntries")),
+            KeyBinding::new("enter", ToggleActivation, Some("ModListEntries")),
+            KeyBinding::new("space", ToggleActivation, Some("ModListEntries")),
+            KeyBinding::new("alt-up", MoveUp, Some("ModListEntries")),
+            KeyBinding::new("alt-down", MoveDown, Some("ModListEntries")),
+            KeyBinding::new("cmd-f", FocusSearch, Some("ModList")),
+            KeyBinding::new("cmd-1", FocusInactiveList, Some("MainPane")),
+            KeyBinding::new("cmd-2", FocusActiveList, Some("MainPane")),
+            KeyBinding::new("tab", ToggleListFocus, Some("MainPane")),
         ]);
 
         let settings = cx.new(|_| Settings::load_or_default());
@@ -1,29 +1,56 @@
 use std::{
-    collections::{HashMap, HashSet, VecDeque},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
     fs::read_dir,
-    path::Path,
+    path::{Path, PathBuf},
+    sync::Arc,
 };
 
-use anyhow::Context as _;
-use chrono::Utc;
-use gpui::{Context, Entity};
+use chrono::{DateTime, Duration, Utc};
+use gpui::{App, Context, Entity, EventEmitter, Task};
 
 use crate::{
-    db::SharedDbPool,
+    db::activations::{ActivationStore, SqliteActivationStore},
+    db::bisect::{BisectStore, SqliteBisectStore},
+    db::defs::{DefIndexRecord, DefRecord, DefStore, SqliteDefStore},
     db::history::{Event, EventType, HistoryStore, SqliteHistoryStore},
-    game::mods::*,
+    db::notes::{NoteStore, SqliteNoteStore},
+    db::stats::{SqliteStatsStore, StatsRecord, StatsStore},
+    db::tags::{SqliteTagStore, TagStore},
+    db::{self, SharedDbPool},
+    game::{
+        defs::{
+            DefConflict, DefEntry, defs_modified, effective_defs, find_def_conflicts, load_defs,
+        },
+        launch::{GameProcess, SAVE_DATA_FOLDER_ARG, split_args, steam_launch_url},
+        mods::*,
+        patches::{Patch, PatchOperation, effective_patches, load_patches},
+        paths,
+        player_log::{LogError, LogMod, load_player_logs, player_log_files},
+        version::{load_installed_version, minor_version},
+    },
+    search::{FilterContext, SearchIndex},
     settings::Settings,
 };
 
-// todo: add mod tagging system for custom filtering
-#[derive(Debug, Clone)]
+mod load_order;
+mod selection;
+
+use selection::Selection;
+
+#[derive(Debug)]
 pub struct Project {
     /// rimru settings
     settings: Entity<Settings>,
 
+    /// id of the instance in use, which partitions history, activations and bisects
+    instance: String,
+
     /// mods configuration loaded from the game
     mods_config: Option<ModsConfigData>,
 
+    /// version of the installed game, read from the game directory
+    installed_version: Option<String>,
+
     /// list of all installed mods (local and steam)
     mods: Vec<ModMetaData>,
 
@@ -39,48 +66,293 @@ pub struct Project {
     /// cached list of inactive mods
     cached_inactive_mods: Vec<ModMetaData>,
 
-    /// current selected mod in rimru
-    selected_mod: Option<ModMetaData>,
+    /// selected mods in rimru, with the current one shown in the details pane
+    selection: Selection,
+
+    /// mod id and history events revealed in the details pane
+    mod_history: Option<(String, Vec<Event>)>,
 
     /// flag to indicate if settings pane is open
     settings_open: bool,
 
+    /// flag to indicate if def browser pane is open
+    def_browser_open: bool,
+
+    /// mod id (lowercase) the def browser is filtered to
+    def_browser_mod_id: Option<String>,
+
     /// map of mod id (lowercase) to mod issues
     mod_issues: HashMap<String, ModIssues>,
 
     /// flag to indicate if only supported mods should be shown
     supported_mods_only: bool,
 
+    /// map of mod id (lowercase) to user-defined tags
+    mod_tags: HashMap<String, BTreeSet<String>>,
+
+    /// map of mod id (lowercase) to personal notes
+    mod_notes: HashMap<String, String>,
+
+    /// set of mod ids (lowercase) installed or updated recently
+    recent_mod_ids: HashSet<String>,
+
+    /// set of mod ids (lowercase) that have been activated at least once
+    activated_mod_ids: HashSet<String>,
+
+    /// set of mod ids (lowercase, without steam suffix) installed more than once
+    duplicate_mod_ids: HashSet<String>,
+
+    /// background task computing mod statistics
+    mod_stats_task: Option<Task<()>>,
+
+    /// map of mod id (lowercase) to texture analysis results
+    texture_reports: HashMap<String, TextureReport>,
+
+    /// maximum texture resolution the texture reports were analyzed with
+    texture_resolution: u32,
+
+    /// background task analyzing mod textures
+    texture_task: Option<Task<()>>,
+
+    /// map of mod id (lowercase) to the assemblies it loads
+    mod_assemblies: HashMap<String, Vec<ModAssembly>>,
+
+    /// background task reading mod assemblies
+    assembly_task: Option<Task<()>>,
+
+    /// map of mod id (lowercase) to the defs in all of its content folders
+    mod_defs: HashMap<String, Arc<Vec<DefEntry>>>,
+
+    /// background task indexing mod defs
+    def_task: Option<Task<()>>,
+
+    /// defs defined by more than one active mod, with the current load order
+    def_conflicts: Vec<DefConflict>,
+
+    /// active mod ids (lowercase) in load order the def conflicts were found for
+    def_conflicts_key: Option<Vec<String>>,
+
+    /// background task looking for def conflicts
+    def_conflict_task: Option<Task<()>>,
+
+    /// map of defName to the mod ids (lowercase) defining it
+    def_owners: HashMap<String, HashSet<String>>,
+
+    /// map of mod id (lowercase) to the patches in all of its content folders
+    mod_patches: HashMap<String, Vec<Patch>>,
+
+    /// background task reading mod patches
+    patch_task: Option<Task<()>>,
+
+    /// map of mod id (lowercase) to the languages in all of its content folders
+    mod_languages: HashMap<String, BTreeMap<String, LanguageEntries>>,
+
+    /// set of mod ids (lowercase) that only provide translations
+    translation_mod_ids: HashSet<String>,
+
+    /// map of mod id (lowercase) to the languages available from it or translation mods
+    supported_languages: HashMap<String, BTreeSet<String>>,
+
+    /// background task reading mod languages
+    language_task: Option<Task<()>>,
+
+    /// errors read from the latest game logs
+    log_errors: Arc<Vec<LogError>>,
+    /// map of mod id (lowercase) to the game log errors attributed to it
+    mod_log_errors: HashMap<String, Vec<LogError>>,
+
+    /// background task reading the game logs
+    log_task: Option<Task<()>>,
+    /// background task attributing the game log errors to mods
+    log_attribution_task: Option<Task<()>>,
+
+    /// map of mod id (lowercase) to the settings files it wrote to the config folder
+    mod_settings: HashMap<String, Vec<ModSettingsFile>>,
+
+    /// settings files in the config folder written by mods that are not installed
+    orphaned_mod_settings: Vec<ModSettingsFile>,
+
+    /// backups of mod settings for the instance in use, newest first
+    mod_settings_backups: Vec<ModSettingsSnapshot>,
+
+    /// bisect in progress to find the mod causing a problem
+    bisect: Option<Bisect>,
+
+    /// game process started by rimru, while it is running
+    game_process: Option<GameProcess>,
+
+    /// task polling the game process until it exits
+    game_task: Option<Task<()>>,
+
+    /// search index over all mods, rebuilt when mods are cached
+    search_index: SearchIndex,
+
+    /// counter incremented whenever search results may have changed
+    search_generation: u64,
+
     /// shared database pool for mod event history
     db_pool: SharedDbPool,
 }
 
+/// Events emitted by the project for other features to react to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProjectEvent {
+    /// the game was started from rimru
+    GameStarted,
+    /// the game started from rimru exited, with its exit code if it has one
+    GameExited(Option<i32>),
+    /// the project was reloaded against another instance
+    InstanceChanged,
+    /// the selected mods changed
+    SelectionChanged,
+}
+
+impl EventEmitter<ProjectEvent> for Project {}
+
+/// Number of days a mod counts as recently installed or updated.
+const RECENT_DAYS: i64 = 7;
+
+/// Number of textures listed per texture warning kind, to keep tooltips readable.
+const MAX_TEXTURE_WARNINGS: usize = 5;
+
+/// Number of patches listed per patch warning kind, to keep tooltips readable.
+const MAX_PATCH_WARNINGS: usize = 5;
+
+/// Number of log errors listed per mod, to keep tooltips readable.
+const MAX_LOG_ERRORS: usize = 5;
+
+/// Interval between checks of whether the game process exited.
+const GAME_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Delay before textures are analyzed again after the maximum resolution setting changes.
+const TEXTURE_REANALYZE_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Get the id used to detect duplicate mods, ignoring case and the Steam copy suffix.
+fn duplicate_key(mod_id: &str) -> String {
+    let mod_id = mod_id.to_ascii_lowercase();
+    match mod_id.strip_suffix("_steam") {
+        Some(id) => id.to_string(),
+        None => mod_id,
+    }
+}
+
 // todo: refactor this into more modules for simple maintenance
 impl Project {
     pub fn new(cx: &mut Context<Self>, settings: Entity<Settings>, db_pool: SharedDbPool) -> Self {
+        let instance = settings.read(cx).instance_id().to_string();
         let mut project = Self {
             settings,
+            instance,
             mods_config: None,
+            installed_version: None,
             mods: Vec::new(),
             active_mod_ids: Vec::new(),
             inactive_mods_order: Order::Name,
             cached_active_mods: Vec::new(),
             cached_inactive_mods: Vec::new(),
-            selected_mod: None,
+            selection: Selection::default(),
+            mod_history: None,
             settings_open: false,
+            def_browser_open: false,
+            def_browser_mod_id: None,
             mod_issues: HashMap::new(),
             supported_mods_only: false,
+            mod_tags: HashMap::new(),
+            mod_notes: HashMap::new(),
+            recent_mod_ids: HashSet::new(),
+            activated_mod_ids: HashSet::new(),
+            duplicate_mod_ids: HashSet::new(),
+            mod_stats_task: None,
+            texture_reports: HashMap::new(),
+            texture_resolution: 0,
+            texture_task: None,
+            mod_assemblies: HashMap::new(),
+            assembly_task: None,
+            mod_defs: HashMap::new(),
+            def_task: None,
+            def_conflicts: Vec::new(),
+            def_conflicts_key: None,
+            def_conflict_task: None,
+            def_owners: HashMap::new(),
+            mod_patches: HashMap::new(),
+            patch_task: None,
+            mod_languages: HashMap::new(),
+            translation_mod_ids: HashSet::new(),
+            supported_languages: HashMap::new(),
+            language_task: None,
+            log_errors: Arc::new(Vec::new()),
+            mod_log_errors: HashMap::new(),
+            log_task: None,
+            log_attribution_task: None,
+            mod_settings: HashMap::new(),
+            orphaned_mod_settings: Vec::new(),
+            mod_settings_backups: Vec::new(),
+            bisect: None,
+            game_process: None,
+            game_task: None,
+            search_index: SearchIndex::default(),
+            search_generation: 0,
             db_pool: db_pool.clone(),
         };
 
-        project.load_mods_config(cx);
-        project.load_mods(cx);
-        project.apply_mods_config();
-        project.update_mod_issues();
-        project.sync_mod_events_with_db();
+        project.load_instance(cx);
+        cx.observe(&project.settings.clone(), |project, settings, cx| {
+            if settings.read(cx).max_texture_resolution() != project.texture_resolution {
+                project.reanalyze_textures(cx);
+            }
+        })
+        .detach();
+        project.load_mod_tags();
+        project.load_mod_notes();
         project
     }
 
+    /// Load the mods, mod order and database partition of the instance in use.
+    fn load_instance(&mut self, cx: &mut Context<Self>) {
+        self.load_mods_config(cx);
+        self.load_mods(cx);
+        self.apply_mods_config(cx);
+        self.update_mod_issues();
+        self.load_activated_mods();
+        self.record_activations();
+        self.load_bisect();
+    }
+
+    /// Switch to a named instance, or to the default setup, and reload the project against it.
+    pub fn switch_instance(&mut self, name: Option<String>, cx: &mut Context<Self>) {
+        let (instance, name) = self.settings.update(cx, |settings, _| {
+            settings.set_active_instance(name);
+            settings.save();
+            (
+                settings.instance_id().to_string(),
+                settings.instance_name().to_string(),
+            )
+        });
+        if instance == self.instance {
+            return;
+        }
+        log::info!("switching to instance '{name}'");
+        self.instance = instance;
+        self.mods_config = None;
+        self.mod_history = None;
+        self.bisect = None;
+        self.load_instance(cx);
+        cx.emit(ProjectEvent::InstanceChanged);
+    }
+
+    /// Add an instance copying the directories in use, and switch to it.
+    pub fn create_instance(&mut self, cx: &mut Context<Self>) {
+        let name = self
+            .settings
+            .update(cx, |settings, _| settings.add_instance());
+        self.switch_instance(Some(name), cx);
+    }
+
+    /// Get the name of the instance in use.
+    pub fn instance_name<'a>(&self, cx: &'a App) -> &'a str {
+        self.settings.read(cx).instance_name()
+    }
+
     /// Load mods configuration from file.
     ///
     /// This function parses the mods configuration from game files and updates the project.
@@ -100,7 +372,7 @@ impl Project {
     /// Apply the loaded mods configuration.
     ///
     /// This function updates the active mods list based on the loaded configuration.
-    pub fn apply_mods_config(&mut self) {
+    pub fn apply_mods_config(&mut self, cx: &mut Context<Self>) {
         log::debug!("applying mods config");
         match self.mods_config {
             Some(ref config) => {
@@ -113,18 +385,34 @@ impl Project {
         }
         self.cache_mods();
         self.update_mod_issues();
+        self.update_def_conflicts(cx);
     }
 
     /// Save mods configuration to file.
     ///
     /// This function updates the mods configuration file with the current active mods list.
     pub fn save_mods_config(&mut self, cx: &mut Context<Self>) {
+        let installed_expansions = self.installed_expansions();
         match &mut self.mods_config {
             Some(mods_config) => {
                 let path = &self.settings.read(cx).mods_config_file();
                 log::info!("saving mods config to {path:?}");
                 mods_config.active_mods = self.active_mod_ids.clone();
-                mods_config.save(path)
+                // the game activates installed expansions it does not know yet on launch, which
+                // would undo deactivating them here
+                for expansion in installed_expansions {
+                    if !mods_config
+                        .known_expansions
+                        .iter()
+                        .any(|id| id.eq_ignore_ascii_case(expansion.id()))
+                    {
+                        mods_config
+                            .known_expansions
+                            .push(expansion.id().to_string());
+                    }
+                }
+                mods_config.save(path);
+                self.record_activations();
             }
             None => {
                 log::error!("no mods config to save");
@@ -138,6 +426,7 @@ impl Project {
     pub fn load_mods(&mut self, cx: &mut Context<Self>) {
         log::debug!("loading mods");
 
+        self.load_installed_version(cx);
         self.mods.clear();
         self.load_official_mods(cx);
         self.load_local_mods(cx);
@@ -149,21 +438,824 @@ impl Project {
             other => other,
         });
 
-        self.selected_mod = self.mods.first().cloned();
+        self.selection.select(self.mods.first());
+        cx.emit(ProjectEvent::SelectionChanged);
+        self.update_duplicate_mods();
         self.cache_mods();
         self.update_mod_issues();
+        self.compute_mod_stats(cx);
+        self.analyze_textures(cx);
+        self.inspect_assemblies(cx);
+        self.index_defs(cx);
+        self.read_patches(cx);
+        self.read_languages(cx);
+        self.read_player_logs(cx);
+        self.load_mod_settings(cx);
+        self.sync_mod_events_with_db();
+        self.load_recent_mods();
+    }
+
+    /// Compute mod statistics in the background, reusing cached stats for unchanged mods.
+    fn compute_mod_stats(&mut self, cx: &mut Context<Self>) {
+        let mods: Vec<(String, PathBuf)> = self
+            .mods
+            .iter()
+            .map(|m| (m.id.clone(), m.path.clone()))
+            .collect();
+        let db_pool = self.db_pool.clone();
+        let instance = self.instance.clone();
+
+        let stats_task = cx.background_spawn(async move {
+            let stats_store = SqliteStatsStore::new(db_pool, &instance);
+            let cached = stats_store.get_all_stats().unwrap_or_else(|e| {
+                log::error!("Failed to load cached mod stats from DB: {e}");
+                HashMap::new()
+            });
+
+            let mut stale = Vec::new();
+            let stats: HashMap<String, ModStats> = mods
+                .into_iter()
+                .map(|(mod_id, path)| {
+                    let path_str = path.to_string_lossy().to_string();
+                    // nested edits don't change the mod directory's own modified time
+                    let modified = latest_modified(&path).map(|t| format!("{t:?}"));
+                    let stats = match cached.get(&mod_id) {
+                        Some(record) if record.path == path_str && record.modified == modified => {
+                            record.stats
+                        }
+                        _ => {
+                            let stats = ModStats::compute(&path);
+                            stale.push(StatsRecord {
+                                mod_id: mod_id.clone(),
+                                path: path_str,
+                                modified,
+                                stats,
+                            });
+                            stats
+                        }
+                    };
+                    (mod_id, stats)
+                })
+                .collect();
+
+            if !stale.is_empty() {
+                log::info!("Computed stats for {} mods", stale.len());
+                if let Err(e) = stats_store.save_stats(&stale) {
+                    log::error!("Failed to cache mod stats in DB: {e}");
+                }
+            }
+            stats
+        });
+
+        self.mod_stats_task = Some(cx.spawn(async move |this, cx| {
+            let stats = stats_task.await;
+            this.update(cx, |project, cx| {
+                project.apply_mod_stats(stats);
+                project.mod_stats_task = None;
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    fn apply_mod_stats(&mut self, stats: HashMap<String, ModStats>) {
+        for mod_meta in self.mods.iter_mut() {
+            mod_meta.stats = stats.get(&mod_meta.id).copied();
+        }
+        if let Some(selected) = self.selection.current_mut() {
+            selected.stats = stats.get(&selected.id).copied();
+        }
+        self.cache_mods();
+    }
+
+    /// Check if mod statistics are still being computed.
+    pub fn is_computing_mod_stats(&self) -> bool {
+        self.mod_stats_task.is_some()
+    }
+
+    /// Get the combined statistics of all active mods with computed stats.
+    pub fn active_mods_stats(&self) -> ModStats {
+        self.cached_active_mods.iter().filter_map(|m| m.stats).sum()
+    }
+
+    /// Analyze mod textures in the background, reporting problems as mod issues.
+    fn analyze_textures(&mut self, cx: &mut Context<Self>) {
+        let max_resolution = self.settings.read(cx).max_texture_resolution();
+        self.texture_resolution = max_resolution;
+        let mods: Vec<(String, PathBuf)> = self
+            .mods
+            .iter()
+            .map(|m| (m.id.to_ascii_lowercase(), m.path.clone()))
+            .collect();
+
+        let texture_task = cx.background_spawn(async move {
+            mods.into_iter()
+                .map(|(mod_id, path)| (mod_id, TextureReport::analyze(&path, max_resolution)))
+                .filter(|(_, report)| report.texture_count > 0)
+                .collect::<HashMap<_, _>>()
+        });
+
+        self.texture_task = Some(cx.spawn(async move |this, cx| {
+            let reports = texture_task.await;
+            this.update(cx, |project, cx| {
+                log::info!("Analyzed textures for {} mods", reports.len());
+                project.texture_reports = reports;
+                project.texture_task = None;
+                project.update_mod_issues();
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    /// Analyze textures again once the maximum resolution setting stops changing.
+    fn reanalyze_textures(&mut self, cx: &mut Context<Self>) {
+        self.texture_task = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor()
+                .timer(TEXTURE_REANALYZE_DELAY)
+                .await;
+            this.update(cx, |project, cx| project.analyze_textures(cx))
+                .ok();
+        }));
+    }
+
+    /// Get the texture analysis for a specific mod id (case-insensitive).
+    pub fn texture_report_for_mod(&self, mod_id: &str) -> Option<&TextureReport> {
+        self.texture_reports.get(&mod_id.to_ascii_lowercase())
+    }
+
+    /// Read mod assemblies in the background, reporting version conflicts as mod issues.
+    ///
+    /// Assemblies are read from every content folder, so conditional folders can be
+    /// resolved later against the current active mods without reading them again.
+    fn inspect_assemblies(&mut self, cx: &mut Context<Self>) {
+        let game_version = self.game_version();
+        let mods: Vec<(String, PathBuf, Vec<PathBuf>)> = self
+            .mods
+            .iter()
+            .map(|m| {
+                let folders = m.content_folders(game_version.as_deref(), None);
+                (m.id.to_ascii_lowercase(), m.path.clone(), folders)
+            })
+            .collect();
+
+        let assembly_task = cx.background_spawn(async move {
+            mods.into_iter()
+                .map(|(mod_id, path, folders)| (mod_id, load_assemblies(&path, &folders)))
+                .filter(|(_, assemblies)| !assemblies.is_empty())
+                .collect::<HashMap<_, _>>()
+        });
+
+        self.assembly_task = Some(cx.spawn(async move |this, cx| {
+            let assemblies = assembly_task.await;
+            this.update(cx, |project, cx| {
+                log::info!("Read assemblies for {} mods", assemblies.len());
+                project.mod_assemblies = assemblies;
+                project.assembly_task = None;
+                // stack frames are attributed by assembly name
+                project.attribute_log_errors(cx);
+                project.update_mod_issues();
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    /// Get the assemblies the game loads for a mod with the current active mods.
+    pub fn assemblies_for_mod(&self, mod_meta: &ModMetaData) -> Vec<&ModAssembly> {
+        self.effective_assemblies(mod_meta, &self.active_id_set())
+    }
+
+    fn effective_assemblies(
+        &self,
+        mod_meta: &ModMetaData,
+        active_ids: &HashSet<String>,
+    ) -> Vec<&ModAssembly> {
+        let Some(assemblies) = self.mod_assemblies.get(&mod_meta.id.to_ascii_lowercase()) else {
+            return Vec::new();
+        };
+        let folders = mod_meta.content_folders(self.game_version().as_deref(), Some(active_ids));
+        effective_assemblies(assemblies, &folders)
+    }
+
+    /// Index mod defs in the background, then look for conflicts between active mods.
+    ///
+    /// Defs are cached per instance in the database and only read again for mods whose path,
+    /// content folders or latest modified time in their `Defs` folders changed.
+    fn index_defs(&mut self, cx: &mut Context<Self>) {
+        let game_version = self.game_version();
+        let mods: Vec<DefIndexRecord> = self
+            .mods
+            .iter()
+            .map(|m| DefIndexRecord {
+                mod_id: m.id.to_ascii_lowercase(),
+                path: m.path.to_string_lossy().to_string(),
+                // set in the background, as it walks the Defs folders
+                modified: None,
+                folders: m.content_folders(game_version.as_deref(), None),
+            })
+            .collect();
+        let db_pool = self.db_pool.clone();
+        let instance = self.instance.clone();
+
+        let def_task = cx.background_spawn(async move {
+            let def_store = SqliteDefStore::new(db_pool, &instance);
+            let index = def_store.get_index().unwrap_or_else(|e| {
+                log::error!("Failed to load def index from DB: {e}");
+                HashMap::new()
+            });
+            let mut cached = def_store.get_all_defs().unwrap_or_else(|e| {
+                log::error!("Failed to load cached defs from DB: {e}");
+                HashMap::new()
+            });
+
+            let installed: HashSet<&str> = mods.iter().map(|m| m.mod_id.as_str()).collect();
+            let removed: Vec<String> = index
+                .keys()
+                .filter(|mod_id| !installed.contains(mod_id.as_str()))
+                .cloned()
+                .collect();
+
+            let mut stale = Vec::new();
+            let mut defs = HashMap::new();
+            for mut record in mods {
+                // nested edits don't change the mod directory's own modified time
+                record.modified = defs_modified(Path::new(&record.path), &record.folders)
+                    .map(|t| format!("{t:?}"));
+                let mod_defs = match index.get(&record.mod_id) {
+                    Some(indexed) if *indexed == record => {
+                        cached.remove(&record.mod_id).unwrap_or_default()
+                    }
+                    _ => {
+                        let mod_defs = load_defs(Path::new(&record.path), &record.folders);
+                        stale.push((record.clone(), mod_defs.clone()));
+                        mod_defs
+                    }
+                };
+                if !mod_defs.is_empty() {
+                    defs.insert(record.mod_id, Arc::new(mod_defs));
+                }
+            }
+
+            if !stale.is_empty() {
+                log::info!("Indexed defs for {} changed mods", stale.len());
+                if let Err(e) = def_store.save_defs(&stale) {
+                    log::error!("Failed to cache mod defs in DB: {e}");
+                }
+            }
+            if !removed.is_empty()
+                && let Err(e) = def_store.remove_mods(&removed)
+            {
+                log::error!("Failed to remove defs of uninstalled mods from DB: {e}");
+            }
+            defs
+        });
+
+        self.def_task = Some(cx.spawn(async move |this, cx| {
+            let defs = def_task.await;
+            this.update(cx, |project, cx| {
+                log::info!("Indexed defs for {} mods", defs.len());
+                project.mod_defs = defs;
+                project.def_task = None;
+                project.def_conflicts_key = None;
+                project.update_def_owners();
+                project.update_def_conflicts(cx);
+                project.update_mod_issues();
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    fn update_def_owners(&mut self) {
+        let mut def_owners: HashMap<String, HashSet<String>> = HashMap::new();
+        for (mod_id, defs) in &self.mod_defs {
+            for def in defs {
+                def_owners
+                    .entry(def.def_name.clone())
+                    .or_default()
+                    .insert(mod_id.clone());
+            }
+        }
+        self.def_owners = def_owners;
+    }
+
+    /// Read mod patches in the background, reporting problems as mod issues.
+    fn read_patches(&mut self, cx: &mut Context<Self>) {
+        let game_version = self.game_version();
+        let mods: Vec<(String, PathBuf, Vec<PathBuf>)> = self
+            .mods
+            .iter()
+            .map(|m| {
+                let folders = m.content_folders(game_version.as_deref(), None);
+                (m.id.to_ascii_lowercase(), m.path.clone(), folders)
+            })
+            .collect();
+
+        let patch_task = cx.background_spawn(async move {
+            mods.into_iter()
+                .map(|(mod_id, path, folders)| (mod_id, load_patches(&path, &folders)))
+                .filter(|(_, patches)| !patches.is_empty())
+                .collect::<HashMap<_, _>>()
+        });
+
+        self.patch_task = Some(cx.spawn(async move |this, cx| {
+            let patches = patch_task.await;
+            this.update(cx, |project, cx| {
+                log::info!("Read patches for {} mods", patches.len());
+                project.mod_patches = patches;
+                project.patch_task = None;
+                project.update_mod_issues();
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    /// Get the patch operations with an xpath that may run for a mod with the given active mods.
+    fn effective_patch_targets(
+        &self,
+        mod_meta: &ModMetaData,
+        active_ids: &HashSet<String>,
+        active_names: &HashSet<&str>,
+    ) -> Vec<&PatchOperation> {
+        let Some(patches) = self.mod_patches.get(&mod_meta.id.to_ascii_lowercase()) else {
+            return Vec::new();
+        };
+        let folders = mod_meta.content_folders(self.game_version().as_deref(), Some(active_ids));
+        let mut targets = Vec::new();
+        for patch in effective_patches(patches, &folders) {
+            for operation in &patch.operations {
+                operation.collect_targets(active_ids, active_names, &mut targets);
+            }
+        }
+        targets
+    }
+
+    /// Read mod languages in the background, including translation mods for other mods.
+    fn read_languages(&mut self, cx: &mut Context<Self>) {
+        let game_version = self.game_version();
+        let mods: Vec<(String, PathBuf, Vec<PathBuf>)> = self
+            .mods
+            .iter()
+            .map(|m| {
+                let folders = m.content_folders(game_version.as_deref(), None);
+                (m.id.to_ascii_lowercase(), m.path.clone(), folders)
+            })
+            .collect();
+
+        let language_task = cx.background_spawn(async move {
+            let mut languages = HashMap::new();
+            let mut translation_mod_ids = HashSet::new();
+            for (mod_id, path, folders) in mods {
+                let mod_languages = load_languages(&path, &folders);
+                if mod_languages.is_empty() {
+                    continue;
+                }
+                if is_translation_mod(&path, &folders) {
+                    translation_mod_ids.insert(mod_id.clone());
+                }
+                languages.insert(mod_id, mod_languages);
+            }
+            (languages, translation_mod_ids)
+        });
+
+        self.language_task = Some(cx.spawn(async move |this, cx| {
+            let (languages, translation_mod_ids) = language_task.await;
+            this.update(cx, |project, cx| {
+                log::info!(
+                    "Read languages for {} mods, {} translation mods",
+                    languages.len(),
+                    translation_mod_ids.len()
+                );
+                project.mod_languages = languages;
+                project.translation_mod_ids = translation_mod_ids;
+                project.language_task = None;
+                project.update_supported_languages();
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    /// Read the errors in the latest game logs in the background, attributing them to mods as issues.
+    pub fn read_player_logs(&mut self, cx: &mut Context<Self>) {
+        let files = player_log_files(&self.settings.read(cx).log_dir());
+        log::debug!("reading game logs {files:?}");
+
+        let log_task = cx.background_spawn(async move { load_player_logs(&files) });
+
+        self.log_task = Some(cx.spawn(async move |this, cx| {
+            let errors = log_task.await;
+            this.update(cx, |project, cx| {
+                log::info!("Read {} errors from game logs", errors.len());
+                project.log_errors = Arc::new(errors);
+                project.log_task = None;
+                project.attribute_log_errors(cx);
+            })
+            .ok();
+        }));
+    }
+
+    /// Attribute the game log errors to mods in the background, when the logs or the assemblies
+    /// of the mods change.
+    fn attribute_log_errors(&mut self, cx: &mut Context<Self>) {
+        let errors = self.log_errors.clone();
+        let mods: Vec<(String, String, PathBuf, Vec<String>)> = self
+            .mods
+            .iter()
+            .map(|m| {
+                let assemblies = self
+                    .mod_assemblies
+                    .get(&m.id.to_ascii_lowercase())
+                    .map(|assemblies| assemblies.iter().map(|a| a.name.clone()).collect())
+                    .unwrap_or_default();
+                (m.id.clone(), m.name.clone(), m.path.clone(), assemblies)
+            })
+            .collect();
+
+        let attribution_task = cx.background_spawn(async move {
+            let log_mods: Vec<LogMod> = mods
+                .iter()
+                .map(|(id, name, path, assemblies)| LogMod {
+                    id,
+                    name,
+                    path,
+                    assemblies: assemblies.iter().map(String::as_str).collect(),
+                })
+                .collect();
+
+            let mut errors_by_mod: HashMap<usize, Vec<LogError>> = HashMap::new();
+            let mut unattributed = 0;
+            for error in errors.iter() {
+                let Some(ix) = error.attribute(&log_mods) else {
+                    unattributed += 1;
+                    continue;
+                };
+                errors_by_mod.entry(ix).or_default().push(error.clone());
+            }
+            if unattributed > 0 {
+                log::debug!("{unattributed} game log errors could not be attributed to a mod");
+            }
+
+            errors_by_mod
+                .into_iter()
+                .map(|(ix, errors)| {
+                    let log_mod = &log_mods[ix];
+                    log::warn!(
+                        "Found {} game log errors for '{}' ({})",
+                        errors.len(),
+                        log_mod.name,
+                        log_mod.id
+                    );
+                    (log_mod.id.to_ascii_lowercase(), errors)
+                })
+                .collect::<HashMap<_, _>>()
+        });
+
+        self.log_attribution_task = Some(cx.spawn(async move |this, cx| {
+            let errors_by_mod = attribution_task.await;
+            this.update(cx, |project, cx| {
+                project.mod_log_errors = errors_by_mod;
+                project.log_attribution_task = None;
+                project.update_mod_issues();
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    /// Find the mod settings files in the config folder and link them to installed mods, and
+    /// the backups of the instance in use.
+    ///
+    /// Files of mods installed in another instance are not orphans, as instances can share a
+    /// config folder.
+    pub fn load_mod_settings(&mut self, cx: &mut Context<Self>) {
+        let settings = self.settings.read(cx);
+        let files = find_mod_settings(settings.config_dir());
+        let (mod_settings, mut orphans) = link_mod_settings(&files, &self.mods);
+        if !orphans.is_empty() {
+            let other_mods: Vec<ModMetaData> = settings
+                .other_mods_dirs()
+                .iter()
+                .filter_map(|dir| read_dir(dir).ok())
+                .flat_map(|entries| entries.flatten().map(|entry| entry.path()))
+                .filter(|path| path.is_dir())
+                .filter_map(|path| ModMetaData::new_local(&path).ok())
+                .collect();
+            let orphan_files: Vec<PathBuf> = orphans.iter().map(|file| file.path.clone()).collect();
+            orphans = link_mod_settings(&orphan_files, &other_mods).1;
+        }
+        log::info!(
+            "Found settings files for {} mods, {} orphaned",
+            mod_settings.len(),
+            orphans.len()
+        );
+        self.mod_settings = mod_settings;
+        self.orphaned_mod_settings = orphans;
+        self.mod_settings_backups = mod_settings_backups(&self.mod_settings_backup_dir());
+    }
+
+    pub fn mod_settings_for(&self, mod_id: &str) -> &[ModSettingsFile] {
+        self.mod_settings
+            .get(&mod_id.to_ascii_lowercase())
+            .map_or(&[], Vec::as_slice)
+    }
+
+    pub fn orphaned_mod_settings(&self) -> &[ModSettingsFile] {
+        &self.orphaned_mod_settings
+    }
+
+    /// Remove a mod settings file, so the mod writes its defaults the next time the game runs.
+    ///
+    /// The file is backed up first, and kept if the backup fails.
+    pub fn reset_mod_settings(&mut self, file: &ModSettingsFile, cx: &mut Context<Self>) {
+        log::info!("resetting mod settings {:?}", file.path);
+        let files = std::slice::from_ref(file);
+        if let Err(e) = self
+            .backup_settings_files(files)
+            .and_then(|_| remove_mod_settings(files))
+        {
+            log::error!("{e:#}");
+        }
+        self.load_mod_settings(cx);
+    }
+
+    /// Remove the settings files of mods that are not installed, backing them up first.
+    pub fn remove_orphaned_mod_settings(&mut self, cx: &mut Context<Self>) {
+        log::info!(
+            "removing {} orphaned mod settings files",
+            self.orphaned_mod_settings.len()
+        );
+        if let Err(e) = self
+            .backup_settings_files(&self.orphaned_mod_settings)
+            .and_then(|_| remove_mod_settings(&self.orphaned_mod_settings))
+        {
+            log::error!("{e:#}");
+        }
+        self.load_mod_settings(cx);
+    }
+
+    fn mod_settings_backup_dir(&self) -> PathBuf {
+        mod_settings_backup_dir(&db::data_dir(), &self.instance)
+    }
+
+    /// Back up the settings files of a mod, along with the current mod list.
+    pub fn backup_mod_settings(&mut self, mod_id: &str, cx: &mut Context<Self>) {
+        let files = self.mod_settings_for(mod_id).to_vec();
+        if let Err(e) = self.backup_settings_files(&files) {
+            log::error!("{e:#}");
+        }
+        self.load_mod_settings(cx);
+    }
+
+    /// Back up settings files into a new snapshot with the active mod list of the instance in use.
+    fn backup_settings_files(&self, files: &[ModSettingsFile]) -> anyhow::Result<()> {
+        if files.is_empty() {
+            return Ok(());
+        }
+        let snapshot_dir =
+            backup_mod_settings(files, &self.active_mod_ids, &self.mod_settings_backup_dir())?;
+        log::info!(
+            "backed up {} mod settings files to {snapshot_dir:?}",
+            files.len()
+        );
+        Ok(())
+    }
+
+    /// Get the backups holding settings files of a mod, newest first, with those files.
+    pub fn mod_settings_backups_for(
+        &self,
+        mod_meta: &ModMetaData,
+    ) -> Vec<(&ModSettingsSnapshot, Vec<ModSettingsFile>)> {
+        let mod_id = mod_meta.id.to_ascii_lowercase();
+        self.mod_settings_backups
+            .iter()
+            .filter_map(|snapshot| {
+                let (mut linked, _) =
+                    link_mod_settings(&snapshot.files, std::slice::from_ref(mod_meta));
+                linked.remove(&mod_id).map(|files| (snapshot, files))
+            })
+            .collect()
+    }
+
+    /// Check if a backup was taken with the current active mod list.
+    pub fn is_current_mod_list(&self, snapshot: &ModSettingsSnapshot) -> bool {
+        snapshot.active_mods == self.active_mod_ids
+    }
+
+    /// Restore backed up settings files into the config folder.
+    ///
+    /// The files they replace are backed up first, and kept if the backup fails.
+    pub fn restore_mod_settings(&mut self, files: &[ModSettingsFile], cx: &mut Context<Self>) {
+        let config_dir = self.settings.read(cx).config_dir().clone();
+        let replaced: Vec<ModSettingsFile> = files
+            .iter()
+            .map(|file| ModSettingsFile {
+                path: config_dir.join(file.file_name()),
+                ..file.clone()
+            })
+            .filter(|file| file.path.is_file())
+            .collect();
+        match self
+            .backup_settings_files(&replaced)
+            .and_then(|_| restore_mod_settings(files, &config_dir))
+        {
+            Ok(()) => log::info!("restored {} mod settings files", files.len()),
+            Err(e) => log::error!("{e:#}"),
+        }
+        self.load_mod_settings(cx);
+    }
+
+    /// Get the installed translation mods targeting a mod, from their dependencies and load order rules.
+    fn translation_mods_for(&self, mod_id: &str) -> Vec<&ModMetaData> {
+        self.mods
+            .iter()
+            .filter(|m| {
+                self.translation_mod_ids
+                    .contains(&m.id.to_ascii_lowercase())
+            })
+            .filter(|m| !m.id.eq_ignore_ascii_case(mod_id))
+            .filter(|m| {
+                m.dependencies
+                    .keys()
+                    .chain(&m.load_after)
+                    .any(|id| id.eq_ignore_ascii_case(mod_id))
+            })
+            .collect()
+    }
+
+    fn update_supported_languages(&mut self) {
+        let mut supported_languages = HashMap::new();
+        for mod_meta in &self.mods {
+            let languages: BTreeSet<String> = self
+                .language_coverage_for_mod(mod_meta)
+                .into_iter()
+                .map(|coverage| coverage.language)
+                .collect();
+            if !languages.is_empty() {
+                supported_languages.insert(mod_meta.id.to_ascii_lowercase(), languages);
+            }
+        }
+        self.supported_languages = supported_languages;
+        self.invalidate_search();
+    }
+
+    /// Get the names of the languages available for a mod id (case-insensitive).
+    pub fn languages_for_mod(&self, mod_id: &str) -> Option<&BTreeSet<String>> {
+        self.supported_languages.get(&mod_id.to_ascii_lowercase())
+    }
+
+    /// Get the languages available for a mod, from the mod itself and translation mods.
+    ///
+    /// Coverage is estimated against the mod's English Keyed entries plus its English
+    /// DefInjected entries, or the number of labelled defs if that is higher, as English
+    /// text is usually written in the defs themselves. Translation mods are only credited with
+    /// the DefInjected entries targeting the mod's defs.
+    pub fn language_coverage_for_mod(&self, mod_meta: &ModMetaData) -> Vec<LanguageCoverage> {
+        let mod_id = mod_meta.id.to_ascii_lowercase();
+        let own_languages = self.mod_languages.get(&mod_id);
+        let english = own_languages
+            .and_then(|languages| languages.get(ENGLISH))
+            .cloned()
+            .unwrap_or_default();
+        let mod_defs = self
+            .mod_defs
+            .get(&mod_id)
+            .map(|defs| defs.as_slice())
+            .unwrap_or_default();
+        let labelled_defs = mod_defs.iter().filter(|def| def.label.is_some()).count();
+        let baseline = english.keyed + english.def_injected.max(labelled_defs);
+
+        let mut coverage: Vec<LanguageCoverage> = own_languages
+            .into_iter()
+            .flatten()
+            .map(|(language, entries)| LanguageCoverage {
+                language: language.clone(),
+                entries: entries.clone(),
+                provider: None,
+                coverage: match language.as_str() {
+                    ENGLISH => None,
+                    _ => entries.coverage(baseline),
+                },
+            })
+            .collect();
+
+        for translation_mod in self.translation_mods_for(&mod_meta.id) {
+            let Some(languages) = self
+                .mod_languages
+                .get(&translation_mod.id.to_ascii_lowercase())
+            else {
+                continue;
+            };
+            coverage.extend(languages.iter().map(|(language, entries)| {
+                let entries = entries.for_defs(mod_defs);
+                LanguageCoverage {
+                    language: language.clone(),
+                    coverage: entries.coverage(baseline),
+                    entries,
+                    provider: Some(translation_mod.name.clone()),
+                }
+            }));
+        }
+        coverage
+    }
+
+    /// Check if mod defs are still being indexed.
+    pub fn is_indexing_defs(&self) -> bool {
+        self.def_task.is_some()
+    }
+
+    /// Look for def conflicts between active mods in the background, unless the active mods
+    /// and their order are unchanged since the last time.
+    fn update_def_conflicts(&mut self, cx: &mut Context<Self>) {
+        let key: Vec<String> = self
+            .cached_active_mods
+            .iter()
+            .map(|m| m.id.to_ascii_lowercase())
+            .collect();
+        if self.def_conflicts_key.as_ref() == Some(&key) {
+            return;
+        }
+        self.def_conflicts_key = Some(key);
+
+        let active_ids = self.active_id_set();
+        let game_version = self.game_version();
+        let mods: Vec<(String, String, Arc<Vec<DefEntry>>, Vec<PathBuf>)> = self
+            .cached_active_mods
+            .iter()
+            .filter_map(|mod_meta| {
+                let defs = self.mod_defs.get(&mod_meta.id.to_ascii_lowercase())?;
+                let folders = mod_meta.content_folders(game_version.as_deref(), Some(&active_ids));
+                Some((
+                    mod_meta.id.clone(),
+                    mod_meta.name.clone(),
+                    defs.clone(),
+                    folders,
+                ))
+            })
+            .collect();
+
+        let conflict_task = cx.background_spawn(async move {
+            find_def_conflicts(mods.iter().map(|(mod_id, mod_name, defs, folders)| {
+                (
+                    mod_id.as_str(),
+                    mod_name.as_str(),
+                    effective_defs(defs, folders),
+                )
+            }))
+        });
+
+        self.def_conflict_task = Some(cx.spawn(async move |this, cx| {
+            let conflicts = conflict_task.await;
+            this.update(cx, |project, cx| {
+                log::info!("Found {} def conflicts", conflicts.len());
+                project.def_conflicts = conflicts;
+                project.def_conflict_task = None;
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    /// Get the def conflicts involving a specific mod id (case-insensitive).
+    pub fn def_conflicts_for_mod(&self, mod_id: &str) -> Vec<&DefConflict> {
+        self.def_conflicts
+            .iter()
+            .filter(|conflict| conflict.involves(mod_id))
+            .collect()
+    }
+
+    /// Get the folders the game loads content from for a mod with the current active mods.
+    pub fn content_folders_for_mod(&self, mod_meta: &ModMetaData) -> Vec<PathBuf> {
+        mod_meta.content_folders(self.game_version().as_deref(), Some(&self.active_id_set()))
+    }
+
+    /// Get the set of active mod ids (lowercase).
+    fn active_id_set(&self) -> HashSet<String> {
+        self.active_mod_ids
+            .iter()
+            .map(|id| id.to_ascii_lowercase())
+            .collect()
+    }
+
+    /// Find mods that share a package id, such as a local mod and its Steam copy.
+    fn update_duplicate_mods(&mut self) {
+        let mut seen = HashSet::new();
+        self.duplicate_mod_ids = self
+            .mods
+            .iter()
+            .map(|m| duplicate_key(&m.id))
+            .filter(|id| !seen.insert(id.clone()))
+            .collect();
     }
 
     fn load_official_mods(&mut self, cx: &mut Context<Self>) {
         let official_mods_dir = self.settings.read(cx).official_mods_dir();
         log::trace!("loading official mods from {official_mods_dir:?}");
-        self.load_mods_from_dir(official_mods_dir, |path| {
+        self.load_mods_from_dir(&official_mods_dir, |path| {
             ModMetaData::new_official(path).map(|mut om| {
-                om.name = match om.id.split('.').next_back() {
-                    Some(name) if name.eq_ignore_ascii_case("rimworld") => "Core".to_string(),
-                    Some(name) => name.to_string(),
-                    None => unreachable!(),
-                };
+                om.name = official_name(&om.id);
                 om
             })
         });
@@ -178,9 +1270,20 @@ impl Project {
     fn load_steam_mods(&mut self, cx: &mut Context<Self>) {
         let steam_mods_dir = self.settings.read(cx).steam_mods_dir();
         log::trace!("loading steam mods from {steam_mods_dir:?}");
+        let workshop_items = match paths::workshop_manifest_file(steam_mods_dir) {
+            Some(path) => WorkshopItem::load_all(&path).unwrap_or_else(|e| {
+                log::warn!("error loading workshop manifest: {e}");
+                HashMap::new()
+            }),
+            None => HashMap::new(),
+        };
         let mods = self.mods.clone();
         self.load_mods_from_dir(steam_mods_dir, move |path| {
             ModMetaData::new_steam(path).map(|mut sm| {
+                sm.workshop = path
+                    .file_name()
+                    .and_then(|name| workshop_items.get(name.to_string_lossy().as_ref()))
+                    .cloned();
                 match mods
                     .iter()
                     .any(|m| m.source == Source::Local && m.id == sm.id)
@@ -227,7 +1330,7 @@ impl Project {
 
     /// Syncs mod events (install, uninstall, update) with the database after loading mods.
     pub fn sync_mod_events_with_db(&self) {
-        let history_store = SqliteHistoryStore::new(self.db_pool.clone());
+        let history_store = SqliteHistoryStore::new(self.db_pool.clone(), &self.instance);
 
         // Get previous state from DB
         let previous_events = match history_store.get_latest_events() {
@@ -303,49 +1406,395 @@ impl Project {
             }
         }
 
-        // Uninstalls
-        for (mod_id, prev_event) in &prev_map {
-            if !curr_map.contains_key(mod_id) {
-                let event = Event {
-                    event_id: 0,
-                    mod_id: mod_id.clone(),
-                    source: prev_event.source.clone(),
-                    steam_app_id: prev_event.steam_app_id.clone(),
-                    event_type: EventType::Uninstall,
-                    timestamp: now.clone(),
-                    version: prev_event.version.clone(),
-                    path: prev_event.path.clone(),
-                    name: prev_event.name.clone(),
-                    authors: prev_event.authors.clone(),
-                    created: prev_event.created.clone(),
-                    modified: prev_event.modified.clone(),
-                };
-                uninstall_events.push(event);
-                log::debug!("Detected uninstall event for mod {mod_id}");
-            }
+        // Uninstalls
+        for (mod_id, prev_event) in &prev_map {
+            if !curr_map.contains_key(mod_id) {
+                let event = Event {
+                    event_id: 0,
+                    mod_id: mod_id.clone(),
+                    source: prev_event.source.clone(),
+                    steam_app_id: prev_event.steam_app_id.clone(),
+                    event_type: EventType::Uninstall,
+                    timestamp: now.clone(),
+                    version: prev_event.version.clone(),
+                    path: prev_event.path.clone(),
+                    name: prev_event.name.clone(),
+                    authors: prev_event.authors.clone(),
+                    created: prev_event.created.clone(),
+                    modified: prev_event.modified.clone(),
+                };
+                uninstall_events.push(event);
+                log::debug!("Detected uninstall event for mod {mod_id}");
+            }
+        }
+
+        let total_installs = install_events.len();
+        let total_updates = update_events.len();
+        let total_uninstalls = uninstall_events.len();
+
+        let mut all_events = Vec::new();
+        all_events.extend(install_events);
+        all_events.extend(update_events);
+        all_events.extend(uninstall_events);
+
+        if !all_events.is_empty() {
+            match history_store.record_events(&all_events) {
+                Ok(_) => log::info!(
+                    "Synced mod events: {total_installs} installs, {total_updates} updates, {total_uninstalls} uninstalls"
+                ),
+                Err(e) => log::error!("Failed to record mod events in bulk: {e}"),
+            }
+        } else {
+            log::info!("No mod event changes detected during sync.");
+        }
+    }
+
+    /// Load mods installed or updated within the recent period from the history store.
+    pub fn load_recent_mods(&mut self) {
+        let history_store = SqliteHistoryStore::new(self.db_pool.clone(), &self.instance);
+        let events = match history_store.get_latest_events() {
+            Ok(events) => events,
+            Err(e) => {
+                log::error!("Failed to get latest mod events from DB: {e}");
+                return;
+            }
+        };
+        let since = Utc::now() - Duration::days(RECENT_DAYS);
+        self.recent_mod_ids = events
+            .into_iter()
+            .filter(|e| matches!(e.event_type, EventType::Install | EventType::Update))
+            .filter(|e| {
+                DateTime::parse_from_rfc3339(&e.timestamp).is_ok_and(|timestamp| timestamp >= since)
+            })
+            .map(|e| e.mod_id.to_ascii_lowercase())
+            .collect();
+        self.invalidate_search();
+    }
+
+    /// Load the mods that have ever been activated from the database.
+    pub fn load_activated_mods(&mut self) {
+        let activation_store = SqliteActivationStore::new(self.db_pool.clone(), &self.instance);
+        match activation_store.get_activated_mod_ids() {
+            Ok(mod_ids) => self.activated_mod_ids = mod_ids,
+            Err(e) => log::error!("Failed to load activated mods from DB: {e}"),
+        }
+        self.invalidate_search();
+    }
+
+    /// Record the current active mods as activated.
+    fn record_activations(&mut self) {
+        let activation_store = SqliteActivationStore::new(self.db_pool.clone(), &self.instance);
+        if let Err(e) = activation_store.record_activations(&self.active_mod_ids) {
+            log::error!("Failed to record mod activations: {e}");
+            return;
+        }
+        self.activated_mod_ids
+            .extend(self.active_mod_ids.iter().cloned());
+        self.invalidate_search();
+    }
+
+    /// Get the data used by quick filters for a specific mod id (case-insensitive).
+    pub fn filter_context(&self, mod_id: &str) -> FilterContext<'_> {
+        let mod_id = mod_id.to_ascii_lowercase();
+        FilterContext {
+            issues: self.mod_issues.get(&mod_id),
+            recent: self.recent_mod_ids.contains(&mod_id),
+            activated: self.activated_mod_ids.contains(&mod_id),
+            duplicate: self.duplicate_mod_ids.contains(&duplicate_key(&mod_id)),
+        }
+    }
+
+    /// Load user-defined mod tags from the database.
+    pub fn load_mod_tags(&mut self) {
+        let tag_store = SqliteTagStore::new(self.db_pool.clone());
+        match tag_store.get_all_tags() {
+            Ok(tags) => self.mod_tags = tags,
+            Err(e) => log::error!("Failed to load mod tags from DB: {e}"),
+        }
+    }
+
+    /// Get the tags for a specific mod id (case-insensitive).
+    pub fn tags_for_mod(&self, mod_id: &str) -> Option<&BTreeSet<String>> {
+        self.mod_tags.get(&mod_id.to_ascii_lowercase())
+    }
+
+    /// Get every tag in use, sorted alphabetically.
+    pub fn all_tags(&self) -> BTreeSet<String> {
+        self.mod_tags.values().flatten().cloned().collect()
+    }
+
+    /// Add a tag to all selected mods.
+    pub fn tag_selected_mods(&mut self, tag: &str) {
+        let mod_ids: Vec<String> = self.selection.ids().cloned().collect();
+        self.tag_mods(&mod_ids, tag);
+    }
+
+    /// Add a tag to each of the mods.
+    pub fn tag_mods(&mut self, mod_ids: &[String], tag: &str) {
+        let tag = tag.trim();
+        if tag.is_empty() {
+            return;
+        }
+        let mod_ids: Vec<String> = mod_ids.iter().map(|id| id.to_ascii_lowercase()).collect();
+        let tag_store = SqliteTagStore::new(self.db_pool.clone());
+        if let Err(e) = tag_store.add_tag(&mod_ids, tag) {
+            log::error!("Failed to add tag {tag} to mods {mod_ids:?}: {e}");
+            return;
+        }
+        for mod_id in mod_ids {
+            self.mod_tags
+                .entry(mod_id)
+                .or_default()
+                .insert(tag.to_string());
+        }
+        self.update_search_index();
+    }
+
+    /// Remove a tag from a mod.
+    pub fn untag_mod(&mut self, mod_id: &str, tag: &str) {
+        let mod_id = mod_id.to_ascii_lowercase();
+        let tag_store = SqliteTagStore::new(self.db_pool.clone());
+        if let Err(e) = tag_store.remove_tag(&mod_id, tag) {
+            log::error!("Failed to remove tag {tag} from mod {mod_id}: {e}");
+            return;
+        }
+        if let Some(tags) = self.mod_tags.get_mut(&mod_id) {
+            tags.remove(tag);
+            if tags.is_empty() {
+                self.mod_tags.remove(&mod_id);
+            }
+        }
+        self.update_search_index();
+    }
+
+    /// Load personal mod notes from the database.
+    pub fn load_mod_notes(&mut self) {
+        let note_store = SqliteNoteStore::new(self.db_pool.clone());
+        match note_store.get_all_notes() {
+            Ok(notes) => self.mod_notes = notes,
+            Err(e) => log::error!("Failed to load mod notes from DB: {e}"),
+        }
+    }
+
+    /// Get the note for a specific mod id (case-insensitive).
+    pub fn note_for_mod(&self, mod_id: &str) -> Option<&String> {
+        self.mod_notes.get(&mod_id.to_ascii_lowercase())
+    }
+
+    /// Set the note for a mod, removing it if the note is empty.
+    pub fn set_mod_note(&mut self, mod_id: &str, note: &str) {
+        let mod_id = mod_id.to_ascii_lowercase();
+        let current = self.mod_notes.get(&mod_id).map(String::as_str);
+        if current.unwrap_or_default() == note {
+            return;
+        }
+        let note_store = SqliteNoteStore::new(self.db_pool.clone());
+        if let Err(e) = note_store.set_note(&mod_id, note) {
+            log::error!("Failed to save note for mod {mod_id}: {e}");
+            return;
+        }
+        match note.trim().is_empty() {
+            true => self.mod_notes.remove(&mod_id),
+            false => self.mod_notes.insert(mod_id, note.to_string()),
+        };
+        self.invalidate_search();
+    }
+
+    /// Load the bisect in progress from the database.
+    pub fn load_bisect(&mut self) {
+        let bisect_store = SqliteBisectStore::new(self.db_pool.clone(), &self.instance);
+        match bisect_store.get_bisect() {
+            Ok(bisect) => self.bisect = bisect,
+            Err(e) => log::error!("Failed to load bisect from DB: {e}"),
+        }
+    }
+
+    /// Get the bisect in progress, if any.
+    pub fn bisect(&self) -> Option<&Bisect> {
+        self.bisect.as_ref()
+    }
+
+    /// Start bisecting the active mods, saving the first half to the game.
+    ///
+    /// Official mods are kept active in every step.
+    pub fn start_bisect(&mut self, cx: &mut Context<Self>) {
+        if self.bisect.is_some() {
+            log::warn!("bisect already in progress");
+            return;
+        }
+        let pinned: HashSet<String> = self
+            .mods
+            .iter()
+            .filter(|m| m.is_official())
+            .map(|m| m.id.to_ascii_lowercase())
+            .collect();
+        let bisect = Bisect::start(&self.active_mod_ids, &pinned, &self.bisect_dependencies());
+        if bisect.is_finished() {
+            log::warn!("not enough active mods to bisect");
+            return;
+        }
+        log::info!("Starting bisect of {} suspects", bisect.suspects().len());
+        self.bisect = Some(bisect);
+        self.apply_bisect_step(cx);
+    }
+
+    /// Report whether the problem happened with the current step, then save the next step to the game.
+    pub fn report_bisect(&mut self, outcome: BisectOutcome, cx: &mut Context<Self>) {
+        let dependencies = self.bisect_dependencies();
+        let Some(bisect) = &mut self.bisect else {
+            log::warn!("no bisect in progress");
+            return;
+        };
+        bisect.report(outcome, &dependencies);
+        match bisect.is_finished() {
+            true => log::info!("Bisect finished with suspects: {:?}", bisect.suspects()),
+            false => log::info!(
+                "Bisect step {}: {} suspects left",
+                bisect.step(),
+                bisect.suspects().len()
+            ),
         }
+        self.apply_bisect_step(cx);
+    }
 
-        let total_installs = install_events.len();
-        let total_updates = update_events.len();
-        let total_uninstalls = uninstall_events.len();
+    /// Stop bisecting, restoring and saving the mod order from before the bisect.
+    pub fn stop_bisect(&mut self, cx: &mut Context<Self>) {
+        let Some(bisect) = self.bisect.take() else {
+            return;
+        };
+        let bisect_store = SqliteBisectStore::new(self.db_pool.clone(), &self.instance);
+        if let Err(e) = bisect_store.clear_bisect() {
+            log::error!("Failed to clear bisect from DB: {e}");
+        }
+        self.active_mod_ids = bisect.original_ids();
+        self.cache_mods();
+        self.update_mod_issues();
+        self.update_def_conflicts(cx);
+        self.save_mods_config(cx);
+    }
 
-        let mut all_events = Vec::new();
-        all_events.extend(install_events);
-        all_events.extend(update_events);
-        all_events.extend(uninstall_events);
+    /// Save the bisect and activate the mods of its current step, keeping the mod order
+    /// unchanged once the bisect is finished.
+    fn apply_bisect_step(&mut self, cx: &mut Context<Self>) {
+        let Some(bisect) = &self.bisect else {
+            return;
+        };
+        let bisect_store = SqliteBisectStore::new(self.db_pool.clone(), &self.instance);
+        if let Err(e) = bisect_store.save_bisect(bisect) {
+            log::error!("Failed to save bisect to DB: {e}");
+        }
+        if bisect.is_finished() {
+            return;
+        }
+        self.active_mod_ids = bisect.active_ids();
+        self.cache_mods();
+        self.update_mod_issues();
+        self.update_def_conflicts(cx);
+        self.save_mods_config(cx);
+    }
 
-        if !all_events.is_empty() {
-            match history_store.record_events(&all_events) {
-                Ok(_) => log::info!(
-                    "Synced mod events: {total_installs} installs, {total_updates} updates, {total_uninstalls} uninstalls"
-                ),
-                Err(e) => log::error!("Failed to record mod events in bulk: {e}"),
+    /// Get the dependencies of each mod, keyed by lowercase mod id.
+    fn bisect_dependencies(&self) -> HashMap<String, Vec<String>> {
+        self.mods
+            .iter()
+            .map(|m| {
+                let dependencies = m
+                    .dependencies
+                    .keys()
+                    .map(|id| id.to_ascii_lowercase())
+                    .collect();
+                (m.id.to_ascii_lowercase(), dependencies)
+            })
+            .collect()
+    }
+
+    /// Launch the game with the configured arguments, saving the mod order first if enabled.
+    ///
+    /// Instances pass their save data folder to the game unless the arguments already do.
+    ///
+    /// Games started from the executable are tracked until they exit, when the game
+    /// logs are read again. Games started through Steam are not tracked.
+    pub fn launch_game(&mut self, cx: &mut Context<Self>) {
+        if self.is_game_running() {
+            log::warn!("game is already running");
+            return;
+        }
+        let settings = self.settings.read(cx);
+        let mut args = split_args(settings.launch_args());
+        if let Some(instance) = settings.active_instance()
+            && !args.iter().any(|arg| arg.starts_with(SAVE_DATA_FOLDER_ARG))
+        {
+            let save_data_dir = instance.save_data_dir();
+            args.push(format!("{SAVE_DATA_FOLDER_ARG}{}", save_data_dir.display()));
+        }
+        let executable = settings.game_executable();
+        let launch_via_steam = settings.launch_via_steam();
+        if settings.save_before_launch() {
+            self.save_mods_config(cx);
+        }
+
+        if launch_via_steam {
+            log::info!("launching game through Steam with args {args:?}");
+            cx.open_url(&steam_launch_url(&args));
+            return;
+        }
+
+        log::info!("launching game {executable:?} with args {args:?}");
+        match GameProcess::spawn(&executable, &args) {
+            Ok(process) => {
+                log::info!("Game started with pid {}", process.pid());
+                self.game_process = Some(process);
+                self.watch_game(cx);
+                cx.emit(ProjectEvent::GameStarted);
             }
-        } else {
-            log::info!("No mod event changes detected during sync.");
+            Err(e) => log::error!("Failed to launch game: {e:#}"),
         }
     }
 
+    /// Check if the game started from rimru is running.
+    pub fn is_game_running(&self) -> bool {
+        self.game_process.is_some()
+    }
+
+    fn watch_game(&mut self, cx: &mut Context<Self>) {
+        self.game_task = Some(cx.spawn(async move |this, cx| {
+            loop {
+                cx.background_executor().timer(GAME_POLL_INTERVAL).await;
+                let exited = this
+                    .update(cx, |project, cx| project.poll_game(cx))
+                    .unwrap_or(true);
+                if exited {
+                    break;
+                }
+            }
+        }));
+    }
+
+    /// Check if the game process exited, returning true once it is no longer tracked.
+    fn poll_game(&mut self, cx: &mut Context<Self>) -> bool {
+        let Some(process) = &mut self.game_process else {
+            return true;
+        };
+        let code = match process.try_wait() {
+            Ok(None) => return false,
+            Ok(Some(status)) => {
+                log::info!("Game exited with {status}");
+                status.code()
+            }
+            Err(e) => {
+                log::error!("Stopped tracking game: {e:#}");
+                None
+            }
+        };
+        self.game_process = None;
+        self.game_task = None;
+        cx.emit(ProjectEvent::GameExited(code));
+        self.read_player_logs(cx);
+        self.load_mod_settings(cx);
+        cx.notify();
+        true
+    }
+
     pub fn settings(&self) -> Entity<Settings> {
         self.settings.clone()
     }
@@ -389,17 +1838,102 @@ impl Project {
 
         self.cached_active_mods = active;
         self.cached_inactive_mods = inactive;
+        self.update_search_index();
+    }
+
+    /// Rebuild the search index from the current mods and tags.
+    fn update_search_index(&mut self) {
+        self.search_index = SearchIndex::new(&self.mods, &self.mod_tags);
+        self.invalidate_search();
+    }
+
+    /// Mark search results as stale.
+    fn invalidate_search(&mut self) {
+        self.search_generation = self.search_generation.wrapping_add(1);
+    }
+
+    pub fn search_index(&self) -> &SearchIndex {
+        &self.search_index
+    }
+
+    /// Get the search generation, which changes whenever search results may have changed.
+    pub fn search_generation(&self) -> u64 {
+        self.search_generation
     }
 
     pub fn selected_mod(&self) -> Option<&ModMetaData> {
-        self.selected_mod.as_ref()
+        self.selection.current()
+    }
+
+    /// Select a single mod, clearing any other selected mods.
+    pub fn select_mod(&mut self, mod_meta: &ModMetaData, cx: &mut Context<Self>) {
+        self.selection.select(Some(mod_meta));
+        cx.emit(ProjectEvent::SelectionChanged);
+    }
+
+    /// Add or remove a mod from the selection, keeping other selected mods.
+    pub fn toggle_mod_selection(&mut self, mod_meta: &ModMetaData, cx: &mut Context<Self>) {
+        self.selection.toggle(mod_meta, &self.mods);
+        cx.emit(ProjectEvent::SelectionChanged);
+    }
+
+    /// Add a range of mods to the selection, making the last one the selected mod.
+    pub fn select_mod_range(&mut self, mods: &[ModMetaData], cx: &mut Context<Self>) {
+        self.selection.extend(mods);
+        cx.emit(ProjectEvent::SelectionChanged);
+    }
+
+    /// Check if a mod is part of the current selection (case-insensitive).
+    pub fn is_selected(&self, mod_id: &str) -> bool {
+        self.selection.contains(mod_id)
+    }
+
+    /// Get the selected mods, active mods first in load order followed by inactive mods.
+    pub fn selected_mods(&self) -> Vec<ModMetaData> {
+        self.cached_active_mods
+            .iter()
+            .chain(self.cached_inactive_mods.iter())
+            .filter(|m| self.is_selected(&m.id))
+            .cloned()
+            .collect()
+    }
+
+    /// Check if a mod is in the active mods list (case-insensitive).
+    pub fn is_active(&self, mod_id: &str) -> bool {
+        self.active_mod_ids
+            .iter()
+            .any(|id| id.eq_ignore_ascii_case(mod_id))
     }
 
-    pub fn select_mod(&mut self, mod_meta: &ModMetaData) {
-        self.selected_mod = Some(mod_meta.clone());
+    /// Select a mod and load its event history for the details pane.
+    pub fn reveal_in_history(&mut self, mod_meta: &ModMetaData, cx: &mut Context<Self>) {
+        self.select_mod(mod_meta, cx);
+        let history_store = SqliteHistoryStore::new(self.db_pool.clone(), &self.instance);
+        match history_store.get_mod_history(&mod_meta.id) {
+            Ok(events) => self.mod_history = Some((mod_meta.id.clone(), events)),
+            Err(e) => log::error!("Failed to get history for mod {}: {e}", mod_meta.id),
+        }
+    }
+
+    /// Get the revealed history events, if they belong to the selected mod.
+    pub fn mod_history(&self) -> Option<&[Event]> {
+        let (mod_id, events) = self.mod_history.as_ref()?;
+        self.selection
+            .current()
+            .filter(|m| &m.id == mod_id)
+            .map(|_| events.as_slice())
+    }
+
+    pub fn close_mod_history(&mut self) {
+        self.mod_history = None;
+    }
+
+    /// Get the number of selected mods.
+    pub fn selected_mods_count(&self) -> usize {
+        self.selection.len()
     }
 
-    pub fn toggle_mod(&mut self, mod_meta: &ModMetaData) {
+    pub fn toggle_mod(&mut self, mod_meta: &ModMetaData, cx: &mut Context<Self>) {
         match self
             .active_mod_ids
             .iter()
@@ -416,57 +1950,83 @@ impl Project {
         }
         self.cache_mods();
         self.update_mod_issues();
+        self.update_def_conflicts(cx);
     }
 
-    pub fn move_active_mod(&mut self, source: String, target: String) -> anyhow::Result<()> {
-        log::debug!("moving mod {source} to {target}");
-        if source == target {
-            return Ok(());
+    /// Activate all given mods that are not already active, appending them in the given order.
+    pub fn activate_mods(&mut self, mods: &[ModMetaData], cx: &mut Context<Self>) {
+        for mod_meta in mods {
+            if !self
+                .active_mod_ids
+                .iter()
+                .any(|id| id.eq_ignore_ascii_case(&mod_meta.id))
+            {
+                self.active_mod_ids.push(mod_meta.id.to_ascii_lowercase());
+                log::info!("activated mod: {}", mod_meta.id);
+            }
         }
+        self.cache_mods();
+        self.update_mod_issues();
+        self.update_def_conflicts(cx);
+    }
 
-        let mut source_index = None;
-        let mut target_index = None;
-        for (i, mod_id) in self.active_mod_ids.iter().enumerate() {
-            if mod_id.eq_ignore_ascii_case(&source) {
-                source_index = Some(i);
-                if target_index.is_some() {
-                    break;
-                }
-            }
-            if mod_id.eq_ignore_ascii_case(&target) {
-                target_index = Some(i);
-                if source_index.is_some() {
-                    break;
-                }
+    /// Deactivate all given mods that are currently active.
+    pub fn deactivate_mods(&mut self, mods: &[ModMetaData], cx: &mut Context<Self>) {
+        self.active_mod_ids.retain(|id| {
+            let deactivate = mods.iter().any(|m| m.id.eq_ignore_ascii_case(id));
+            if deactivate {
+                log::info!("deactivated mod: {id}");
             }
-        }
+            !deactivate
+        });
+        self.cache_mods();
+        self.update_mod_issues();
+        self.update_def_conflicts(cx);
+    }
 
-        let source_index = source_index.with_context(|| "dragged mod is not active {source}")?;
-        let target_index = target_index.with_context(|| "target mod is not active {target}")?;
+    pub fn move_active_mod(&mut self, source: String, target: String) -> anyhow::Result<()> {
+        log::debug!("moving mod {source} to {target}");
+        load_order::move_mod(&mut self.active_mod_ids, &source, &target)?;
+        self.cache_mods();
+        Ok(())
+    }
 
-        let moving = self.active_mod_ids.remove(source_index);
-        self.active_mod_ids.insert(target_index, moving);
+    /// Move a group of active mods as a contiguous block next to the target mod.
+    pub fn move_active_mods(
+        &mut self,
+        sources: &[String],
+        target: String,
+        cx: &mut Context<Self>,
+    ) -> anyhow::Result<()> {
+        log::debug!("moving mods {sources:?} to {target}");
+        load_order::move_mods(&mut self.active_mod_ids, sources, &target)?;
         self.cache_mods();
+        self.update_mod_issues();
+        self.update_def_conflicts(cx);
         Ok(())
     }
 
-    pub fn clear_active_mods(&mut self) {
+    pub fn clear_active_mods(&mut self, cx: &mut Context<Self>) {
         log::info!("clearing active mods");
         self.active_mod_ids.clear();
         self.cache_mods();
         self.update_mod_issues();
+        self.update_def_conflicts(cx);
     }
 
-    pub fn sort_active_mods(&mut self) {
+    pub fn sort_active_mods(&mut self, cx: &mut Context<Self>) {
         log::debug!("sorting active mods");
         let mut active_mods = self.active_mods();
         active_mods.sort_by(Order::Topological.sort_fn());
+        pin_official_mods(&mut active_mods);
         self.active_mod_ids = active_mods
             .iter()
             .map(|m| m.id.to_ascii_lowercase())
             .collect();
         self.cached_active_mods = active_mods;
         self.update_mod_issues();
+        self.update_def_conflicts(cx);
+        self.invalidate_search();
     }
 
     pub fn cycle_inactive_mods_order(&mut self) {
@@ -475,7 +2035,11 @@ impl Project {
             Order::Name => Order::Id,
             Order::Id => Order::Created,
             Order::Created => Order::Modified,
-            Order::Modified => Order::Name,
+            Order::Modified => Order::WorkshopUpdated,
+            Order::WorkshopUpdated => Order::WorkshopTouched,
+            Order::WorkshopTouched => Order::WorkshopSize,
+            Order::WorkshopSize => Order::DiskSize,
+            Order::DiskSize => Order::Name,
             _ => Order::Name,
         };
     }
@@ -509,6 +2073,7 @@ impl Project {
             }
         });
         self.settings_open = !self.settings_open;
+        self.def_browser_open = false;
         self.load_mods(cx);
         self.cache_mods();
         self.update_mod_issues();
@@ -518,10 +2083,89 @@ impl Project {
         self.settings_open
     }
 
+    pub fn toggle_def_browser(&mut self) {
+        self.def_browser_open = !self.def_browser_open;
+    }
+
+    pub fn is_def_browser_open(&self) -> bool {
+        self.def_browser_open && !self.settings_open
+    }
+
+    /// Open the def browser filtered to the defs of a mod.
+    pub fn browse_defs_for_mod(&mut self, mod_meta: &ModMetaData) {
+        self.def_browser_mod_id = Some(mod_meta.id.to_ascii_lowercase());
+        self.def_browser_open = true;
+    }
+
+    /// Get the mod the def browser is filtered to.
+    pub fn def_browser_mod(&self) -> Option<&ModMetaData> {
+        self.find_mod(self.def_browser_mod_id.as_ref()?)
+    }
+
+    pub fn set_def_browser_mod(&mut self, mod_id: Option<&str>) {
+        self.def_browser_mod_id = mod_id.map(|id| id.to_ascii_lowercase());
+    }
+
+    /// Search the def index, limited to the def browser mod if set.
+    pub fn search_defs(&self, search: String, limit: usize, cx: &App) -> Task<Vec<DefRecord>> {
+        let def_store = SqliteDefStore::new(self.db_pool.clone(), &self.instance);
+        let mod_id = self.def_browser_mod_id.clone();
+        cx.background_spawn(async move {
+            def_store
+                .search_defs(&search, mod_id.as_deref(), limit)
+                .unwrap_or_else(|e| {
+                    log::error!("Failed to search defs: {e}");
+                    Vec::new()
+                })
+        })
+    }
+
+    /// Get the path of the file defining a def.
+    pub fn def_file_path(&self, record: &DefRecord) -> Option<PathBuf> {
+        self.find_mod(&record.mod_id)
+            .map(|m| m.path.join(&record.def.folder).join(&record.def.file))
+    }
+
+    /// Get the expansions installed in the game's `Data` folder, in canonical order.
+    pub fn installed_expansions(&self) -> Vec<Expansion> {
+        Expansion::ALL
+            .into_iter()
+            .filter(|expansion| {
+                self.mods
+                    .iter()
+                    .any(|m| m.is_official() && m.id.eq_ignore_ascii_case(expansion.id()))
+            })
+            .collect()
+    }
+
+    /// Find an installed mod by id (case-insensitive).
+    pub fn find_mod(&self, mod_id: &str) -> Option<&ModMetaData> {
+        self.mods.iter().find(|m| m.id.eq_ignore_ascii_case(mod_id))
+    }
+
+    /// Read the version of the installed game from the game directory.
+    pub fn load_installed_version(&mut self, cx: &mut Context<Self>) {
+        self.installed_version = load_installed_version(self.settings.read(cx).game_dir());
+        log::info!("Installed game version: {:?}", self.installed_version);
+    }
+
+    /// Get the minor game version used for compatibility checks, preferring the installed version.
     pub fn game_version(&self) -> Option<String> {
-        self.mods_config
-            .as_ref()
-            .map(|config| config.minor_version())
+        match &self.installed_version {
+            Some(version) => Some(minor_version(version)),
+            None => self
+                .mods_config
+                .as_ref()
+                .map(|config| config.minor_version()),
+        }
+    }
+
+    /// Get the installed and mods config versions if they differ, as they do when the game was
+    /// updated but not launched since.
+    pub fn game_version_mismatch(&self) -> Option<(&str, &str)> {
+        let installed = self.installed_version.as_deref()?;
+        let config = self.mods_config.as_ref()?.version.trim();
+        (!config.is_empty() && installed != config).then_some((installed, config))
     }
 
     /// Get the current map of mod issues for UI presentation.
@@ -547,6 +2191,9 @@ impl Project {
     }
 
     pub fn update_mod_issues(&mut self) {
+        // issues can be searched with `has:`
+        self.invalidate_search();
+
         let active_ids: Vec<String> = self
             .active_mod_ids
             .iter()
@@ -644,6 +2291,11 @@ impl Project {
         let mut issues = HashMap::new();
         self.collect_active_mod_issues(&mut issues);
         self.collect_inactive_mod_issues(&mut issues);
+        self.collect_texture_issues(&mut issues);
+        self.collect_assembly_issues(&mut issues);
+        self.collect_load_folder_issues(&mut issues);
+        self.collect_patch_issues(&mut issues);
+        self.collect_log_issues(&mut issues);
         log::info!("Found {} mod issues", issues.len());
         self.mod_issues = issues;
     }
@@ -666,27 +2318,43 @@ impl Project {
             .map(|m| (m.id.to_ascii_lowercase(), m))
             .collect();
 
+        let game_version = self.game_version();
         for (this_idx, mod_meta) in active_mods.iter().enumerate() {
             let mod_id = &mod_meta.id;
             let mod_name = &mod_meta.name;
             let mut mod_issues = ModIssues::new(mod_id.clone());
 
             // Check game version compatibility
-            if let Some(config) = &self.mods_config {
-                let game_version = config.minor_version();
-                if !mod_meta.supported_versions.contains(&game_version)
-                    && !mod_id.eq_ignore_ascii_case("ludeon.rimworld")
-                {
-                    log::warn!(
-                        "Mod '{mod_name}' ({mod_id}) is not compatible with game version '{game_version}'"
-                    );
-                    mod_issues.add_unsupported_game_version(game_version);
-                }
+            if let Some(game_version) = &game_version
+                && !mod_meta.supported_versions.contains(game_version)
+                && !mod_id.eq_ignore_ascii_case("ludeon.rimworld")
+            {
+                log::warn!(
+                    "Mod '{mod_name}' ({mod_id}) is not compatible with game version '{game_version}'"
+                );
+                mod_issues.add_unsupported_game_version(game_version.clone());
             }
 
             // Check for missing dependencies
             for dep_id in mod_meta.dependencies.keys() {
                 let dep_id_lc = dep_id.to_ascii_lowercase();
+                if let Some(expansion) = Expansion::from_id(dep_id) {
+                    let missing = match (
+                        mod_map.contains_key(&dep_id_lc),
+                        active_id_set.contains(&dep_id_lc),
+                    ) {
+                        (false, _) => Some("not installed"),
+                        (true, false) => Some("not active"),
+                        (true, true) => None,
+                    };
+                    if let Some(reason) = missing {
+                        log::warn!(
+                            "Mod '{mod_name}' ({mod_id}) requires DLC '{expansion}', which is {reason}"
+                        );
+                        mod_issues.add_missing_expansion(format!("{expansion} ({reason})"));
+                    }
+                    continue;
+                }
                 let dep_name = mod_map
                     .get(&dep_id_lc)
                     .map(|m| m.name.as_str())
@@ -774,8 +2442,7 @@ impl Project {
             .iter()
             .filter(|m| !active_id_set.contains(&m.id.to_ascii_lowercase()));
 
-        if let Some(config) = &self.mods_config {
-            let game_version = config.minor_version();
+        if let Some(game_version) = self.game_version() {
             for mod_meta in inactive_mods {
                 let mod_id = &mod_meta.id;
                 // Don't overwrite issues for active mods
@@ -798,4 +2465,245 @@ impl Project {
             }
         }
     }
+
+    fn collect_texture_issues(&self, issues: &mut HashMap<String, ModIssues>) {
+        for mod_meta in &self.mods {
+            let mod_id = mod_meta.id.to_ascii_lowercase();
+            let Some(report) = self.texture_reports.get(&mod_id) else {
+                continue;
+            };
+            if !report.has_warnings() {
+                continue;
+            }
+
+            let mod_issues = issues
+                .entry(mod_id)
+                .or_insert_with(|| ModIssues::new(mod_meta.id.clone()));
+            for texture in report.oversized.iter().take(MAX_TEXTURE_WARNINGS) {
+                mod_issues.add_texture_warning(format!(
+                    "Oversized texture {} ({}x{})",
+                    texture.path.display(),
+                    texture.width,
+                    texture.height
+                ));
+            }
+            for texture in report.missing_dds.iter().take(MAX_TEXTURE_WARNINGS) {
+                mod_issues.add_texture_warning(format!(
+                    "Missing DDS for {} ({}x{})",
+                    texture.path.display(),
+                    texture.width,
+                    texture.height
+                ));
+            }
+            let hidden = report.oversized.len().saturating_sub(MAX_TEXTURE_WARNINGS)
+                + report
+                    .missing_dds
+                    .len()
+                    .saturating_sub(MAX_TEXTURE_WARNINGS);
+            if hidden > 0 {
+                mod_issues.add_texture_warning(format!("...and {hidden} more"));
+            }
+        }
+    }
+
+    fn collect_assembly_issues(&self, issues: &mut HashMap<String, ModIssues>) {
+        let active_ids = self.active_id_set();
+        // assembly name (lowercase) to the active mods loading it, with their versions
+        let mut loaded: HashMap<String, Vec<(&ModMetaData, &ModAssembly)>> = HashMap::new();
+        for mod_meta in &self.cached_active_mods {
+            for assembly in self.effective_assemblies(mod_meta, &active_ids) {
+                loaded
+                    .entry(assembly.name.to_ascii_lowercase())
+                    .or_default()
+                    .push((mod_meta, assembly));
+            }
+        }
+
+        for loaders in loaded.values() {
+            let versions: HashSet<_> = loaders.iter().map(|(_, a)| a.version).collect();
+            if versions.len() < 2 {
+                continue;
+            }
+            for (mod_meta, assembly) in loaders {
+                let mod_id = &mod_meta.id;
+                let mod_issues = issues
+                    .entry(mod_id.to_ascii_lowercase())
+                    .or_insert_with(|| ModIssues::new(mod_id.clone()));
+                for (other_meta, other) in loaders {
+                    if other.version == assembly.version {
+                        continue;
+                    }
+                    let msg = format!(
+                        "{} {} conflicts with {} in '{}' ({})",
+                        assembly.name,
+                        assembly.version,
+                        other.version,
+                        other_meta.name,
+                        other_meta.id
+                    );
+                    log::warn!(
+                        "Assembly conflict for '{}' ({mod_id}): {msg}",
+                        mod_meta.name
+                    );
+                    mod_issues.add_assembly_conflict(msg);
+                }
+            }
+        }
+    }
+
+    fn collect_load_folder_issues(&self, issues: &mut HashMap<String, ModIssues>) {
+        let Some(game_version) = self.game_version() else {
+            return;
+        };
+        for mod_meta in self.mods.iter().filter(|m| !m.is_official()) {
+            if mod_meta.has_version_folder(&game_version) {
+                continue;
+            }
+            let mod_id = &mod_meta.id;
+            log::warn!(
+                "Mod '{}' ({mod_id}) has no content folder for game version '{game_version}'",
+                mod_meta.name
+            );
+            issues
+                .entry(mod_id.to_ascii_lowercase())
+                .or_insert_with(|| ModIssues::new(mod_id.clone()))
+                .add_missing_version_folder(game_version.clone());
+        }
+    }
+
+    fn collect_patch_issues(&self, issues: &mut HashMap<String, ModIssues>) {
+        let active_ids = self.active_id_set();
+        let active_names: HashSet<&str> = self
+            .cached_active_mods
+            .iter()
+            .map(|m| m.name.as_str())
+            .collect();
+        let mod_names: HashMap<String, &str> = self
+            .mods
+            .iter()
+            .map(|m| (m.id.to_ascii_lowercase(), m.name.as_str()))
+            .collect();
+
+        // normalized xpath to the active mods replacing or removing it
+        let mut destructive: HashMap<String, Vec<&ModMetaData>> = HashMap::new();
+        // mod id (lowercase) to patches targeting defs only inactive mods define
+        let mut inactive_targets: HashMap<String, Vec<String>> = HashMap::new();
+
+        for mod_meta in &self.cached_active_mods {
+            let mod_id = mod_meta.id.to_ascii_lowercase();
+            for operation in self.effective_patch_targets(mod_meta, &active_ids, &active_names) {
+                if operation.kind.is_destructive()
+                    && let Some(xpath) = operation.normalized_xpath()
+                {
+                    let mods = destructive.entry(xpath).or_default();
+                    if !mods.iter().any(|m| m.id == mod_meta.id) {
+                        mods.push(mod_meta);
+                    }
+                }
+
+                for def_name in operation.target_def_names() {
+                    let Some(owners) = self.def_owners.get(def_name) else {
+                        continue;
+                    };
+                    if owners.iter().any(|owner| active_ids.contains(owner)) {
+                        continue;
+                    }
+                    let owner_names = owners
+                        .iter()
+                        .map(|owner| format!("'{}'", mod_names.get(owner).unwrap_or(&"<unknown>")))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    inactive_targets
+                        .entry(mod_id.clone())
+                        .or_default()
+                        .push(format!(
+                            "{} targets '{def_name}', only defined by inactive {owner_names}",
+                            operation.kind
+                        ));
+                }
+            }
+        }
+
+        let mut conflicts: HashMap<String, Vec<String>> = HashMap::new();
+        for (xpath, mods) in destructive.iter().filter(|(_, mods)| mods.len() > 1) {
+            for mod_meta in mods {
+                let others = mods
+                    .iter()
+                    .filter(|other| other.id != mod_meta.id)
+                    .map(|other| format!("'{}' ({})", other.name, other.id))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                conflicts
+                    .entry(mod_meta.id.to_ascii_lowercase())
+                    .or_default()
+                    .push(format!("{xpath} is also replaced or removed by {others}"));
+            }
+        }
+
+        for mod_meta in &self.cached_active_mods {
+            let mod_id = mod_meta.id.to_ascii_lowercase();
+            let mut warnings = Vec::new();
+            for messages in [conflicts.get(&mod_id), inactive_targets.get(&mod_id)]
+                .into_iter()
+                .flatten()
+            {
+                let mut messages = messages.clone();
+                messages.sort();
+                messages.dedup();
+                let hidden = messages.len().saturating_sub(MAX_PATCH_WARNINGS);
+                warnings.extend(messages.into_iter().take(MAX_PATCH_WARNINGS));
+                if hidden > 0 {
+                    warnings.push(format!("...and {hidden} more"));
+                }
+            }
+            if warnings.is_empty() {
+                continue;
+            }
+
+            log::warn!(
+                "Found {} patch warnings for '{}' ({})",
+                warnings.len(),
+                mod_meta.name,
+                mod_meta.id
+            );
+            let mod_issues = issues
+                .entry(mod_id)
+                .or_insert_with(|| ModIssues::new(mod_meta.id.clone()));
+            for warning in warnings {
+                mod_issues.add_patch_warning(warning);
+            }
+        }
+    }
+
+    fn collect_log_issues(&self, issues: &mut HashMap<String, ModIssues>) {
+        for (mod_id, mod_errors) in &self.mod_log_errors {
+            let Some(mod_meta) = self.find_mod(mod_id) else {
+                continue;
+            };
+            let mut errors: Vec<String> = mod_errors
+                .iter()
+                .map(|error| {
+                    let mut message = format!("{}: {}", error.kind, error.message);
+                    if error.count > 1 {
+                        message.push_str(&format!(" (x{})", error.count));
+                    }
+                    if error.is_from_previous_session() {
+                        message.push_str(" (previous session)");
+                    }
+                    message
+                })
+                .collect();
+            let hidden = errors.len().saturating_sub(MAX_LOG_ERRORS);
+            errors.truncate(MAX_LOG_ERRORS);
+            if hidden > 0 {
+                errors.push(format!("...and {hidden} more"));
+            }
+            let mod_issues = issues
+                .entry(mod_meta.id.to_ascii_lowercase())
+                .or_insert_with(|| ModIssues::new(mod_meta.id.clone()));
+            for error in errors {
+                mod_issues.add_log_error(error);
+            }
+        }
+    }
 }
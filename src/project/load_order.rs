@@ -0,0 +1,106 @@
+//! Reordering of the active mod ids.
+
+use anyhow::Context as _;
+
+/// Move a mod onto the position of the target mod, shifting the mods in between.
+pub fn move_mod(ids: &mut Vec<String>, source: &str, target: &str) -> anyhow::Result<()> {
+    if source.eq_ignore_ascii_case(target) {
+        return Ok(());
+    }
+    let source_index =
+        position(ids, source).with_context(|| format!("dragged mod is not active {source}"))?;
+    let target_index =
+        position(ids, target).with_context(|| format!("target mod is not active {target}"))?;
+
+    let moving = ids.remove(source_index);
+    ids.insert(target_index, moving);
+    Ok(())
+}
+
+/// Move a group of mods as a contiguous block next to the target mod.
+///
+/// The mods keep their relative order. When moving down the list the block is placed after
+/// the target, otherwise before it, matching the behaviour of [`move_mod`].
+pub fn move_mods(ids: &mut Vec<String>, sources: &[String], target: &str) -> anyhow::Result<()> {
+    let is_source = |id: &str| sources.iter().any(|s| s.eq_ignore_ascii_case(id));
+    if is_source(target) {
+        return Ok(());
+    }
+
+    let target_index =
+        position(ids, target).with_context(|| format!("target mod is not active {target}"))?;
+    let first_source_index = ids
+        .iter()
+        .position(|id| is_source(id))
+        .with_context(|| format!("dragged mods are not active {sources:?}"))?;
+
+    let (moving, mut remaining): (Vec<_>, Vec<_>) = ids.drain(..).partition(|id| is_source(id));
+
+    let mut insert_index = position(&remaining, target).expect("target mod should remain active");
+    if first_source_index < target_index {
+        insert_index += 1;
+    }
+
+    remaining.splice(insert_index..insert_index, moving);
+    *ids = remaining;
+    Ok(())
+}
+
+fn position(ids: &[String], mod_id: &str) -> Option<usize> {
+    ids.iter().position(|id| id.eq_ignore_ascii_case(mod_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_move_mod() {
+        let mut order = ids(&["a", "b", "c", "d"]);
+        move_mod(&mut order, "a", "C").unwrap();
+        assert_eq!(order, ids(&["b", "c", "a", "d"]));
+
+        move_mod(&mut order, "d", "b").unwrap();
+        assert_eq!(order, ids(&["d", "b", "c", "a"]));
+
+        move_mod(&mut order, "b", "B").unwrap();
+        assert_eq!(order, ids(&["d", "b", "c", "a"]));
+
+        assert!(move_mod(&mut order, "x", "a").is_err());
+        assert!(move_mod(&mut order, "a", "x").is_err());
+        assert_eq!(order, ids(&["d", "b", "c", "a"]));
+    }
+
+    #[test]
+    fn test_move_mods_down() {
+        let mut order = ids(&["a", "b", "c", "d", "e"]);
+        move_mods(&mut order, &ids(&["d", "A"]), "c").unwrap();
+        assert_eq!(order, ids(&["b", "c", "a", "d", "e"]));
+    }
+
+    #[test]
+    fn test_move_mods_up() {
+        let mut order = ids(&["a", "b", "c", "d", "e"]);
+        move_mods(&mut order, &ids(&["c", "e"]), "b").unwrap();
+        assert_eq!(order, ids(&["a", "c", "e", "b", "d"]));
+    }
+
+    #[test]
+    fn test_move_mods_onto_selection() {
+        let mut order = ids(&["a", "b", "c"]);
+        move_mods(&mut order, &ids(&["a", "b"]), "b").unwrap();
+        assert_eq!(order, ids(&["a", "b", "c"]));
+    }
+
+    #[test]
+    fn test_move_mods_not_active() {
+        let mut order = ids(&["a", "b", "c"]);
+        assert!(move_mods(&mut order, &ids(&["x"]), "b").is_err());
+        assert!(move_mods(&mut order, &ids(&["a"]), "x").is_err());
+        assert_eq!(order, ids(&["a", "b", "c"]));
+    }
+}
@@ -0,0 +1,164 @@
+//! Selection of mods in the mod lists, which can span several mods.
+
+use std::collections::HashSet;
+
+use crate::game::mods::ModMetaData;
+
+#[derive(Debug, Default)]
+pub struct Selection {
+    /// current selected mod, shown in the details pane
+    current: Option<ModMetaData>,
+    /// set of selected mod ids (lowercase), including the current mod
+    ids: HashSet<String>,
+}
+
+impl Selection {
+    pub fn current(&self) -> Option<&ModMetaData> {
+        self.current.as_ref()
+    }
+
+    pub fn current_mut(&mut self) -> Option<&mut ModMetaData> {
+        self.current.as_mut()
+    }
+
+    /// Select a single mod, or nothing, clearing any other selected mods.
+    pub fn select(&mut self, mod_meta: Option<&ModMetaData>) {
+        self.current = mod_meta.cloned();
+        self.ids = self
+            .current
+            .iter()
+            .map(|m| m.id.to_ascii_lowercase())
+            .collect();
+    }
+
+    /// Add or remove a mod, keeping other selected mods.
+    ///
+    /// When the current mod is removed, the first remaining selected mod in `mods` becomes current.
+    pub fn toggle(&mut self, mod_meta: &ModMetaData, mods: &[ModMetaData]) {
+        let mod_id = mod_meta.id.to_ascii_lowercase();
+        if self.ids.remove(&mod_id) {
+            if self
+                .current
+                .as_ref()
+                .is_some_and(|m| m.id.eq_ignore_ascii_case(&mod_id))
+            {
+                self.current = mods
+                    .iter()
+                    .find(|m| self.ids.contains(&m.id.to_ascii_lowercase()))
+                    .cloned();
+            }
+        } else {
+            self.ids.insert(mod_id);
+            self.current = Some(mod_meta.clone());
+        }
+    }
+
+    /// Add a range of mods, making the last one the current mod.
+    pub fn extend(&mut self, mods: &[ModMetaData]) {
+        self.ids
+            .extend(mods.iter().map(|m| m.id.to_ascii_lowercase()));
+        if let Some(last) = mods.last() {
+            self.current = Some(last.clone());
+        }
+    }
+
+    /// Check if a mod is selected (case-insensitive).
+    pub fn contains(&self, mod_id: &str) -> bool {
+        self.ids.contains(&mod_id.to_ascii_lowercase())
+    }
+
+    /// Get the selected mod ids (lowercase), in no particular order.
+    pub fn ids(&self) -> impl Iterator<Item = &String> {
+        self.ids.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_mod(id: &str) -> ModMetaData {
+        ModMetaData {
+            id: id.into(),
+            ..Default::default()
+        }
+    }
+
+    fn current_id(selection: &Selection) -> Option<&str> {
+        selection.current().map(|m| m.id.as_str())
+    }
+
+    #[test]
+    fn test_select_replaces_selection() {
+        let mods = [make_mod("a"), make_mod("b")];
+        let mut selection = Selection::default();
+        selection.extend(&mods);
+        selection.select(Some(&mods[0]));
+
+        assert_eq!(selection.len(), 1);
+        assert!(selection.contains("A"));
+        assert!(!selection.contains("b"));
+        assert_eq!(current_id(&selection), Some("a"));
+
+        selection.select(None);
+        assert!(selection.is_empty());
+        assert_eq!(current_id(&selection), None);
+    }
+
+    #[test]
+    fn test_toggle_adds_and_removes() {
+        let mods = [make_mod("a"), make_mod("B"), make_mod("c")];
+        let mut selection = Selection::default();
+        selection.select(Some(&mods[0]));
+
+        selection.toggle(&mods[2], &mods);
+        assert_eq!(selection.len(), 2);
+        assert_eq!(current_id(&selection), Some("c"));
+
+        selection.toggle(&mods[1], &mods);
+        assert!(selection.contains("b"));
+        assert_eq!(current_id(&selection), Some("B"));
+
+        // removing a mod other than the current one keeps the current mod
+        selection.toggle(&mods[0], &mods);
+        assert!(!selection.contains("a"));
+        assert_eq!(current_id(&selection), Some("B"));
+    }
+
+    #[test]
+    fn test_toggle_current_picks_next_selected() {
+        let mods = [make_mod("a"), make_mod("b"), make_mod("c")];
+        let mut selection = Selection::default();
+        selection.select(Some(&mods[2]));
+        selection.toggle(&mods[1], &mods);
+
+        selection.toggle(&mods[1], &mods);
+        assert_eq!(current_id(&selection), Some("c"));
+
+        selection.toggle(&mods[2], &mods);
+        assert!(selection.is_empty());
+        assert_eq!(current_id(&selection), None);
+    }
+
+    #[test]
+    fn test_extend_range() {
+        let mods = [make_mod("a"), make_mod("b"), make_mod("c")];
+        let mut selection = Selection::default();
+        selection.select(Some(&mods[0]));
+        selection.extend(&mods[1..]);
+
+        assert_eq!(selection.len(), 3);
+        assert_eq!(current_id(&selection), Some("c"));
+
+        selection.extend(&[]);
+        assert_eq!(current_id(&selection), Some("c"));
+    }
+}
@@ -0,0 +1,11 @@
+mod filter;
+mod fuzzy;
+mod index;
+mod matcher;
+mod query;
+
+pub use filter::*;
+pub use fuzzy::*;
+pub use index::*;
+pub use matcher::*;
+pub use query::*;
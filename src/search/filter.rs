@@ -0,0 +1,172 @@
+use std::{collections::BTreeSet, fmt::Display};
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::mods::{ModIssues, ModMetaData, Source};
+
+/// Quick filter toggled from a mod list header.
+///
+/// Source filters match any selected source, all other filters must match.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ModFilter {
+    Official,
+    Local,
+    Steam,
+    Errors,
+    Warnings,
+    Recent,
+    NeverActivated,
+    Duplicates,
+}
+
+impl ModFilter {
+    pub const ALL: [ModFilter; 8] = [
+        ModFilter::Official,
+        ModFilter::Local,
+        ModFilter::Steam,
+        ModFilter::Errors,
+        ModFilter::Warnings,
+        ModFilter::Recent,
+        ModFilter::NeverActivated,
+        ModFilter::Duplicates,
+    ];
+
+    pub fn source(&self) -> Option<Source> {
+        match self {
+            ModFilter::Official => Some(Source::Official),
+            ModFilter::Local => Some(Source::Local),
+            ModFilter::Steam => Some(Source::Steam),
+            _ => None,
+        }
+    }
+
+    pub fn matches(&self, mod_meta: &ModMetaData, cx: &FilterContext) -> bool {
+        match self {
+            ModFilter::Official | ModFilter::Local | ModFilter::Steam => {
+                self.source().as_ref() == Some(&mod_meta.source)
+            }
+            ModFilter::Errors => cx.issues.is_some_and(ModIssues::has_errors),
+            ModFilter::Warnings => cx.issues.is_some_and(ModIssues::has_warnings),
+            ModFilter::Recent => cx.recent,
+            ModFilter::NeverActivated => !cx.activated,
+            ModFilter::Duplicates => cx.duplicate,
+        }
+    }
+}
+
+impl Display for ModFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModFilter::Official => write!(f, "Official"),
+            ModFilter::Local => write!(f, "Local"),
+            ModFilter::Steam => write!(f, "Steam"),
+            ModFilter::Errors => write!(f, "Errors"),
+            ModFilter::Warnings => write!(f, "Warnings"),
+            ModFilter::Recent => write!(f, "Recent"),
+            ModFilter::NeverActivated => write!(f, "Never Activated"),
+            ModFilter::Duplicates => write!(f, "Duplicates"),
+        }
+    }
+}
+
+/// Data about a mod that is not part of its metadata, used when applying filters.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FilterContext<'a> {
+    pub issues: Option<&'a ModIssues>,
+    /// installed or updated recently
+    pub recent: bool,
+    /// activated at least once
+    pub activated: bool,
+    /// another installed mod has the same package id
+    pub duplicate: bool,
+}
+
+/// Check a mod against a combination of filters.
+pub fn matches_filters(
+    filters: &BTreeSet<ModFilter>,
+    mod_meta: &ModMetaData,
+    cx: &FilterContext,
+) -> bool {
+    let (sources, others): (Vec<&ModFilter>, Vec<&ModFilter>) =
+        filters.iter().partition(|filter| filter.source().is_some());
+    (sources.is_empty() || sources.iter().any(|filter| filter.matches(mod_meta, cx)))
+        && others.iter().all(|filter| filter.matches(mod_meta, cx))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_mod(source: Source) -> ModMetaData {
+        ModMetaData {
+            id: "brrainz.harmony".into(),
+            source,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_no_filters() {
+        let filters = BTreeSet::new();
+        let cx = FilterContext::default();
+        assert!(matches_filters(&filters, &make_mod(Source::Steam), &cx));
+    }
+
+    #[test]
+    fn test_source_filters_match_any() {
+        let filters = BTreeSet::from([ModFilter::Local, ModFilter::Steam]);
+        let cx = FilterContext::default();
+        assert!(matches_filters(&filters, &make_mod(Source::Steam), &cx));
+        assert!(matches_filters(&filters, &make_mod(Source::Local), &cx));
+        assert!(!matches_filters(&filters, &make_mod(Source::Official), &cx));
+    }
+
+    #[test]
+    fn test_other_filters_match_all() {
+        let filters = BTreeSet::from([ModFilter::Steam, ModFilter::Recent, ModFilter::Duplicates]);
+        let steam = make_mod(Source::Steam);
+        let cx = FilterContext {
+            recent: true,
+            duplicate: true,
+            activated: true,
+            ..Default::default()
+        };
+        assert!(matches_filters(&filters, &steam, &cx));
+        assert!(!matches_filters(&filters, &make_mod(Source::Local), &cx));
+
+        let cx = FilterContext {
+            duplicate: false,
+            ..cx
+        };
+        assert!(!matches_filters(&filters, &steam, &cx));
+    }
+
+    #[test]
+    fn test_activation_and_issue_filters() {
+        let steam = make_mod(Source::Steam);
+        let never_activated = BTreeSet::from([ModFilter::NeverActivated]);
+        assert!(matches_filters(
+            &never_activated,
+            &steam,
+            &FilterContext::default()
+        ));
+        let activated = FilterContext {
+            activated: true,
+            ..Default::default()
+        };
+        assert!(!matches_filters(&never_activated, &steam, &activated));
+
+        let errors = BTreeSet::from([ModFilter::Errors]);
+        let warnings = BTreeSet::from([ModFilter::Warnings]);
+        let mut issues = ModIssues::default();
+        issues.add_missing_dependency("unlimitedhugs.hugslib".into());
+        let cx = FilterContext {
+            issues: Some(&issues),
+            ..Default::default()
+        };
+        assert!(matches_filters(&errors, &steam, &cx));
+        assert!(!matches_filters(&warnings, &steam, &cx));
+        assert!(!matches_filters(&errors, &steam, &FilterContext::default()));
+    }
+}
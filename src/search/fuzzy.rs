@@ -0,0 +1,116 @@
+/// Score for each matched character.
+const MATCH_SCORE: i64 = 16;
+/// Bonus for a match directly after the previous match.
+const CONSECUTIVE_BONUS: i64 = 15;
+/// Bonus for a match at the start of a word.
+const WORD_START_BONUS: i64 = 10;
+/// Penalty for each character skipped between matches.
+const GAP_PENALTY: i64 = 1;
+
+/// Result of a successful fuzzy match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    pub score: i64,
+    /// byte offsets of the matched characters in the text
+    pub positions: Vec<usize>,
+}
+
+/// Match the pattern characters in order against the text, ignoring whitespace in the pattern.
+///
+/// Every possible starting point is tried, keeping the highest scoring match.
+pub fn fuzzy_match(pattern: &str, text: &str, case_sensitive: bool) -> Option<FuzzyMatch> {
+    let normalize = |c: char| match case_sensitive {
+        true => c,
+        false => c.to_lowercase().next().unwrap_or(c),
+    };
+    let pattern: Vec<char> = pattern
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(normalize)
+        .collect();
+    let first = *pattern.first()?;
+    let text: Vec<(usize, char)> = text.char_indices().collect();
+
+    text.iter()
+        .enumerate()
+        .filter(|(_, (_, c))| normalize(*c) == first)
+        .filter_map(|(start, _)| match_from(&pattern, &text, start, normalize))
+        .max_by_key(|m| m.score)
+}
+
+fn match_from(
+    pattern: &[char],
+    text: &[(usize, char)],
+    start: usize,
+    normalize: impl Fn(char) -> char,
+) -> Option<FuzzyMatch> {
+    let mut positions = Vec::with_capacity(pattern.len());
+    let mut score = 0;
+    let mut last: Option<usize> = None;
+    let mut pattern = pattern.iter().peekable();
+
+    for (ix, &(offset, c)) in text.iter().enumerate().skip(start) {
+        let Some(&&p) = pattern.peek() else {
+            break;
+        };
+        if normalize(c) != p {
+            continue;
+        }
+        pattern.next();
+        score += MATCH_SCORE;
+        match last {
+            Some(last) if last + 1 == ix => score += CONSECUTIVE_BONUS,
+            Some(last) => score -= (ix - last - 1) as i64 * GAP_PENALTY,
+            None => score -= ix as i64 * GAP_PENALTY,
+        }
+        if is_word_start(text, ix) {
+            score += WORD_START_BONUS;
+        }
+        positions.push(offset);
+        last = Some(ix);
+    }
+
+    pattern
+        .peek()
+        .is_none()
+        .then_some(FuzzyMatch { score, positions })
+}
+
+fn is_word_start(text: &[(usize, char)], ix: usize) -> bool {
+    let c = text[ix].1;
+    match ix.checked_sub(1).map(|prev| text[prev].1) {
+        None => true,
+        Some(prev) => {
+            !prev.is_alphanumeric() && c.is_alphanumeric()
+                || prev.is_lowercase() && c.is_uppercase()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_match_positions() {
+        let m = fuzzy_match("hsl", "HugsLib", false).unwrap();
+        assert_eq!(m.positions, vec![0, 3, 4]);
+        assert!(fuzzy_match("hsl", "HugsLib", true).is_none());
+        assert!(fuzzy_match("xyz", "HugsLib", false).is_none());
+        assert!(fuzzy_match("", "HugsLib", false).is_none());
+    }
+
+    #[test]
+    fn test_fuzzy_match_ranking() {
+        let score = |text| fuzzy_match("core", text, false).unwrap().score;
+        assert!(score("Core") > score("Combat Extended Ore"));
+        assert!(score("Vanilla Core") > score("Scorer"));
+    }
+
+    #[test]
+    fn test_fuzzy_match_best_start() {
+        // the later, consecutive match beats the first scattered one
+        let m = fuzzy_match("ab", "a x b ab", false).unwrap();
+        assert_eq!(m.positions, vec![6, 7]);
+    }
+}
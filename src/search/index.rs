@@ -0,0 +1,114 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    ops::Bound,
+};
+
+use crate::game::mods::ModMetaData;
+
+/// Precomputed, normalized search data for every mod.
+#[derive(Debug, Clone, Default)]
+pub struct SearchIndex {
+    /// map of mod id (lowercase) to index entry
+    entries: HashMap<String, IndexEntry>,
+}
+
+/// Normalized search data for a single mod.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexEntry {
+    /// lowercase name
+    pub name: String,
+    /// lowercase id
+    pub id: String,
+    /// lowercase authors
+    pub authors: Vec<String>,
+    /// lowercase words from the name, id, authors, description and tags
+    pub tokens: BTreeSet<String>,
+}
+
+impl SearchIndex {
+    pub fn new<'a>(
+        mods: impl IntoIterator<Item = &'a ModMetaData>,
+        tags: &HashMap<String, BTreeSet<String>>,
+    ) -> Self {
+        let entries = mods
+            .into_iter()
+            .map(|mod_meta| {
+                let id = mod_meta.id.to_lowercase();
+                let entry = IndexEntry::new(mod_meta, tags.get(&id));
+                (id, entry)
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// Get the index entry for a mod id (case-insensitive).
+    pub fn entry(&self, mod_id: &str) -> Option<&IndexEntry> {
+        self.entries.get(&mod_id.to_lowercase())
+    }
+}
+
+impl IndexEntry {
+    pub fn new(mod_meta: &ModMetaData, tags: Option<&BTreeSet<String>>) -> Self {
+        let name = mod_meta.name.to_lowercase();
+        let id = mod_meta.id.to_lowercase();
+        let authors: Vec<String> = mod_meta.authors.iter().map(|a| a.to_lowercase()).collect();
+        let description = mod_meta.description.to_lowercase();
+        let tokens = [name.as_str(), id.as_str(), description.as_str()]
+            .into_iter()
+            .chain(authors.iter().map(String::as_str))
+            .flat_map(tokenize)
+            .chain(tags.into_iter().flatten().map(|tag| tag.to_lowercase()))
+            .collect();
+        Self {
+            name,
+            id,
+            authors,
+            tokens,
+        }
+    }
+
+    /// Check if any token starts with the given lowercase prefix.
+    pub fn has_token_prefix(&self, prefix: &str) -> bool {
+        self.tokens
+            .range::<str, _>((Bound::Included(prefix), Bound::Unbounded))
+            .next()
+            .is_some_and(|token| token.starts_with(prefix))
+    }
+}
+
+/// Split text into alphanumeric words.
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_entry() {
+        let mod_meta = ModMetaData {
+            id: "Ludeon.RimWorld.Royalty".into(),
+            name: "Royalty".into(),
+            authors: vec!["Ludeon Studios".into()],
+            description: "Adds an <i>empire</i> of nobles.".into(),
+            ..Default::default()
+        };
+        let tags = BTreeSet::from(["DLC".to_string()]);
+        let index = SearchIndex::new(
+            [&mod_meta],
+            &HashMap::from([("ludeon.rimworld.royalty".to_string(), tags)]),
+        );
+
+        let entry = index.entry("ludeon.rimworld.ROYALTY").unwrap();
+        assert_eq!(entry.id, "ludeon.rimworld.royalty");
+        assert_eq!(entry.authors, vec!["ludeon studios"]);
+        assert!(entry.has_token_prefix("emp"));
+        assert!(entry.has_token_prefix("studios"));
+        assert!(entry.has_token_prefix("dlc"));
+        assert!(entry.has_token_prefix("rimworld"));
+        assert!(!entry.has_token_prefix("nobless"));
+    }
+}
@@ -0,0 +1,187 @@
+use std::fmt::Display;
+
+use regex::{Regex, RegexBuilder};
+
+use crate::{
+    game::mods::ModMetaData,
+    search::{MatchContext, Query, fuzzy_match},
+};
+
+/// How the search text is interpreted.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SearchMode {
+    /// Structured query, see [`Query`]
+    #[default]
+    Query,
+    /// Regular expression matched against name, id and notes
+    Regex,
+    /// Fuzzy match against name and id, ranked by score in the inactive list
+    Fuzzy,
+}
+
+impl Display for SearchMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SearchMode::Query => write!(f, "Query"),
+            SearchMode::Regex => write!(f, "Regex"),
+            SearchMode::Fuzzy => write!(f, "Fuzzy"),
+        }
+    }
+}
+
+/// Compiled search text for a search mode.
+#[derive(Debug, Clone)]
+pub enum Matcher {
+    Query(Query),
+    Regex(Regex),
+    Fuzzy(String),
+}
+
+impl Matcher {
+    pub fn new(mode: SearchMode, search: &str, case_sensitive: bool) -> Result<Self, String> {
+        match mode {
+            SearchMode::Query => Query::parse(search)
+                .map(Matcher::Query)
+                .map_err(|e| e.to_string()),
+            SearchMode::Regex => RegexBuilder::new(search)
+                .case_insensitive(!case_sensitive)
+                .build()
+                .map(Matcher::Regex)
+                .map_err(|e| format!("Invalid regex: {e}")),
+            SearchMode::Fuzzy => Ok(Matcher::Fuzzy(search.to_string())),
+        }
+    }
+
+    /// Check if the matcher accepts every mod.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Matcher::Query(query) => query.is_empty(),
+            Matcher::Regex(regex) => regex.as_str().is_empty(),
+            Matcher::Fuzzy(pattern) => pattern.trim().is_empty(),
+        }
+    }
+
+    /// Score a mod against the matcher, returning `None` if it does not match.
+    ///
+    /// Only fuzzy matching produces meaningful scores, other modes score every match as 0.
+    pub fn score(&self, mod_meta: &ModMetaData, cx: &MatchContext) -> Option<i64> {
+        if self.is_empty() {
+            return Some(0);
+        }
+        match self {
+            Matcher::Query(query) => query.matches(mod_meta, cx).then_some(0),
+            Matcher::Regex(regex) => (regex.is_match(&mod_meta.name)
+                || regex.is_match(&mod_meta.id)
+                || cx.note.is_some_and(|note| regex.is_match(note)))
+            .then_some(0),
+            Matcher::Fuzzy(pattern) => {
+                let name = fuzzy_match(pattern, &mod_meta.name, cx.case_sensitive);
+                let id = fuzzy_match(pattern, &mod_meta.id, cx.case_sensitive);
+                name.into_iter().chain(id).map(|m| m.score).max()
+            }
+        }
+    }
+
+    /// Byte offsets of the characters in a mod name to highlight.
+    pub fn highlights(&self, name: &str, case_sensitive: bool) -> Vec<usize> {
+        match self {
+            Matcher::Fuzzy(pattern) => fuzzy_match(pattern, name, case_sensitive)
+                .map(|m| m.positions)
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_mod(id: &str, name: &str) -> ModMetaData {
+        ModMetaData {
+            id: id.into(),
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_empty_matcher() {
+        let mod_meta = make_mod("brrainz.harmony", "Harmony");
+        let cx = MatchContext::default();
+        for mode in [SearchMode::Query, SearchMode::Regex, SearchMode::Fuzzy] {
+            let matcher = Matcher::new(mode, "", false).unwrap();
+            assert!(matcher.is_empty());
+            assert_eq!(matcher.score(&mod_meta, &cx), Some(0));
+        }
+    }
+
+    #[test]
+    fn test_regex_matcher() {
+        let mod_meta = make_mod("brrainz.harmony", "Harmony");
+        let cx = MatchContext {
+            note: Some("load first"),
+            ..Default::default()
+        };
+        let score = |search: &str, case_sensitive: bool| {
+            Matcher::new(SearchMode::Regex, search, case_sensitive)
+                .unwrap()
+                .score(&mod_meta, &cx)
+        };
+
+        assert_eq!(score("^harm", false), Some(0));
+        assert_eq!(score(r"brrainz\.", false), Some(0));
+        assert_eq!(score("first$", false), Some(0));
+        assert_eq!(score("^harm", true), None);
+        assert_eq!(score("hugslib", false), None);
+
+        let error = Matcher::new(SearchMode::Regex, "(harmony", false).unwrap_err();
+        assert!(error.starts_with("Invalid regex"));
+    }
+
+    #[test]
+    fn test_query_matcher() {
+        let mod_meta = make_mod("brrainz.harmony", "Harmony");
+        let cx = MatchContext::default();
+        let matcher = Matcher::new(SearchMode::Query, "harm -id:core", false).unwrap();
+        assert_eq!(matcher.score(&mod_meta, &cx), Some(0));
+        let matcher = Matcher::new(SearchMode::Query, "id:core", false).unwrap();
+        assert_eq!(matcher.score(&mod_meta, &cx), None);
+        assert!(Matcher::new(SearchMode::Query, "author:", false).is_err());
+    }
+
+    #[test]
+    fn test_fuzzy_matcher() {
+        let core = make_mod("ludeon.rimworld", "Core");
+        let ore = make_mod("ceteam.combatextended", "Combat Extended Ore");
+        let harmony = make_mod("brrainz.harmony", "Harmony");
+        let cx = MatchContext::default();
+        let matcher = Matcher::new(SearchMode::Fuzzy, "core", false).unwrap();
+
+        let core_score = matcher.score(&core, &cx).unwrap();
+        let ore_score = matcher.score(&ore, &cx).unwrap();
+        assert!(core_score > ore_score);
+        assert_eq!(matcher.score(&harmony, &cx), None);
+
+        // the id is matched as well as the name
+        let matcher = Matcher::new(SearchMode::Fuzzy, "brrainz", false).unwrap();
+        assert!(matcher.score(&harmony, &cx).is_some());
+
+        let cx = MatchContext {
+            case_sensitive: true,
+            ..Default::default()
+        };
+        let matcher = Matcher::new(SearchMode::Fuzzy, "CORE", true).unwrap();
+        assert_eq!(matcher.score(&core, &cx), None);
+    }
+
+    #[test]
+    fn test_highlights() {
+        let matcher = Matcher::new(SearchMode::Fuzzy, "hsl", false).unwrap();
+        assert_eq!(matcher.highlights("HugsLib", false), vec![0, 3, 4]);
+        assert!(matcher.highlights("Harmony", false).is_empty());
+
+        let matcher = Matcher::new(SearchMode::Regex, "hugs", false).unwrap();
+        assert!(matcher.highlights("HugsLib", false).is_empty());
+    }
+}
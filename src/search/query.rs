@@ -0,0 +1,483 @@
+use std::{collections::BTreeSet, fmt::Display};
+
+use crate::{
+    game::mods::{ModIssues, ModMetaData, Source},
+    search::IndexEntry,
+};
+
+/// A parsed search query, matching mods that satisfy every term.
+///
+/// Queries are whitespace separated terms, for example
+/// `author:Oskar source:steam tag:qol has:issues version:1.5 lang:french -id:hugslib "exact phrase"`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Query {
+    pub terms: Vec<Term>,
+}
+
+/// A single, optionally negated, query term.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Term {
+    pub negated: bool,
+    pub filter: Filter,
+}
+
+/// Filter applied by a query term.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Filter {
+    /// Free text or quoted phrase matched against name, id and notes
+    Text(String),
+    /// Substring of any author
+    Author(String),
+    /// Exact mod source
+    Source(Source),
+    /// Exact user-defined tag, ignoring case
+    Tag(String),
+    /// Mod has the given property
+    Has(Property),
+    /// Exact supported game version
+    Version(String),
+    /// Substring of the package id
+    Id(String),
+    /// Substring of the name
+    Name(String),
+    /// Substring of the personal note
+    Note(String),
+    /// Substring of any available language, ignoring case
+    Language(String),
+}
+
+/// Properties that can be checked with `has:`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Property {
+    Issues,
+    Errors,
+    Warnings,
+    Notes,
+    Tags,
+}
+
+/// Error returned when a query cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    /// byte offset of the error in the query
+    pub position: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    UnknownField(String),
+    MissingValue(String),
+    UnterminatedQuote,
+    UnknownSource(String),
+    UnknownProperty(String),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            ParseErrorKind::UnknownField(field) => write!(f, "Unknown field '{field}'"),
+            ParseErrorKind::MissingValue(field) => write!(f, "Missing value for '{field}:'"),
+            ParseErrorKind::UnterminatedQuote => write!(f, "Unterminated quote"),
+            ParseErrorKind::UnknownSource(source) => write!(
+                f,
+                "Unknown source '{source}', expected official, local or steam"
+            ),
+            ParseErrorKind::UnknownProperty(property) => write!(
+                f,
+                "Unknown property '{property}', expected issues, errors, warnings, notes or tags"
+            ),
+        }?;
+        write!(f, " at column {}", self.position + 1)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Data about a mod that is not part of its metadata, used when matching queries.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MatchContext<'a> {
+    pub tags: Option<&'a BTreeSet<String>>,
+    pub note: Option<&'a str>,
+    pub issues: Option<&'a ModIssues>,
+    /// languages available from the mod or translation mods
+    pub languages: Option<&'a BTreeSet<String>>,
+    /// precomputed search data, used to avoid normalizing mod metadata on every match
+    pub entry: Option<&'a IndexEntry>,
+    pub case_sensitive: bool,
+}
+
+impl MatchContext<'_> {
+    /// Substring match respecting case sensitivity, using the lowercase haystack if available.
+    fn contains(&self, haystack: &str, lowercase: Option<&str>, needle: &str) -> bool {
+        match (self.case_sensitive, lowercase) {
+            (true, _) => haystack.contains(needle),
+            (false, Some(lowercase)) => lowercase.contains(&needle.to_lowercase()),
+            (false, None) => haystack.to_lowercase().contains(&needle.to_lowercase()),
+        }
+    }
+}
+
+impl Query {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut parser = Parser { input, pos: 0 };
+        let mut terms = Vec::new();
+        loop {
+            parser.skip_whitespace();
+            if parser.peek().is_none() {
+                break;
+            }
+            terms.push(parser.parse_term()?);
+        }
+        Ok(Self { terms })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub fn matches(&self, mod_meta: &ModMetaData, cx: &MatchContext) -> bool {
+        self.terms
+            .iter()
+            .all(|term| term.filter.matches(mod_meta, cx) != term.negated)
+    }
+}
+
+impl Filter {
+    pub fn matches(&self, mod_meta: &ModMetaData, cx: &MatchContext) -> bool {
+        let name = || cx.entry.map(|entry| entry.name.as_str());
+        let id = || cx.entry.map(|entry| entry.id.as_str());
+        match self {
+            // free text also matches the start of indexed words when ignoring case
+            Filter::Text(text) => {
+                cx.contains(&mod_meta.name, name(), text)
+                    || cx.contains(&mod_meta.id, id(), text)
+                    || cx.note.is_some_and(|note| cx.contains(note, None, text))
+                    || (!cx.case_sensitive
+                        && cx
+                            .entry
+                            .is_some_and(|entry| entry.has_token_prefix(&text.to_lowercase())))
+            }
+            Filter::Author(author) => mod_meta.authors.iter().enumerate().any(|(ix, a)| {
+                let lowercase = cx.entry.and_then(|entry| entry.authors.get(ix));
+                cx.contains(a, lowercase.map(String::as_str), author)
+            }),
+            Filter::Source(source) => mod_meta.source == *source,
+            Filter::Tag(tag) => cx
+                .tags
+                .is_some_and(|tags| tags.iter().any(|t| t.eq_ignore_ascii_case(tag))),
+            Filter::Has(property) => match property {
+                Property::Issues => cx.issues.is_some_and(ModIssues::has_issues),
+                Property::Errors => cx.issues.is_some_and(ModIssues::has_errors),
+                Property::Warnings => cx.issues.is_some_and(ModIssues::has_warnings),
+                Property::Notes => cx.note.is_some_and(|note| !note.trim().is_empty()),
+                Property::Tags => cx.tags.is_some_and(|tags| !tags.is_empty()),
+            },
+            Filter::Version(version) => mod_meta.supported_versions.contains(version),
+            Filter::Id(text) => cx.contains(&mod_meta.id, id(), text),
+            Filter::Name(text) => cx.contains(&mod_meta.name, name(), text),
+            Filter::Note(text) => cx.note.is_some_and(|note| cx.contains(note, None, text)),
+            Filter::Language(language) => cx.languages.is_some_and(|languages| {
+                let language = language.to_lowercase();
+                languages
+                    .iter()
+                    .any(|l| l.to_lowercase().contains(&language))
+            }),
+        }
+    }
+
+    fn from_field(field: &str, value: String, position: usize) -> Result<Self, ParseError> {
+        let error = |kind| ParseError { kind, position };
+        match field.to_lowercase().as_str() {
+            "author" | "authors" => Ok(Filter::Author(value)),
+            "source" => Source::try_from(value.to_lowercase().as_str())
+                .map(Filter::Source)
+                .map_err(|_| error(ParseErrorKind::UnknownSource(value))),
+            "tag" => Ok(Filter::Tag(value)),
+            "has" => match value.to_lowercase().as_str() {
+                "issues" | "issue" => Ok(Filter::Has(Property::Issues)),
+                "errors" | "error" => Ok(Filter::Has(Property::Errors)),
+                "warnings" | "warning" => Ok(Filter::Has(Property::Warnings)),
+                "notes" | "note" => Ok(Filter::Has(Property::Notes)),
+                "tags" | "tag" => Ok(Filter::Has(Property::Tags)),
+                _ => Err(error(ParseErrorKind::UnknownProperty(value))),
+            },
+            "version" => Ok(Filter::Version(value)),
+            "id" => Ok(Filter::Id(value)),
+            "name" => Ok(Filter::Name(value)),
+            "note" => Ok(Filter::Note(value)),
+            "lang" | "language" => Ok(Filter::Language(value)),
+            _ => unreachable!("field names are checked before parsing values"),
+        }
+    }
+}
+
+const FIELDS: &[&str] = &[
+    "author", "authors", "source", "tag", "has", "version", "id", "name", "note", "lang",
+    "language",
+];
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn peek_second(&self) -> Option<char> {
+        self.input[self.pos..].chars().nth(1)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> &str {
+        let start = self.pos;
+        while self.peek().is_some_and(&f) {
+            self.bump();
+        }
+        &self.input[start..self.pos]
+    }
+
+    fn parse_term(&mut self) -> Result<Term, ParseError> {
+        // a lone `-` is treated as text rather than negating nothing
+        let negated =
+            self.peek() == Some('-') && self.peek_second().is_some_and(|c| !c.is_whitespace());
+        if negated {
+            self.bump();
+        }
+
+        if self.peek() == Some('"') {
+            let text = self.parse_quoted()?;
+            return Ok(Term {
+                negated,
+                filter: Filter::Text(text),
+            });
+        }
+
+        let field_start = self.pos;
+        let word = self
+            .take_while(|c| !c.is_whitespace() && c != ':')
+            .to_string();
+        if self.peek() != Some(':') {
+            return Ok(Term {
+                negated,
+                filter: Filter::Text(word),
+            });
+        }
+
+        if !FIELDS.contains(&word.to_lowercase().as_str()) {
+            return Err(ParseError {
+                kind: ParseErrorKind::UnknownField(word),
+                position: field_start,
+            });
+        }
+
+        self.bump();
+        let value_start = self.pos;
+        let value = match self.peek() {
+            Some('"') => self.parse_quoted()?,
+            _ => self.take_while(|c| !c.is_whitespace()).to_string(),
+        };
+        if value.is_empty() {
+            return Err(ParseError {
+                kind: ParseErrorKind::MissingValue(word),
+                position: value_start,
+            });
+        }
+
+        Ok(Term {
+            negated,
+            filter: Filter::from_field(&word, value, value_start)?,
+        })
+    }
+
+    fn parse_quoted(&mut self) -> Result<String, ParseError> {
+        let start = self.pos;
+        self.bump();
+        let text = self.take_while(|c| c != '"').to_string();
+        match self.bump() {
+            Some('"') => Ok(text),
+            _ => Err(ParseError {
+                kind: ParseErrorKind::UnterminatedQuote,
+                position: start,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(negated: bool, filter: Filter) -> Term {
+        Term { negated, filter }
+    }
+
+    fn error(input: &str) -> ParseError {
+        Query::parse(input).expect_err("query should not parse")
+    }
+
+    #[test]
+    fn test_parse_empty() {
+        assert!(Query::parse("").unwrap().is_empty());
+        assert!(Query::parse("   ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_text() {
+        let query = Query::parse("harmony  hugslib").unwrap();
+        assert_eq!(
+            query.terms,
+            vec![
+                term(false, Filter::Text("harmony".into())),
+                term(false, Filter::Text("hugslib".into())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_example() {
+        let query = Query::parse(
+            r#"author:Oskar source:steam tag:qol has:issues version:1.5 lang:french -id:hugslib "exact phrase""#,
+        )
+        .unwrap();
+        assert_eq!(
+            query.terms,
+            vec![
+                term(false, Filter::Author("Oskar".into())),
+                term(false, Filter::Source(Source::Steam)),
+                term(false, Filter::Tag("qol".into())),
+                term(false, Filter::Has(Property::Issues)),
+                term(false, Filter::Version("1.5".into())),
+                term(false, Filter::Language("french".into())),
+                term(true, Filter::Id("hugslib".into())),
+                term(false, Filter::Text("exact phrase".into())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_quoted_value() {
+        let query = Query::parse(r#"-author:"Oskar Potocki" NAME:core"#).unwrap();
+        assert_eq!(
+            query.terms,
+            vec![
+                term(true, Filter::Author("Oskar Potocki".into())),
+                term(false, Filter::Name("core".into())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_lone_dash() {
+        let query = Query::parse("a - b").unwrap();
+        assert_eq!(query.terms[1], term(false, Filter::Text("-".into())));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            error("foo bar:baz"),
+            ParseError {
+                kind: ParseErrorKind::UnknownField("bar".into()),
+                position: 4,
+            }
+        );
+        assert_eq!(
+            error("tag:"),
+            ParseError {
+                kind: ParseErrorKind::MissingValue("tag".into()),
+                position: 4,
+            }
+        );
+        assert_eq!(
+            error(r#"a "unterminated"#),
+            ParseError {
+                kind: ParseErrorKind::UnterminatedQuote,
+                position: 2,
+            }
+        );
+        assert_eq!(
+            error("source:github"),
+            ParseError {
+                kind: ParseErrorKind::UnknownSource("github".into()),
+                position: 7,
+            }
+        );
+        assert_eq!(
+            error("has:bugs"),
+            ParseError {
+                kind: ParseErrorKind::UnknownProperty("bugs".into()),
+                position: 4,
+            }
+        );
+        assert_eq!(
+            error("tag:").to_string(),
+            "Missing value for 'tag:' at column 5"
+        );
+    }
+
+    #[test]
+    fn test_matches() {
+        let mod_meta = ModMetaData {
+            id: "brrainz.harmony".into(),
+            name: "Harmony".into(),
+            authors: vec!["Andreas Pardeike".into()],
+            supported_versions: vec!["1.5".into()],
+            source: Source::Steam,
+            ..Default::default()
+        };
+        let tags = BTreeSet::from(["Library".to_string()]);
+        let languages = BTreeSet::from(["English".to_string(), "ChineseSimplified".to_string()]);
+        let cx = MatchContext {
+            tags: Some(&tags),
+            note: Some("keep at the top"),
+            languages: Some(&languages),
+            ..Default::default()
+        };
+        let matches = |input: &str| Query::parse(input).unwrap().matches(&mod_meta, &cx);
+
+        assert!(matches(""));
+        assert!(matches("harm"));
+        assert!(matches("BRRAINZ"));
+        assert!(matches(r#""the top""#));
+        assert!(matches(
+            "author:pardeike source:steam tag:library version:1.5"
+        ));
+        assert!(matches("has:notes has:tags -has:issues"));
+        assert!(!matches("-id:harmony"));
+        assert!(!matches("source:local"));
+        assert!(!matches("version:1.4"));
+        assert!(!matches("note:bottom"));
+        assert!(matches("lang:chinese language:English"));
+        assert!(!matches("lang:french"));
+
+        let entry = IndexEntry::new(&mod_meta, Some(&tags));
+        let indexed_cx = MatchContext {
+            entry: Some(&entry),
+            ..cx
+        };
+        let query = Query::parse("pardei author:ANDREAS -id:core").unwrap();
+        assert!(query.matches(&mod_meta, &indexed_cx));
+
+        let cx = MatchContext {
+            case_sensitive: true,
+            ..cx
+        };
+        let query = Query::parse("HARMONY").unwrap();
+        assert!(!query.matches(&mod_meta, &cx));
+    }
+}
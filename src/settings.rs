@@ -1,11 +1,50 @@
-use std::{fs, path::PathBuf};
+use std::{collections::BTreeSet, fs, path::PathBuf};
 
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
 
-use crate::game;
+use crate::{game, search::ModFilter};
 
 mod paths;
 
+/// Name of the setup using the directories in the settings themselves, rather than an instance.
+pub const DEFAULT_INSTANCE: &str = "default";
+
+/// A named setup of the game with its own data folders, e.g. for modded and vanilla play.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Instance {
+    pub name: String,
+    /// Stable identifier partitioning the data of the instance, kept when it is renamed.
+    #[serde(default)]
+    pub id: String,
+    /// The directory where configuration files are stored, inside the save data folder.
+    pub config_dir: PathBuf,
+    /// The directory where local mods are stored.
+    pub local_mods_dir: PathBuf,
+    /// The game directory, if different from the default setup.
+    #[serde(default)]
+    pub game_dir: Option<PathBuf>,
+    /// The directory where official mods are stored, if not the one in the game directory.
+    #[serde(default)]
+    pub official_mods_dir: Option<PathBuf>,
+    /// Quick filters enabled for the active mods list.
+    #[serde(default)]
+    pub active_list_filters: BTreeSet<ModFilter>,
+    /// Quick filters enabled for the inactive mods list.
+    #[serde(default)]
+    pub inactive_list_filters: BTreeSet<ModFilter>,
+}
+
+impl Instance {
+    /// Get the save data folder passed to the game with `-savedatafolder`.
+    pub fn save_data_dir(&self) -> PathBuf {
+        self.config_dir
+            .parent()
+            .unwrap_or(&self.config_dir)
+            .to_path_buf()
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Settings {
     /// The game directory, where the game executable is located.
@@ -25,6 +64,41 @@ pub struct Settings {
     separate_search_bar: bool,
     /// Automatically activate case sensitivity if searches contain uppercase letters.
     smart_search: bool,
+    /// Quick filters enabled for the active mods list.
+    #[serde(default)]
+    active_list_filters: BTreeSet<ModFilter>,
+    /// Quick filters enabled for the inactive mods list.
+    #[serde(default)]
+    inactive_list_filters: BTreeSet<ModFilter>,
+    /// Largest texture resolution before a texture is reported as oversized.
+    #[serde(default = "default_max_texture_resolution")]
+    max_texture_resolution: u32,
+    /// Executable to launch instead of the one in the game directory.
+    #[serde(default)]
+    game_executable: Option<PathBuf>,
+    /// Arguments passed to the game when launching it.
+    #[serde(default)]
+    launch_args: String,
+    /// Launch the game through Steam instead of starting the executable.
+    #[serde(default)]
+    launch_via_steam: bool,
+    /// Save the mod order to the game before launching it.
+    #[serde(default = "default_save_before_launch")]
+    save_before_launch: bool,
+    /// Named setups that can be switched between.
+    #[serde(default)]
+    instances: Vec<Instance>,
+    /// The name of the instance in use, or none for the default setup.
+    #[serde(default)]
+    active_instance: Option<String>,
+}
+
+fn default_save_before_launch() -> bool {
+    true
+}
+
+fn default_max_texture_resolution() -> u32 {
+    game::mods::DEFAULT_MAX_TEXTURE_RESOLUTION
 }
 
 impl Settings {
@@ -33,34 +107,80 @@ impl Settings {
         Self::default()
     }
 
-    /// Get the game directory.
+    /// Get the game directory of the active instance.
     pub fn game_dir(&self) -> &PathBuf {
-        &self.game_dir
+        self.active_instance()
+            .and_then(|instance| instance.game_dir.as_ref())
+            .unwrap_or(&self.game_dir)
     }
 
-    /// Set the game directory.
+    /// Set the game directory of the active instance.
     pub fn set_game_dir(&mut self, game_dir: PathBuf) {
-        self.game_dir = game_dir;
+        match self.active_instance_mut() {
+            Some(instance) => instance.game_dir = Some(game_dir),
+            None => self.game_dir = game_dir,
+        }
+    }
+
+    /// Get the official mods directory of the active instance, which is in its game directory
+    /// unless set otherwise.
+    pub fn official_mods_dir(&self) -> PathBuf {
+        self.instance_official_mods_dir(self.active_instance())
+    }
+
+    fn instance_official_mods_dir(&self, instance: Option<&Instance>) -> PathBuf {
+        match instance {
+            Some(Instance {
+                official_mods_dir: Some(official_mods_dir),
+                ..
+            }) => official_mods_dir.clone(),
+            Some(Instance {
+                game_dir: Some(game_dir),
+                ..
+            }) => game::paths::official_mods_dir(game_dir),
+            _ => self.official_mods_dir.clone(),
+        }
     }
 
-    /// Get the official mods directory.
-    pub fn official_mods_dir(&self) -> &PathBuf {
-        &self.official_mods_dir
+    /// Get the official and local mods directories of the setups not in use, which may have mods
+    /// installed that the setup in use does not.
+    pub fn other_mods_dirs(&self) -> Vec<PathBuf> {
+        let current = [self.official_mods_dir(), self.local_mods_dir().clone()];
+        let mut dirs = vec![
+            self.instance_official_mods_dir(None),
+            self.local_mods_dir.clone(),
+        ];
+        for instance in &self.instances {
+            dirs.push(self.instance_official_mods_dir(Some(instance)));
+            dirs.push(instance.local_mods_dir.clone());
+        }
+        let mut seen = BTreeSet::new();
+        dirs.retain(|dir| !current.contains(dir) && seen.insert(dir.clone()));
+        dirs
     }
 
-    /// Set the official mods directory.
+    /// Set the official mods directory of the active instance.
     pub fn set_official_mods_dir(&mut self, official_mods_dir: PathBuf) {
-        self.official_mods_dir = official_mods_dir;
+        match self.active_instance_mut() {
+            Some(instance) => instance.official_mods_dir = Some(official_mods_dir),
+            None => self.official_mods_dir = official_mods_dir,
+        }
     }
 
-    /// Get the local mods directory.
+    /// Get the local mods directory of the active instance.
     pub fn local_mods_dir(&self) -> &PathBuf {
-        &self.local_mods_dir
+        match self.active_instance() {
+            Some(instance) => &instance.local_mods_dir,
+            None => &self.local_mods_dir,
+        }
     }
 
-    /// Set the local mods directory.
+    /// Set the local mods directory of the active instance.
     pub fn set_local_mods_dir(&mut self, local_mods_dir: PathBuf) {
-        self.local_mods_dir = local_mods_dir;
+        match self.active_instance_mut() {
+            Some(instance) => instance.local_mods_dir = local_mods_dir,
+            None => self.local_mods_dir = local_mods_dir,
+        }
     }
 
     /// Get the Steam mods directory.
@@ -73,19 +193,114 @@ impl Settings {
         self.steam_mods_dir = steam_mods_dir;
     }
 
-    /// Get the configuration directory.
+    /// Get the configuration directory of the active instance.
     pub fn config_dir(&self) -> &PathBuf {
-        &self.config_dir
+        match self.active_instance() {
+            Some(instance) => &instance.config_dir,
+            None => &self.config_dir,
+        }
     }
 
-    /// Set the configuration directory.
+    /// Set the configuration directory of the active instance.
     pub fn set_config_dir(&mut self, config_dir: PathBuf) {
-        self.config_dir = config_dir;
+        match self.active_instance_mut() {
+            Some(instance) => instance.config_dir = config_dir,
+            None => self.config_dir = config_dir,
+        }
     }
 
     /// Get the path to the mods config file.
     pub fn mods_config_file(&self) -> PathBuf {
-        game::paths::mods_config_file(&self.config_dir)
+        game::paths::mods_config_file(self.config_dir())
+    }
+
+    /// Get the directory the game writes its log files to.
+    pub fn log_dir(&self) -> PathBuf {
+        game::paths::log_dir(self.config_dir())
+    }
+
+    /// Get the named instances.
+    pub fn instances(&self) -> &[Instance] {
+        &self.instances
+    }
+
+    /// Get the instance in use, or none for the default setup.
+    pub fn active_instance(&self) -> Option<&Instance> {
+        let name = self.active_instance.as_ref()?;
+        self.instances
+            .iter()
+            .find(|instance| &instance.name == name)
+    }
+
+    fn active_instance_mut(&mut self) -> Option<&mut Instance> {
+        let name = self.active_instance.as_ref()?;
+        self.instances
+            .iter_mut()
+            .find(|instance| &instance.name == name)
+    }
+
+    /// Get the name identifying the setup in use, including the default setup.
+    pub fn instance_name(&self) -> &str {
+        self.active_instance()
+            .map_or(DEFAULT_INSTANCE, |instance| instance.name.as_str())
+    }
+
+    /// Get the id partitioning the data of the setup in use, including the default setup.
+    pub fn instance_id(&self) -> &str {
+        self.active_instance()
+            .map_or(DEFAULT_INSTANCE, |instance| instance.id.as_str())
+    }
+
+    /// Give an id to instances created before instances had ids.
+    fn assign_instance_ids(&mut self) {
+        for instance in &mut self.instances {
+            if instance.id.is_empty() {
+                // their data was partitioned by name until now
+                instance.id = instance.name.clone();
+            }
+        }
+    }
+
+    /// Switch to a named instance, or to the default setup if the name is none or unknown.
+    pub fn set_active_instance(&mut self, name: Option<String>) {
+        self.active_instance =
+            name.filter(|name| self.instances.iter().any(|instance| &instance.name == name));
+    }
+
+    /// Add an instance copying the directories in use, returning its name.
+    pub fn add_instance(&mut self) -> String {
+        let name = (self.instances.len() + 1..)
+            .map(|n| format!("Instance {n}"))
+            .find(|name| !self.instances.iter().any(|instance| &instance.name == name))
+            .expect("an unused instance name");
+        let created = Utc::now().timestamp_millis();
+        let id = (created..)
+            .map(|n| format!("instance-{n}"))
+            .find(|id| id != DEFAULT_INSTANCE && !self.instances.iter().any(|i| &i.id == id))
+            .expect("an unused instance id");
+        let active_instance = self.active_instance();
+        self.instances.push(Instance {
+            name: name.clone(),
+            id,
+            config_dir: self.config_dir().clone(),
+            local_mods_dir: self.local_mods_dir().clone(),
+            game_dir: active_instance.and_then(|instance| instance.game_dir.clone()),
+            official_mods_dir: active_instance
+                .and_then(|instance| instance.official_mods_dir.clone()),
+            active_list_filters: self.active_list_filters().clone(),
+            inactive_list_filters: self.inactive_list_filters().clone(),
+        });
+        name
+    }
+
+    /// Set whether to show advanced search controls.
+    pub fn set_advanced_search(&mut self, advanced_search: bool) {
+        self.advanced_search = advanced_search;
+    }
+
+    /// Check if advanced search controls are shown.
+    pub fn advanced_search(&self) -> bool {
+        self.advanced_search
     }
 
     /// Set whether to separate the search bar from mod list controls.
@@ -108,6 +323,91 @@ impl Settings {
         self.smart_search
     }
 
+    /// Get the quick filters enabled for the active mods list of the active instance.
+    pub fn active_list_filters(&self) -> &BTreeSet<ModFilter> {
+        match self.active_instance() {
+            Some(instance) => &instance.active_list_filters,
+            None => &self.active_list_filters,
+        }
+    }
+
+    /// Set the quick filters enabled for the active mods list of the active instance.
+    pub fn set_active_list_filters(&mut self, filters: BTreeSet<ModFilter>) {
+        match self.active_instance_mut() {
+            Some(instance) => instance.active_list_filters = filters,
+            None => self.active_list_filters = filters,
+        }
+    }
+
+    /// Get the quick filters enabled for the inactive mods list of the active instance.
+    pub fn inactive_list_filters(&self) -> &BTreeSet<ModFilter> {
+        match self.active_instance() {
+            Some(instance) => &instance.inactive_list_filters,
+            None => &self.inactive_list_filters,
+        }
+    }
+
+    /// Set the quick filters enabled for the inactive mods list of the active instance.
+    pub fn set_inactive_list_filters(&mut self, filters: BTreeSet<ModFilter>) {
+        match self.active_instance_mut() {
+            Some(instance) => instance.inactive_list_filters = filters,
+            None => self.inactive_list_filters = filters,
+        }
+    }
+
+    /// Get the largest texture resolution before a texture is reported as oversized.
+    pub fn max_texture_resolution(&self) -> u32 {
+        self.max_texture_resolution
+    }
+
+    /// Set the largest texture resolution before a texture is reported as oversized.
+    pub fn set_max_texture_resolution(&mut self, max_texture_resolution: u32) {
+        self.max_texture_resolution = max_texture_resolution;
+    }
+
+    /// Get the executable to launch, which is in the game directory unless overridden.
+    pub fn game_executable(&self) -> PathBuf {
+        match &self.game_executable {
+            Some(executable) => executable.clone(),
+            None => game::paths::game_executable(self.game_dir()),
+        }
+    }
+
+    /// Set the executable to launch instead of the one in the game directory.
+    pub fn set_game_executable(&mut self, game_executable: Option<PathBuf>) {
+        self.game_executable = game_executable;
+    }
+
+    /// Get the arguments passed to the game when launching it.
+    pub fn launch_args(&self) -> &str {
+        &self.launch_args
+    }
+
+    /// Set the arguments passed to the game when launching it.
+    pub fn set_launch_args(&mut self, launch_args: String) {
+        self.launch_args = launch_args;
+    }
+
+    /// Check if the game is launched through Steam.
+    pub fn launch_via_steam(&self) -> bool {
+        self.launch_via_steam
+    }
+
+    /// Set whether to launch the game through Steam.
+    pub fn set_launch_via_steam(&mut self, launch_via_steam: bool) {
+        self.launch_via_steam = launch_via_steam;
+    }
+
+    /// Check if the mod order is saved to the game before launching it.
+    pub fn save_before_launch(&self) -> bool {
+        self.save_before_launch
+    }
+
+    /// Set whether to save the mod order to the game before launching it.
+    pub fn set_save_before_launch(&mut self, save_before_launch: bool) {
+        self.save_before_launch = save_before_launch;
+    }
+
     /// Load settings from the default settings file, or return default settings if the file does not exist.
     pub fn load_or_default() -> Self {
         Self::load().unwrap_or_default()
@@ -123,11 +423,13 @@ impl Settings {
             })
             .ok()?;
 
-        toml::from_str(&settings)
+        let mut settings: Self = toml::from_str(&settings)
             .map_err(|e| {
                 log::error!("error parsing settings file {settings_path:?}: {e}");
             })
-            .ok()
+            .ok()?;
+        settings.assign_instance_ids();
+        Some(settings)
     }
 
     pub fn save(&self) {
@@ -161,6 +463,15 @@ impl Default for Settings {
             advanced_search: true,
             separate_search_bar: true,
             smart_search: true,
+            active_list_filters: BTreeSet::new(),
+            inactive_list_filters: BTreeSet::new(),
+            max_texture_resolution: default_max_texture_resolution(),
+            game_executable: None,
+            launch_args: String::new(),
+            launch_via_steam: false,
+            save_before_launch: default_save_before_launch(),
+            instances: Vec::new(),
+            active_instance: None,
         }
     }
 }
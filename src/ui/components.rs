@@ -1,4 +1,5 @@
 pub mod button;
+pub mod context_menu;
 pub mod icon;
 pub mod mod_details;
 pub mod mod_list;
@@ -6,6 +7,7 @@ pub mod text_input;
 pub mod tooltip;
 
 pub use button::*;
+pub use context_menu::*;
 pub use icon::*;
 pub use mod_details::*;
 pub use mod_list::*;
@@ -0,0 +1,138 @@
+use std::rc::Rc;
+
+use gpui::{DismissEvent, EventEmitter, FocusHandle, Focusable};
+
+use crate::{theme::colors, ui::prelude::*};
+
+actions!(context_menu, [Dismiss]);
+
+type OnSelectFunc = Rc<dyn Fn(&mut Window, &mut App) + 'static>;
+
+enum ContextMenuEntry {
+    Item {
+        label: SharedString,
+        disabled: bool,
+        handler: OnSelectFunc,
+    },
+    Separator,
+}
+
+/// A popup menu of actions, dismissed when an entry is selected or the user clicks outside it.
+pub struct ContextMenu {
+    focus_handle: FocusHandle,
+    entries: Vec<ContextMenuEntry>,
+}
+
+impl ContextMenu {
+    pub fn build(
+        window: &mut Window,
+        cx: &mut App,
+        f: impl FnOnce(Self, &mut Window, &mut Context<Self>) -> Self,
+    ) -> Entity<Self> {
+        cx.new(|cx| {
+            let menu = Self {
+                focus_handle: cx.focus_handle(),
+                entries: Vec::new(),
+            };
+            f(menu, window, cx)
+        })
+    }
+
+    /// Add an entry that runs the handler when selected.
+    pub fn entry(
+        mut self,
+        label: impl Into<SharedString>,
+        handler: impl Fn(&mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.entries.push(ContextMenuEntry::Item {
+            label: label.into(),
+            disabled: false,
+            handler: Rc::new(handler),
+        });
+        self
+    }
+
+    /// Add an entry that is shown but can not be selected.
+    pub fn disabled_entry(mut self, label: impl Into<SharedString>) -> Self {
+        self.entries.push(ContextMenuEntry::Item {
+            label: label.into(),
+            disabled: true,
+            handler: Rc::new(|_, _| {}),
+        });
+        self
+    }
+
+    pub fn separator(mut self) -> Self {
+        self.entries.push(ContextMenuEntry::Separator);
+        self
+    }
+
+    fn dismiss(&mut self, _: &Dismiss, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+}
+
+impl FluentBuilder for ContextMenu {}
+
+impl EventEmitter<DismissEvent> for ContextMenu {}
+
+impl Focusable for ContextMenu {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for ContextMenu {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .id("context-menu")
+            .key_context("ContextMenu")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::dismiss))
+            .on_mouse_down_out(cx.listener(|_, _, _, cx| cx.emit(DismissEvent)))
+            .flex()
+            .flex_col()
+            .min_w_48()
+            .py_1()
+            .bg(rgba(colors::ELEVATED_SURFACE_BACKGROUND))
+            .rounded_lg()
+            .border_1()
+            .border_color(rgba(colors::BORDER_VARIANT))
+            .shadow_md()
+            .text_sm()
+            .text_color(rgba(colors::TEXT))
+            .children(self.entries.iter().enumerate().map(|(ix, entry)| {
+                match entry {
+                    ContextMenuEntry::Separator => div()
+                        .h_px()
+                        .my_1()
+                        .bg(rgba(colors::BORDER))
+                        .into_any_element(),
+                    ContextMenuEntry::Item {
+                        label,
+                        disabled,
+                        handler,
+                    } => {
+                        let handler = handler.clone();
+                        div()
+                            .id(ix)
+                            .px_2()
+                            .py_0p5()
+                            .when(*disabled, |this| {
+                                this.text_color(rgba(colors::TEXT_DISABLED))
+                            })
+                            .when(!*disabled, |this| {
+                                this.cursor_pointer()
+                                    .hover(|style| style.bg(rgba(colors::ELEMENT_HOVER)))
+                                    .on_click(cx.listener(move |_, _, window, cx| {
+                                        handler(window, cx);
+                                        cx.emit(DismissEvent);
+                                    }))
+                            })
+                            .child(label.clone())
+                            .into_any_element()
+                    }
+                }
+            }))
+    }
+}
@@ -5,17 +5,33 @@ use crate::{theme::colors, ui::prelude::*};
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum IconName {
     // Controls
+    Activate,
+    Backup,
+    Bad,
+    Bisect,
     CaseSensitive,
     Clear,
+    Deactivate,
+    Filter,
+    Fuzzy,
+    Good,
+    Launch,
+    OpenFolder,
+    Regex,
     Reload,
+    Remove,
     Reset,
+    Restore,
     Save,
     Sort,
     Supported,
+    Tag,
     // Panes?
+    Defs,
     History,
     Settings,
     // Indicators
+    Note,
     Warning,
     Error,
     // Mod sources
@@ -28,15 +44,31 @@ pub enum IconName {
 impl IconName {
     pub fn path(&self) -> &'static str {
         match self {
+            IconName::Activate => "icons/list-plus.svg",
+            IconName::Backup => "icons/archive.svg",
+            IconName::Bad => "icons/thumbs-down.svg",
+            IconName::Bisect => "icons/split.svg",
             IconName::Clear => "icons/list-x.svg",
+            IconName::Deactivate => "icons/list-minus.svg",
+            IconName::Filter => "icons/funnel.svg",
+            IconName::Fuzzy => "icons/text-search.svg",
+            IconName::Good => "icons/thumbs-up.svg",
+            IconName::Launch => "icons/play.svg",
+            IconName::OpenFolder => "icons/folder-open.svg",
+            IconName::Regex => "icons/regex.svg",
             IconName::CaseSensitive => "icons/a-large-small.svg",
             IconName::Reload => "icons/folder-sync.svg",
+            IconName::Remove => "icons/trash-2.svg",
             IconName::Reset => "icons/list-restart.svg",
+            IconName::Restore => "icons/archive-restore.svg",
             IconName::Save => "icons/save.svg",
             IconName::Sort => "icons/arrow-up-down.svg",
             IconName::Supported => "icons/cable.svg",
+            IconName::Tag => "icons/tag.svg",
+            IconName::Defs => "icons/book-open.svg",
             IconName::History => "icons/history.svg",
             IconName::Settings => "icons/settings.svg",
+            IconName::Note => "icons/sticky-note.svg",
             IconName::Warning => "icons/triangle-alert.svg",
             IconName::Error => "icons/octagon-x.svg",
             IconName::Local => "icons/hard-drive.svg",
@@ -1,23 +1,284 @@
-use std::fs;
+use std::{fs, time::Duration};
 
-use gpui::{img, relative};
+use chrono::{DateTime, Local};
+use gpui::{
+    ClickEvent, DismissEvent, Focusable, Pixels, Point, PromptLevel, Subscription, Task, anchored,
+    deferred, img, relative,
+};
 
-use crate::{project::Project, theme::colors, ui::prelude::*};
+use crate::{
+    game::{
+        defs::DefConflict,
+        mods::{LanguageCoverage, ModAssembly, ModMetaData, ModSettingsFile, format_bytes},
+    },
+    project::{Project, ProjectEvent},
+    theme::colors,
+    ui::{ContextMenu, TextInput, TextInputEvent, prelude::*},
+};
 
 pub struct ModDetails {
     project: Entity<Project>,
+    tag_input: Entity<TextInput>,
+    note_input: Entity<TextInput>,
+    /// id of the mod whose note is loaded into note_input
+    note_mod_id: Option<String>,
+    /// mod id and note typed but not saved yet
+    pending_note: Option<(String, String)>,
+    /// task saving the pending note once typing pauses
+    note_save_task: Option<Task<()>>,
+    /// flag to indicate if def conflicts are expanded
+    show_def_conflicts: bool,
+    /// menu to pick the mod settings backup to restore
+    settings_backup_menu: Option<(Entity<ContextMenu>, Point<Pixels>, Subscription)>,
 }
 
 impl ModDetails {
-    pub fn new(project: Entity<Project>) -> Self {
-        Self { project }
+    pub fn new(project: Entity<Project>, cx: &mut Context<Self>) -> Self {
+        let tag_input = TextInput::new(cx);
+        tag_input.update(cx, |input, _| {
+            input.placeholder("Add tag...");
+        });
+
+        cx.subscribe(&tag_input, |details, input, event, cx| match event {
+            TextInputEvent::ContentChanged { .. } => {}
+            TextInputEvent::Confirmed { content } => {
+                details.project.update(cx, |project, _| {
+                    project.tag_selected_mods(content);
+                });
+                input.update(cx, |input, cx| {
+                    input.reset();
+                    cx.notify();
+                });
+            }
+        })
+        .detach();
+
+        let note_input = TextInput::new(cx);
+        note_input.update(cx, |input, _| {
+            input.placeholder("Add a note...");
+        });
+
+        cx.subscribe(&note_input, |details, _, event, cx| {
+            let Some(mod_id) = details.note_mod_id.clone() else {
+                return;
+            };
+            match event {
+                TextInputEvent::ContentChanged { content } => {
+                    details.pending_note = Some((mod_id, content.to_string()));
+                    details.note_save_task = Some(cx.spawn(async move |details, cx| {
+                        cx.background_executor().timer(NOTE_SAVE_DELAY).await;
+                        details
+                            .update(cx, |details, cx| details.save_pending_note(cx))
+                            .ok();
+                    }));
+                }
+                TextInputEvent::Confirmed { content } => {
+                    details.pending_note = Some((mod_id, content.to_string()));
+                    details.save_pending_note(cx);
+                }
+            }
+        })
+        .detach();
+
+        cx.subscribe(&project, |details, _, event, cx| {
+            if let ProjectEvent::SelectionChanged = event {
+                details.sync_note_input(cx);
+            }
+        })
+        .detach();
+
+        let mut details = Self {
+            project,
+            tag_input,
+            note_input,
+            note_mod_id: None,
+            pending_note: None,
+            note_save_task: None,
+            show_def_conflicts: false,
+            settings_backup_menu: None,
+        };
+        details.sync_note_input(cx);
+        details
+    }
+
+    fn save_pending_note(&mut self, cx: &mut Context<Self>) {
+        self.note_save_task = None;
+        if let Some((mod_id, note)) = self.pending_note.take() {
+            self.project.update(cx, |project, _| {
+                project.set_mod_note(&mod_id, &note);
+            });
+        }
+    }
+
+    /// Load the note for the selected mod into the note input when the selection changes.
+    fn sync_note_input(&mut self, cx: &mut Context<Self>) {
+        self.save_pending_note(cx);
+        let project = self.project.read(cx);
+        let mod_id = project.selected_mod().map(|mod_meta| mod_meta.id.clone());
+        if mod_id == self.note_mod_id {
+            return;
+        }
+        let note = mod_id
+            .as_ref()
+            .and_then(|id| project.note_for_mod(id))
+            .cloned()
+            .unwrap_or_default();
+        self.note_mod_id = mod_id;
+        self.note_input.update(cx, |input, cx| {
+            input.set_content(note);
+            cx.notify();
+        });
+    }
+
+    /// Reset a mod settings file once the user confirms, which backs it up first.
+    fn confirm_reset_mod_settings(
+        &mut self,
+        file: ModSettingsFile,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let answer = window.prompt(
+            PromptLevel::Warning,
+            &format!("Reset {}?", file.file_name()),
+            Some(
+                "The file is backed up and removed, so the mod writes its default settings \
+                 the next time the game runs.",
+            ),
+            &["Reset", "Cancel"],
+            cx,
+        );
+        cx.spawn(async move |this, cx| {
+            if !matches!(answer.await, Ok(0)) {
+                return;
+            }
+            this.update(cx, |this, cx| {
+                this.project.update(cx, |project, cx| {
+                    project.reset_mod_settings(&file, cx);
+                    cx.notify();
+                });
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Show the backups of a mod's settings to pick one to restore, labeled with the mod list
+    /// they were backed up with.
+    fn deploy_settings_backup_menu(
+        &mut self,
+        mod_meta: &ModMetaData,
+        position: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let project = self.project.clone();
+        let backups: Vec<(String, Vec<ModSettingsFile>)> = project.read_with(cx, |project, _| {
+            project
+                .mod_settings_backups_for(mod_meta)
+                .into_iter()
+                .map(|(snapshot, files)| {
+                    let created = snapshot
+                        .created
+                        .map(|created| {
+                            created
+                                .and_utc()
+                                .with_timezone(&Local)
+                                .format("%Y-%m-%d %H:%M:%S")
+                                .to_string()
+                        })
+                        .unwrap_or_else(|| "Unknown time".to_string());
+                    let mod_list = match project.is_current_mod_list(snapshot) {
+                        true => "current mod list".to_string(),
+                        false => format!("{} active mods", snapshot.active_mods.len()),
+                    };
+                    (format!("{created} ({mod_list})"), files)
+                })
+                .collect()
+        });
+
+        let menu = ContextMenu::build(window, cx, move |menu, _, _| {
+            if backups.is_empty() {
+                return menu.disabled_entry("No backups yet");
+            }
+            backups.into_iter().fold(menu, |menu, (label, files)| {
+                let project = project.clone();
+                menu.entry(label, move |_, cx| {
+                    project.update(cx, |project, cx| {
+                        project.restore_mod_settings(&files, cx);
+                        cx.notify();
+                    });
+                })
+            })
+        });
+
+        window.focus(&menu.focus_handle(cx));
+        let subscription = cx.subscribe(&menu, |this, _, _: &DismissEvent, cx| {
+            this.settings_backup_menu = None;
+            cx.notify();
+        });
+        self.settings_backup_menu = Some((menu, position, subscription));
+        cx.notify();
     }
 }
 
+/// Time typing must pause before a changed note is saved.
+const NOTE_SAVE_DELAY: Duration = Duration::from_millis(500);
+
 // todo: add placeholder with no selected mod
 impl Render for ModDetails {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let selected = self.project.read(cx).selected_mod();
+        let project = self.project.read(cx);
+        let selected = project.selected_mod().cloned();
+        let history = project.mod_history().map(|events| events.to_vec());
+        let tags = selected
+            .as_ref()
+            .and_then(|mod_meta| project.tags_for_mod(&mod_meta.id))
+            .cloned()
+            .unwrap_or_default();
+        let selected_count = project.selected_mods_count();
+        let texture_memory = selected
+            .as_ref()
+            .and_then(|mod_meta| project.texture_report_for_mod(&mod_meta.id))
+            .map(|report| report.memory);
+        let assemblies: Vec<ModAssembly> = selected
+            .as_ref()
+            .map(|mod_meta| {
+                project
+                    .assemblies_for_mod(mod_meta)
+                    .into_iter()
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        let def_conflicts: Vec<DefConflict> = selected
+            .as_ref()
+            .map(|mod_meta| {
+                project
+                    .def_conflicts_for_mod(&mod_meta.id)
+                    .into_iter()
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        let languages: Vec<LanguageCoverage> = selected
+            .as_ref()
+            .map(|mod_meta| project.language_coverage_for_mod(mod_meta))
+            .unwrap_or_default();
+        let mod_settings: Vec<ModSettingsFile> = selected
+            .as_ref()
+            .map(|mod_meta| project.mod_settings_for(&mod_meta.id).to_vec())
+            .unwrap_or_default();
+        let has_settings_backup = selected
+            .as_ref()
+            .is_some_and(|mod_meta| !project.mod_settings_backups_for(mod_meta).is_empty());
+        let show_def_conflicts = self.show_def_conflicts;
+        let content_folders = selected
+            .as_ref()
+            .filter(|mod_meta| {
+                mod_meta.load_folders.is_some() || !mod_meta.version_folders.is_empty()
+            })
+            .map(|mod_meta| project.content_folders_for_mod(mod_meta))
+            .unwrap_or_default();
         div()
             .flex()
             .flex_col()
@@ -64,6 +325,104 @@ impl Render for ModDetails {
                                 .child(mod_meta.id.clone())
                                 .child(format!("Authors: {}", mod_meta.authors.join(", "))),
                         )
+                        .when_some(mod_meta.workshop.clone(), |this, workshop| {
+                            let date = |time: Option<u64>| {
+                                time.and_then(|time| DateTime::from_timestamp(time as i64, 0))
+                                    .map(|date| date.format("%Y-%m-%d").to_string())
+                            };
+                            this.child(
+                                div()
+                                    .flex()
+                                    .flex_row()
+                                    .gap_2()
+                                    .text_sm()
+                                    .text_color(rgba(colors::TEXT_SECONDARY))
+                                    .when_some(date(workshop.time_updated), |this, date| {
+                                        this.child(format!("Updated: {date}"))
+                                    })
+                                    .when_some(date(workshop.time_touched), |this, date| {
+                                        this.child(format!("Subscribed: {date}"))
+                                    }),
+                            )
+                        })
+                        .child(
+                            div()
+                                .flex()
+                                .flex_row()
+                                .flex_wrap()
+                                .items_center()
+                                .gap_1()
+                                .text_sm()
+                                .children(tags.into_iter().map(|tag| {
+                                    let mod_id = mod_meta.id.clone();
+                                    div()
+                                        .id(SharedString::from(format!("tag-{tag}")))
+                                        .px_1()
+                                        .rounded_md()
+                                        .cursor_pointer()
+                                        .bg(rgba(colors::ELEMENT_BACKGROUND))
+                                        .text_color(rgba(colors::TEXT_ACCENT))
+                                        .hover(|style| style.bg(rgba(colors::ELEMENT_HOVER)))
+                                        .tooltip(Tooltip::text("Remove tag"))
+                                        .on_click(cx.listener({
+                                            let tag = tag.clone();
+                                            move |this, _, _, cx| {
+                                                this.project.update(cx, |project, _| {
+                                                    project.untag_mod(&mod_id, &tag);
+                                                });
+                                            }
+                                        }))
+                                        .child(tag)
+                                }))
+                                .child(div().min_w_24().child(self.tag_input.clone()))
+                                .when(selected_count > 1, |this| {
+                                    this.child(
+                                        div().text_color(rgba(colors::TEXT_SECONDARY)).child(
+                                            format!("(adds to {selected_count} selected mods)"),
+                                        ),
+                                    )
+                                }),
+                        )
+                        .when_some(mod_meta.stats, |this, stats| {
+                            this.child(
+                                div()
+                                    .flex()
+                                    .flex_row()
+                                    .flex_wrap()
+                                    .gap_x_2()
+                                    .text_sm()
+                                    .text_color(rgba(colors::TEXT_SECONDARY))
+                                    .child(format!("Size: {}", format_bytes(stats.disk_size)))
+                                    .child(format!("Files: {}", stats.file_count))
+                                    .child(format!(
+                                        "Textures: {} ({})",
+                                        stats.texture_count,
+                                        format_bytes(stats.texture_bytes)
+                                    ))
+                                    .child(format!("Assemblies: {}", stats.assembly_count))
+                                    .child(format!("Defs: {}", stats.def_file_count))
+                                    .child(format!("Patches: {}", stats.patch_file_count))
+                                    .when_some(texture_memory, |this, memory| {
+                                        this.child(format!(
+                                            "Texture memory: ~{}",
+                                            format_bytes(memory)
+                                        ))
+                                    }),
+                            )
+                        })
+                        .child(
+                            div()
+                                .flex()
+                                .flex_col()
+                                .gap_1()
+                                .text_sm()
+                                .child(
+                                    div()
+                                        .text_color(rgba(colors::TEXT_SECONDARY))
+                                        .child("Notes:"),
+                                )
+                                .child(self.note_input.clone()),
+                        )
                         .when(!mod_meta.dependencies.is_empty(), |this| {
                             this.child(
                                 div()
@@ -77,8 +436,277 @@ impl Render for ModDetails {
                                     ),
                             )
                         })
+                        .when(!content_folders.is_empty(), |this| {
+                            this.child(
+                                div()
+                                    .flex()
+                                    .flex_col()
+                                    .text_sm()
+                                    .text_color(rgba(colors::TEXT_SECONDARY))
+                                    .child("Load folders:")
+                                    .children(
+                                        content_folders
+                                            .iter()
+                                            .map(|folder| format!("- /{}", folder.display())),
+                                    ),
+                            )
+                        })
+                        .when(!languages.is_empty(), |this| {
+                            this.child(
+                                div()
+                                    .flex()
+                                    .flex_col()
+                                    .text_sm()
+                                    .text_color(rgba(colors::TEXT_SECONDARY))
+                                    .child("Languages:")
+                                    .children(languages.iter().map(|language| {
+                                        let mut line = format!("- {}", language.language);
+                                        if let Some(provider) = &language.provider {
+                                            line.push_str(&format!(" via '{provider}'"));
+                                        }
+                                        if language.entries.packed {
+                                            line.push_str(" (packed)");
+                                        } else if let Some(coverage) = language.coverage {
+                                            line.push_str(&format!(" (~{:.0}%)", coverage * 100.0));
+                                        }
+                                        line
+                                    })),
+                            )
+                        })
+                        .when(!mod_settings.is_empty() || has_settings_backup, |this| {
+                            this.child(
+                                div()
+                                    .flex()
+                                    .flex_col()
+                                    .text_sm()
+                                    .text_color(rgba(colors::TEXT_SECONDARY))
+                                    .child(
+                                        div()
+                                            .flex()
+                                            .flex_row()
+                                            .items_center()
+                                            .justify_between()
+                                            .child("Settings files:")
+                                            .child(
+                                                div()
+                                                    .flex()
+                                                    .flex_row()
+                                                    .child(
+                                                        IconButton::from_name(
+                                                            "backup mod settings",
+                                                            IconName::Backup,
+                                                        )
+                                                        .on_click(cx.listener({
+                                                            let mod_id = mod_meta.id.clone();
+                                                            move |this, _, _, cx| {
+                                                                this.project.update(
+                                                                    cx,
+                                                                    |project, cx| {
+                                                                        project
+                                                                            .backup_mod_settings(
+                                                                                &mod_id, cx,
+                                                                            );
+                                                                        cx.notify();
+                                                                    },
+                                                                );
+                                                            }
+                                                        }))
+                                                        .disabled(mod_settings.is_empty())
+                                                        .tooltip(Tooltip::text(
+                                                            "Back up settings with the current mod list",
+                                                        )),
+                                                    )
+                                                    .child(
+                                                        IconButton::from_name(
+                                                            "restore mod settings",
+                                                            IconName::Restore,
+                                                        )
+                                                        .on_click(cx.listener({
+                                                            let mod_meta = mod_meta.clone();
+                                                            move |this, event, window, cx| {
+                                                                let ClickEvent::Mouse(
+                                                                    mouse_event,
+                                                                ) = event
+                                                                else {
+                                                                    return;
+                                                                };
+                                                                this.deploy_settings_backup_menu(
+                                                                    &mod_meta,
+                                                                    mouse_event.up.position,
+                                                                    window,
+                                                                    cx,
+                                                                );
+                                                            }
+                                                        }))
+                                                        .disabled(!has_settings_backup)
+                                                        .tooltip(Tooltip::text(
+                                                            "Restore settings from a backup",
+                                                        )),
+                                                    ),
+                                            ),
+                                    )
+                                    .children(mod_settings.iter().map(|file| {
+                                        div()
+                                            .flex()
+                                            .flex_row()
+                                            .items_center()
+                                            .justify_between()
+                                            .child(
+                                                div()
+                                                    .id(SharedString::from(format!(
+                                                        "mod-settings-{}",
+                                                        file.file_name()
+                                                    )))
+                                                    .cursor_pointer()
+                                                    .hover(|style| {
+                                                        style.bg(rgba(colors::ELEMENT_HOVER))
+                                                    })
+                                                    .tooltip(Tooltip::text("Open settings file"))
+                                                    .on_click({
+                                                        let path = file.path.clone();
+                                                        move |_, _, cx| cx.open_with_system(&path)
+                                                    })
+                                                    .child(format!("- {}", file.class)),
+                                            )
+                                            .child(
+                                                IconButton::from_name(
+                                                    SharedString::from(format!(
+                                                        "reset-mod-settings-{}",
+                                                        file.file_name()
+                                                    )),
+                                                    IconName::Reset,
+                                                )
+                                                .on_click(cx.listener({
+                                                    let file = file.clone();
+                                                    move |this, _, window, cx| {
+                                                        this.confirm_reset_mod_settings(
+                                                            file.clone(),
+                                                            window,
+                                                            cx,
+                                                        );
+                                                    }
+                                                }))
+                                                .tooltip(Tooltip::text(
+                                                    "Reset to defaults by removing the file",
+                                                )),
+                                            )
+                                    })),
+                            )
+                        })
+                        .when(!assemblies.is_empty(), |this| {
+                            this.child(
+                                div()
+                                    .flex()
+                                    .flex_col()
+                                    .text_sm()
+                                    .text_color(rgba(colors::TEXT_SECONDARY))
+                                    .child("Assemblies:")
+                                    .children(assemblies.iter().map(|assembly| {
+                                        format!("- {} {}", assembly.name, assembly.version)
+                                    })),
+                            )
+                        })
+                        .when(!def_conflicts.is_empty(), |this| {
+                            this.child(
+                                div()
+                                    .flex()
+                                    .flex_col()
+                                    .text_sm()
+                                    .text_color(rgba(colors::TEXT_SECONDARY))
+                                    .child(
+                                        div()
+                                            .id("def-conflicts")
+                                            .cursor_pointer()
+                                            .text_color(rgba(colors::TEXT_ACCENT))
+                                            .hover(|style| style.bg(rgba(colors::ELEMENT_HOVER)))
+                                            .on_click(cx.listener(|this, _, _, cx| {
+                                                this.show_def_conflicts = !this.show_def_conflicts;
+                                                cx.notify();
+                                            }))
+                                            .child(format!(
+                                                "Def conflicts ({}) {}",
+                                                def_conflicts.len(),
+                                                if show_def_conflicts { "▾" } else { "▸" }
+                                            )),
+                                    )
+                                    .when(show_def_conflicts, |this| {
+                                        this.children(def_conflicts.iter().map(|conflict| {
+                                            let winner = conflict.winner();
+                                            let others = conflict.sources
+                                                [..conflict.sources.len() - 1]
+                                                .iter()
+                                                .map(|source| format!("'{}'", source.mod_name))
+                                                .collect::<Vec<_>>()
+                                                .join(", ");
+                                            div()
+                                                .id(SharedString::from(format!(
+                                                    "def-conflict-{}-{}",
+                                                    conflict.def_type, conflict.def_name
+                                                )))
+                                                .tooltip(Tooltip::text(
+                                                    conflict
+                                                        .sources
+                                                        .iter()
+                                                        .map(|source| {
+                                                            format!(
+                                                                "{}: {}",
+                                                                source.mod_name,
+                                                                source.file.display()
+                                                            )
+                                                        })
+                                                        .collect::<Vec<_>>()
+                                                        .join("\n"),
+                                                ))
+                                                .child(format!(
+                                                    "- {} {}: '{}' overrides {others}",
+                                                    conflict.def_type,
+                                                    conflict.def_name,
+                                                    winner.mod_name
+                                                ))
+                                        }))
+                                    }),
+                            )
+                        })
                         .child(mod_meta.description.clone())
+                    })
+                    .when_some(history, |this, events| {
+                        this.child(
+                            div()
+                                .flex()
+                                .flex_col()
+                                .text_sm()
+                                .text_color(rgba(colors::TEXT_SECONDARY))
+                                .child(
+                                    div()
+                                        .flex()
+                                        .flex_row()
+                                        .items_center()
+                                        .justify_between()
+                                        .child("History:")
+                                        .child(
+                                            IconButton::from_name("close history", IconName::Clear)
+                                                .on_click(cx.listener(|this, _, _, cx| {
+                                                    this.project.update(cx, |project, _| {
+                                                        project.close_mod_history();
+                                                    });
+                                                }))
+                                                .tooltip(Tooltip::text("Close history")),
+                                        ),
+                                )
+                                .when(events.is_empty(), |this| this.child("No recorded events"))
+                                .children(events.iter().map(|event| {
+                                    format!(
+                                        "- {} {} {}",
+                                        event.timestamp,
+                                        event.event_type,
+                                        event.version.as_deref().unwrap_or_default()
+                                    )
+                                })),
+                        )
                     }),
             )
+            .children(self.settings_backup_menu.as_ref().map(|(menu, position, _)| {
+                deferred(anchored().position(*position).child(menu.clone())).with_priority(1)
+            }))
     }
 }
@@ -1,5 +1,7 @@
+mod actions;
 mod item;
 mod list;
 
+pub use actions::*;
 pub use item::*;
 pub use list::*;
@@ -0,0 +1,19 @@
+use gpui::actions;
+
+actions!(
+    mod_list,
+    [
+        SelectNext,
+        SelectPrevious,
+        SelectFirst,
+        SelectLast,
+        ToggleActivation,
+        MoveUp,
+        MoveDown,
+        FocusSearch,
+        FocusList,
+        FocusActiveList,
+        FocusInactiveList,
+        ToggleListFocus,
+    ]
+);
@@ -1,6 +1,6 @@
 use std::fs;
 
-use gpui::ClickEvent;
+use gpui::{ClickEvent, FontWeight, HighlightStyle, StyledText};
 
 use crate::{
     game::mods::{ModIssues, ModMetaData},
@@ -15,6 +15,9 @@ pub struct ModListItem {
     id: ElementId,
     mod_meta: Entity<ModMetaData>,
     mod_issues: Option<ModIssues>,
+    tags: Vec<String>,
+    note: Option<String>,
+    highlights: Vec<usize>,
     selected: bool,
     on_click: Option<OnClickFunc>,
 }
@@ -29,11 +32,30 @@ impl ModListItem {
             id: id.into(),
             mod_meta,
             mod_issues,
+            tags: Vec::new(),
+            note: None,
+            highlights: Vec::new(),
             selected: false,
             on_click: None,
         }
     }
 
+    pub fn tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    pub fn note(mut self, note: Option<String>) -> Self {
+        self.note = note;
+        self
+    }
+
+    /// Set the byte offsets of characters to highlight in the mod name.
+    pub fn highlights(mut self, highlights: Vec<usize>) -> Self {
+        self.highlights = highlights;
+        self
+    }
+
     pub fn on_click(
         mut self,
         handler: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
@@ -80,13 +102,51 @@ impl RenderOnce for ModListItem {
                 };
                 IconButton::new(SharedString::from(id), icon_source).style(ButtonStyle::Transparent)
             })
-            .child(
-                div()
-                    .flex_grow()
-                    .overflow_hidden()
-                    .text_ellipsis()
-                    .child(mod_name.clone()),
-            )
+            .child(div().flex_grow().overflow_hidden().text_ellipsis().child({
+                let highlight = HighlightStyle {
+                    color: Some(Hsla::from(rgba(colors::TEXT_ACCENT))),
+                    font_weight: Some(FontWeight::BOLD),
+                    ..Default::default()
+                };
+                let highlights = self
+                    .highlights
+                    .iter()
+                    .filter_map(|&ix| {
+                        let len = mod_name[ix..].chars().next()?.len_utf8();
+                        Some((ix..ix + len, highlight))
+                    })
+                    .collect::<Vec<_>>();
+                StyledText::new(mod_name.clone()).with_highlights(highlights)
+            }))
+            .when(!self.tags.is_empty(), |this| {
+                this.child(
+                    div()
+                        .flex()
+                        .flex_row()
+                        .flex_none()
+                        .items_center()
+                        .gap_1()
+                        .children(self.tags.iter().map(|tag| {
+                            div()
+                                .px_1()
+                                .rounded_md()
+                                .text_xs()
+                                .bg(rgba(colors::ELEMENT_BACKGROUND))
+                                .text_color(rgba(colors::TEXT_ACCENT))
+                                .child(tag.clone())
+                        })),
+                )
+            })
+            .when_some(self.note, |this, note| {
+                this.child(
+                    IconButton::from_name(
+                        SharedString::from(format!("{mod_name}-note")),
+                        IconName::Note,
+                    )
+                    .style(ButtonStyle::Transparent)
+                    .tooltip(Tooltip::text(note)),
+                )
+            })
             .when_some(self.mod_issues, |this, issues: ModIssues| {
                 this.child(div().flex().flex_row().items_center().px_2().child({
                     let id = format!("{mod_name}-issues");
@@ -1,18 +1,29 @@
-use std::{fmt::Display, ops::Range};
+use std::{
+    collections::BTreeSet,
+    fmt::Display,
+    ops::Range,
+    time::{Duration, Instant},
+};
 
 use gpui::{
-    ClickEvent, FocusHandle, MouseButton, Pixels, Point, UniformList, px, relative, uniform_list,
+    ClickEvent, ClipboardItem, DismissEvent, FocusHandle, Focusable, KeyDownEvent, MouseButton,
+    MouseDownEvent, Pixels, Point, ScrollStrategy, Subscription, UniformList,
+    UniformListScrollHandle, anchored, deferred, px, relative, uniform_list,
 };
 
 use crate::{
-    game::mods::{ModIssues, ModMetaData},
+    game::mods::ModMetaData,
     project::Project,
+    search::{MatchContext, Matcher, ModFilter, SearchMode, matches_filters},
     settings::Settings,
     theme::colors,
-    ui::{TextInput, TextInputEvent, prelude::*},
+    ui::{ContextMenu, TextInput, TextInputEvent, prelude::*},
 };
 
-use super::ModListItem;
+use super::*;
+
+/// Time after which type-ahead input starts a new search.
+const TYPE_AHEAD_TIMEOUT: Duration = Duration::from_millis(1000);
 
 pub struct ModList {
     project: Entity<Project>,
@@ -23,7 +34,28 @@ pub struct ModList {
     list_type: ModListType,
     search_text: SharedString,
     case_sensitive: bool,
+    search_mode: SearchMode,
+    show_filters: bool,
     mouse_down: bool,
+    /// mod id used as the starting point for shift-click range selection
+    selection_anchor: Option<String>,
+    scroll_handle: UniformListScrollHandle,
+    /// characters typed to jump to a mod by name, and when the last one was typed
+    type_ahead: String,
+    type_ahead_at: Option<Instant>,
+    context_menu: Option<(Entity<ContextMenu>, Point<Pixels>, Subscription)>,
+    /// filtered mods and the search inputs they were filtered with
+    filtered_mods: Option<(FilterKey, Vec<ModMetaData>)>,
+}
+
+/// Inputs that determine the filtered mods, used to invalidate cached results.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FilterKey {
+    search_text: SharedString,
+    search_mode: SearchMode,
+    case_sensitive: bool,
+    filters: BTreeSet<ModFilter>,
+    search_generation: u64,
 }
 
 impl ModList {
@@ -51,6 +83,7 @@ impl ModList {
                 list.search_text = content.into();
                 list.smart_case(cx);
             }
+            TextInputEvent::Confirmed { .. } => {}
         })
         .detach();
 
@@ -63,18 +96,27 @@ impl ModList {
             list_type,
             search_text: "".into(),
             case_sensitive: false,
+            search_mode: SearchMode::default(),
+            show_filters: false,
             mouse_down: false,
+            selection_anchor: None,
+            scroll_handle: UniformListScrollHandle::new(),
+            type_ahead: String::new(),
+            type_ahead_at: None,
+            context_menu: None,
+            filtered_mods: None,
         }
     }
 
-    // todo: move search to new line - possible setting to toggle advanced search on new line
-    // todo: add search controls (case sensitive, regex, etc.)
     fn render_header(&mut self, cx: &mut Context<Self>) -> Div {
         let mods = self.mods_for_list_type(cx).len();
         let filtered_mods = self.filtered_mods_for_list_type(cx).len();
         let inactive_order = self
             .project
             .read_with(cx, |project, _| project.inactive_mods_order());
+        let bisecting = self
+            .project
+            .read_with(cx, |project, _| project.bisect().is_some());
 
         let separate_search_bar = self
             .settings
@@ -85,14 +127,17 @@ impl ModList {
             false => format!("{filtered_mods} / {mods}"),
         };
 
+        let selected_mods = self.selected_mods_for_list_type(cx);
+        let search_error = self.search_matcher().err();
+
         // todo: don't do this every render
-        let buttons = match self.list_type {
+        let mut buttons = match self.list_type {
             ModListType::Active => {
                 vec![
                     IconButton::from_name("sort", IconName::Sort)
                         .on_click(cx.listener(|this, _, _, cx| {
-                            this.project.update(cx, |project, _| {
-                                project.sort_active_mods();
+                            this.project.update(cx, |project, cx| {
+                                project.sort_active_mods(cx);
                             });
                         }))
                         .tooltip(Tooltip::text("Sort active mods")),
@@ -107,24 +152,42 @@ impl ModList {
                         .on_click(cx.listener(|this, _, _, cx| {
                             this.project.update(cx, |project, cx| {
                                 project.load_mods_config(cx);
-                                project.apply_mods_config();
+                                project.apply_mods_config(cx);
                             });
                         }))
                         .tooltip(Tooltip::text("Reload mod order from game")),
                     IconButton::from_name("reset", IconName::Reset)
                         .on_click(cx.listener(|this, _, _, cx| {
-                            this.project.update(cx, |project, _| {
-                                project.apply_mods_config();
+                            this.project.update(cx, |project, cx| {
+                                project.apply_mods_config(cx);
                             });
                         }))
                         .tooltip(Tooltip::text("Restore loaded mod order")),
                     IconButton::from_name("clear", IconName::Clear)
                         .on_click(cx.listener(|this, _, _, cx| {
-                            this.project.update(cx, |project, _| {
-                                project.clear_active_mods();
+                            this.project.update(cx, |project, cx| {
+                                project.clear_active_mods(cx);
                             });
                         }))
                         .tooltip(Tooltip::text("Clear mod order")),
+                    IconButton::from_name("bisect", IconName::Bisect)
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.project.update(cx, |project, cx| {
+                                match bisecting {
+                                    true => project.stop_bisect(cx),
+                                    false => project.start_bisect(cx),
+                                }
+                                cx.notify();
+                            });
+                        }))
+                        .icon_color(Hsla::from(rgba(match bisecting {
+                            true => colors::SUCCESS_TEXT,
+                            false => colors::TEXT,
+                        })))
+                        .tooltip(Tooltip::text(match bisecting {
+                            true => "Stop bisect and restore mod order",
+                            false => "Bisect active mods to find a problem mod",
+                        })),
                 ]
             }
             ModListType::Inactive => {
@@ -167,6 +230,71 @@ impl ModList {
             }
         };
 
+        let has_filters = !self.list_filters(cx).is_empty();
+        buttons.push(
+            IconButton::from_name("filters", IconName::Filter)
+                .on_click(cx.listener(|this, _, _, cx| {
+                    this.show_filters = !this.show_filters;
+                    cx.notify();
+                }))
+                .icon_color(Hsla::from(rgba(match has_filters {
+                    true => colors::SUCCESS_TEXT,
+                    false => colors::TEXT,
+                })))
+                .tooltip(Tooltip::text("Toggle quick filters")),
+        );
+
+        if !selected_mods.is_empty() {
+            let count = selected_mods.len();
+            buttons.insert(
+                0,
+                IconButton::from_name("tag selected", IconName::Tag)
+                    .on_click(cx.listener(|this, event, window, cx| {
+                        let ClickEvent::Mouse(mouse_event) = event else {
+                            return;
+                        };
+                        cx.stop_propagation();
+                        this.deploy_tag_menu(mouse_event.up.position, window, cx);
+                    }))
+                    .tooltip(Tooltip::text(format!("Tag {count} selected mods"))),
+            );
+            buttons.insert(
+                0,
+                IconButton::from_name("open selected", IconName::OpenFolder)
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        for mod_meta in this.selected_mods_for_list_type(cx) {
+                            cx.open_with_system(&mod_meta.path);
+                        }
+                    }))
+                    .tooltip(Tooltip::text(format!("Open {count} selected mod folders"))),
+            );
+            buttons.insert(
+                0,
+                match self.list_type {
+                    ModListType::Active => {
+                        IconButton::from_name("bulk toggle", IconName::Deactivate)
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                let selected = this.selected_mods_for_list_type(cx);
+                                this.project.update(cx, |project, cx| {
+                                    project.deactivate_mods(&selected, cx);
+                                });
+                            }))
+                            .tooltip(Tooltip::text(format!("Deactivate {count} selected mods")))
+                    }
+                    ModListType::Inactive => {
+                        IconButton::from_name("bulk toggle", IconName::Activate)
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                let selected = this.selected_mods_for_list_type(cx);
+                                this.project.update(cx, |project, cx| {
+                                    project.activate_mods(&selected, cx);
+                                });
+                            }))
+                            .tooltip(Tooltip::text(format!("Activate {count} selected mods")))
+                    }
+                },
+            );
+        }
+
         div()
             .flex()
             .flex_col()
@@ -198,24 +326,7 @@ impl ModList {
                                 .items_center()
                                 .gap_2()
                                 .child(self.text_input.clone())
-                                .child(
-                                    div().flex().flex_row().items_center().gap_1().child(
-                                        IconButton::from_name(
-                                            "case sensitive",
-                                            IconName::CaseSensitive,
-                                        )
-                                        .on_click(cx.listener(|this, _, _, _| {
-                                            this.case_sensitive = !this.case_sensitive;
-                                        }))
-                                        .icon_color(Hsla::from(rgba({
-                                            match self.case_sensitive {
-                                                true => colors::SUCCESS_TEXT,
-                                                false => colors::TEXT,
-                                            }
-                                        })))
-                                        .tooltip(Tooltip::text("Toggle case sensitivity")),
-                                    ),
-                                ),
+                                .child(self.render_search_controls(cx)),
                         )
                     })
                     .child(
@@ -235,28 +346,105 @@ impl ModList {
                         .items_center()
                         .gap_2()
                         .child(self.text_input.clone())
-                        .child(
-                            div().flex().flex_row().items_center().gap_1().child(
-                                IconButton::from_name("case sensitive", IconName::CaseSensitive)
-                                    .on_click(cx.listener(|this, _, _, _| {
-                                        this.case_sensitive = !this.case_sensitive;
-                                    }))
-                                    .icon_color(Hsla::from(rgba({
-                                        match self.case_sensitive {
-                                            true => colors::SUCCESS_TEXT,
-                                            false => colors::TEXT,
-                                        }
-                                    })))
-                                    .tooltip(Tooltip::text("Toggle case sensitivity")),
-                            ),
-                        ),
+                        .child(self.render_search_controls(cx)),
                 )
             })
+            .when(self.show_filters, |this| {
+                this.child(self.render_filters(cx))
+            })
+            .when_some(search_error, |this, error| {
+                this.child(
+                    div()
+                        .text_sm()
+                        .text_color(rgba(colors::ERROR_TEXT))
+                        .child(error),
+                )
+            })
+    }
+
+    fn render_filters(&self, cx: &mut Context<Self>) -> Div {
+        let filters = self.list_filters(cx);
+        div()
+            .flex()
+            .flex_row()
+            .flex_wrap()
+            .gap_1()
+            .text_xs()
+            .children(ModFilter::ALL.map(|filter| {
+                let enabled = filters.contains(&filter);
+                div()
+                    .id(SharedString::from(format!("filter-{filter}")))
+                    .px_1()
+                    .rounded_md()
+                    .cursor_pointer()
+                    .bg(rgba(match enabled {
+                        true => colors::ELEMENT_SELECTED,
+                        false => colors::ELEMENT_BACKGROUND,
+                    }))
+                    .text_color(rgba(match enabled {
+                        true => colors::TEXT,
+                        false => colors::TEXT_SECONDARY,
+                    }))
+                    .hover(|style| style.bg(rgba(colors::ELEMENT_HOVER)))
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.toggle_filter(filter, cx);
+                    }))
+                    .child(filter.to_string())
+            }))
+    }
+
+    fn render_search_controls(&self, cx: &mut Context<Self>) -> Div {
+        let advanced_search = self
+            .settings
+            .read_with(cx, |settings, _| settings.advanced_search());
+
+        let toggle_color = |enabled: bool| {
+            Hsla::from(rgba(match enabled {
+                true => colors::SUCCESS_TEXT,
+                false => colors::TEXT,
+            }))
+        };
+
+        div()
+            .flex()
+            .flex_row()
+            .items_center()
+            .gap_1()
+            .child(
+                IconButton::from_name("case sensitive", IconName::CaseSensitive)
+                    .on_click(cx.listener(|this, _, _, _| {
+                        this.case_sensitive = !this.case_sensitive;
+                    }))
+                    .icon_color(toggle_color(self.case_sensitive))
+                    .tooltip(Tooltip::text("Toggle case sensitivity")),
+            )
+            .when(advanced_search, |this| {
+                this.children([SearchMode::Regex, SearchMode::Fuzzy].map(|mode| {
+                    let icon = match mode {
+                        SearchMode::Regex => IconName::Regex,
+                        _ => IconName::Fuzzy,
+                    };
+                    IconButton::from_name(SharedString::from(format!("{mode} search")), icon)
+                        .on_click(cx.listener(move |this, _, _, _| {
+                            this.search_mode = match this.search_mode == mode {
+                                true => SearchMode::default(),
+                                false => mode,
+                            };
+                        }))
+                        .icon_color(toggle_color(self.search_mode == mode))
+                        .tooltip(Tooltip::text(format!("Toggle {mode} search")))
+                }))
+            })
     }
 
     // todo: preload images for visible mods in this list
     fn render_list(&self, cx: &mut Context<Self>) -> UniformList {
         let mods = self.filtered_mods_for_list_type(cx);
+        // built once per render, as compiling a search for every row is expensive
+        let highlight_matcher = match self.search_mode {
+            SearchMode::Fuzzy => self.search_matcher().ok(),
+            _ => None,
+        };
         uniform_list(
             self.list_name.clone(),
             mods.len(),
@@ -264,31 +452,33 @@ impl ModList {
                 let mut items = Vec::with_capacity(range.end - range.start);
                 for ix in range {
                     let mod_meta = cx.new(|_| mods[ix].clone());
-                    let mod_issues = this.project.read_with(cx, |project, _| {
-                        project.issues_for_mod(&mods[ix].id).cloned()
-                    });
-                    items.push(this.render_entry(mod_meta, mod_issues, window, cx));
+                    items.push(this.render_entry(mod_meta, highlight_matcher.as_ref(), window, cx));
                 }
                 items
             }),
         )
+        .track_scroll(self.scroll_handle.clone())
         .flex_grow()
     }
 
     fn render_entry(
         &self,
         mod_meta: Entity<ModMetaData>,
-        mod_issues: Option<ModIssues>,
+        highlight_matcher: Option<&Matcher>,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Stateful<Div> {
         let mod_id = mod_meta.read_with(cx, |mod_meta, _| mod_meta.id.clone());
 
-        let is_selected = self.project.read_with(cx, |project, _| {
-            project
-                .selected_mod()
-                .is_some_and(|selected| selected.id == mod_id)
-        });
+        let (is_selected, mod_issues, mod_tags, mod_note) =
+            self.project.read_with(cx, |project, _| {
+                (
+                    project.is_selected(&mod_id),
+                    project.issues_for_mod(&mod_id).cloned(),
+                    project.tags_for_mod(&mod_id).cloned(),
+                    project.note_for_mod(&mod_id).cloned(),
+                )
+            });
 
         let bg_color = match is_selected {
             true => rgba(colors::ELEMENT_SELECTED),
@@ -316,12 +506,22 @@ impl ModList {
 
         let id = SharedString::from(format!("{}-{}", self.list_name, mod_id));
 
+        // drag the whole selection when dragging a selected mod, otherwise just this mod
         let dragged_selection = DraggedSelection {
-            selected: mod_meta.read(cx).clone(),
+            selected: match is_selected {
+                true => self.selected_mods_for_list_type(cx),
+                false => vec![mod_meta.read(cx).clone()],
+            },
         };
 
-        let item =
-            ModListItem::new(id.clone(), mod_meta.clone(), mod_issues).toggle_state(is_selected);
+        let highlights = highlight_matcher
+            .map(|matcher| matcher.highlights(&mod_meta.read(cx).name, self.case_sensitive));
+
+        let item = ModListItem::new(id.clone(), mod_meta.clone(), mod_issues)
+            .tags(mod_tags.into_iter().flatten().collect())
+            .highlights(highlights.unwrap_or_default())
+            .note(mod_note)
+            .toggle_state(is_selected);
 
         div()
             .id(id)
@@ -332,7 +532,7 @@ impl ModList {
             .hover(|style| style.bg(bg_hover_color).border_color(border_hover_color))
             .on_drag(dragged_selection, |selection, click_offset, _window, cx| {
                 cx.new(|_| DraggedModListItemView {
-                    mod_meta: selection.selected.clone(),
+                    mods: selection.selected.clone(),
                     click_offset,
                 })
             })
@@ -346,38 +546,60 @@ impl ModList {
             )
             .on_mouse_down(
                 MouseButton::Left,
-                cx.listener(|this, _, _, cx| {
+                cx.listener(|this, _, window, cx| {
                     this.mouse_down = true;
+                    window.focus(&this.focus_handle);
                     cx.propagate();
                 }),
             )
+            .on_mouse_down(MouseButton::Right, {
+                let mod_meta = mod_meta.clone();
+                cx.listener(move |this, event: &MouseDownEvent, window, cx| {
+                    let mod_meta = mod_meta.read(cx).clone();
+                    this.deploy_context_menu(event.position, mod_meta, window, cx);
+                })
+            })
             .on_click(cx.listener(move |this, event: &ClickEvent, _, cx| {
                 let mod_meta_entity = mod_meta.clone();
                 let mod_meta = mod_meta_entity.read(cx);
                 if let ClickEvent::Mouse(mouse_event) = event {
                     match mouse_event.down.button {
                         MouseButton::Left => match mouse_event.down.click_count {
+                            1 if mouse_event.down.modifiers.shift => {
+                                log::debug!("select range to {mod_meta:?}");
+                                let mod_id = mod_meta.id.clone();
+                                let range = this.mods_in_range_to(&mod_id, cx);
+                                this.project.update(cx, |project, cx| {
+                                    project.select_mod_range(&range, cx);
+                                });
+                            }
+                            1 if mouse_event.down.modifiers.secondary() => {
+                                log::debug!("toggle selection {mod_meta:?}");
+                                this.selection_anchor = Some(mod_meta.id.clone());
+                                this.project.update(cx, move |project, cx| {
+                                    let mod_meta = mod_meta_entity.read(cx).clone();
+                                    project.toggle_mod_selection(&mod_meta, cx);
+                                });
+                            }
                             1 => {
                                 log::debug!("select {mod_meta:?}");
+                                this.selection_anchor = Some(mod_meta.id.clone());
                                 this.project.update(cx, {
                                     move |project, cx| {
-                                        let mod_meta = mod_meta_entity.read(cx);
-                                        project.select_mod(mod_meta);
+                                        let mod_meta = mod_meta_entity.read(cx).clone();
+                                        project.select_mod(&mod_meta, cx);
                                     }
                                 });
                             }
                             2 => {
                                 log::debug!("toggle {mod_meta:?}");
                                 this.project.update(cx, move |project, cx| {
-                                    let mod_meta = mod_meta_entity.read(cx);
-                                    project.toggle_mod(mod_meta);
+                                    let mod_meta = mod_meta_entity.read(cx).clone();
+                                    project.toggle_mod(&mod_meta, cx);
                                 });
                             }
                             _ => {}
                         },
-                        MouseButton::Right => {
-                            log::debug!("context menu {mod_meta:?}");
-                        }
                         _ => {
                             log::debug!("unhandled click {mod_meta:?}")
                         }
@@ -387,6 +609,135 @@ impl ModList {
             .child(item)
     }
 
+    fn deploy_context_menu(
+        &mut self,
+        position: Point<Pixels>,
+        mod_meta: ModMetaData,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        log::debug!("context menu {mod_meta:?}");
+        let is_selected = self
+            .project
+            .read_with(cx, |project, _| project.is_selected(&mod_meta.id));
+        if !is_selected {
+            self.selection_anchor = Some(mod_meta.id.clone());
+            self.project
+                .update(cx, |project, cx| project.select_mod(&mod_meta, cx));
+        }
+
+        let project = self.project.clone();
+        let is_active = project.read_with(cx, |project, _| project.is_active(&mod_meta.id));
+        let menu = ContextMenu::build(window, cx, move |menu, _, _| {
+            let about_file = mod_meta.about_file_path();
+            let folder = mod_meta.path.clone();
+            let package_id = mod_meta.id.clone();
+            let path = mod_meta.path.to_string_lossy().to_string();
+
+            menu.entry("Open mod folder", move |_, cx| {
+                cx.open_with_system(&folder);
+            })
+            .entry("Open About.xml", move |_, cx| {
+                cx.open_with_system(&about_file);
+            })
+            .map(|menu| match mod_meta.url.clone() {
+                Some(url) => menu.entry("Open mod URL", move |_, cx| cx.open_url(&url)),
+                None => menu.disabled_entry("Open mod URL"),
+            })
+            .map(|menu| match mod_meta.steam_workshop_url() {
+                Some(url) => menu.entry("Open Steam workshop page", move |_, cx| cx.open_url(&url)),
+                None => menu.disabled_entry("Open Steam workshop page"),
+            })
+            .separator()
+            .entry("Copy packageId", move |_, cx| {
+                cx.write_to_clipboard(ClipboardItem::new_string(package_id.clone()));
+            })
+            .entry("Copy path", move |_, cx| {
+                cx.write_to_clipboard(ClipboardItem::new_string(path.clone()));
+            })
+            .separator()
+            .entry(
+                match is_active {
+                    true => "Deactivate",
+                    false => "Activate",
+                },
+                {
+                    let project = project.clone();
+                    let mod_meta = mod_meta.clone();
+                    move |_, cx| project.update(cx, |project, cx| project.toggle_mod(&mod_meta, cx))
+                },
+            )
+            .entry("Browse defs", {
+                let project = project.clone();
+                let mod_meta = mod_meta.clone();
+                move |_, cx| {
+                    project.update(cx, |project, cx| {
+                        project.browse_defs_for_mod(&mod_meta);
+                        cx.notify();
+                    })
+                }
+            })
+            .entry("Reveal in history", move |_, cx| {
+                project.update(cx, |project, cx| project.reveal_in_history(&mod_meta, cx));
+            })
+        });
+
+        self.show_context_menu(menu, position, window, cx);
+    }
+
+    /// Offer the existing tags to add to the selected mods in this list.
+    fn deploy_tag_menu(
+        &mut self,
+        position: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let project = self.project.clone();
+        let mod_ids: Vec<String> = self
+            .selected_mods_for_list_type(cx)
+            .into_iter()
+            .map(|mod_meta| mod_meta.id)
+            .collect();
+        let tags = project.read_with(cx, |project, _| project.all_tags());
+        let menu = ContextMenu::build(window, cx, move |menu, _, _| {
+            if tags.is_empty() {
+                return menu.disabled_entry("No tags yet, add one in the details pane");
+            }
+            tags.into_iter().fold(menu, |menu, tag| {
+                let project = project.clone();
+                let mod_ids = mod_ids.clone();
+                menu.entry(format!("Tag with '{tag}'"), move |_, cx| {
+                    project.update(cx, |project, cx| {
+                        project.tag_mods(&mod_ids, &tag);
+                        cx.notify();
+                    });
+                })
+            })
+        });
+        self.show_context_menu(menu, position, window, cx);
+    }
+
+    fn show_context_menu(
+        &mut self,
+        menu: Entity<ContextMenu>,
+        position: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        window.focus(&menu.focus_handle(cx));
+        let subscription =
+            cx.subscribe_in(&menu, window, |this, _, _: &DismissEvent, window, cx| {
+                if let Some((menu, ..)) = this.context_menu.take()
+                    && menu.focus_handle(cx).contains_focused(window, cx)
+                {
+                    window.focus(&this.focus_handle);
+                }
+                cx.notify();
+            });
+        self.context_menu = Some((menu, position, subscription));
+        cx.notify();
+    }
+
     fn mods_for_list_type(&self, cx: &mut Context<Self>) -> Vec<ModMetaData> {
         self.project
             .read_with(cx, |project, _| match self.list_type {
@@ -395,11 +746,108 @@ impl ModList {
             })
     }
 
-    // todo: support case-insensitive search - case sensitive toggle + smart activation
-    // todo: support selecting which fields to search (name, id, description, etc.)
-    // todo: support regex search
+    fn selected_mods_for_list_type(&self, cx: &mut Context<Self>) -> Vec<ModMetaData> {
+        self.project.read_with(cx, |project, _| {
+            let mods = match self.list_type {
+                ModListType::Active => project.active_mods(),
+                ModListType::Inactive => project.inactive_mods(),
+            };
+            mods.into_iter()
+                .filter(|m| project.is_selected(&m.id))
+                .collect()
+        })
+    }
+
+    /// Get the visible mods between the selection anchor and the given mod, inclusive.
+    ///
+    /// Falls back to just the given mod if there is no anchor in this list.
+    fn mods_in_range_to(&self, mod_id: &str, cx: &mut Context<Self>) -> Vec<ModMetaData> {
+        let mods = self.filtered_mods_for_list_type(cx);
+        let Some(end) = mods.iter().position(|m| m.id == mod_id) else {
+            return Vec::new();
+        };
+        let start = self
+            .selection_anchor
+            .as_ref()
+            .and_then(|anchor| mods.iter().position(|m| &m.id == anchor))
+            .unwrap_or(end);
+
+        // keep the clicked mod last so it becomes the selected mod
+        match start <= end {
+            true => mods[start..=end].to_vec(),
+            false => mods[end..=start].iter().rev().cloned().collect(),
+        }
+    }
+
+    /// Get the quick filters enabled for this list.
+    fn list_filters(&self, cx: &mut Context<Self>) -> BTreeSet<ModFilter> {
+        self.settings
+            .read_with(cx, |settings, _| match self.list_type {
+                ModListType::Active => settings.active_list_filters(),
+                ModListType::Inactive => settings.inactive_list_filters(),
+            })
+            .clone()
+    }
+
+    /// Toggle a quick filter for this list and persist it.
+    fn toggle_filter(&mut self, filter: ModFilter, cx: &mut Context<Self>) {
+        let mut filters = self.list_filters(cx);
+        if !filters.remove(&filter) {
+            filters.insert(filter);
+        }
+        let list_type = self.list_type;
+        self.settings.update(cx, |settings, _| {
+            match list_type {
+                ModListType::Active => settings.set_active_list_filters(filters),
+                ModListType::Inactive => settings.set_inactive_list_filters(filters),
+            }
+            settings.save();
+        });
+        cx.notify();
+    }
+
+    /// Compile the search text for the current search mode.
+    fn search_matcher(&self) -> Result<Matcher, String> {
+        Matcher::new(self.search_mode, &self.search_text, self.case_sensitive)
+    }
+
+    fn filter_key(&self, cx: &mut Context<Self>) -> FilterKey {
+        FilterKey {
+            search_text: self.search_text.clone(),
+            search_mode: self.search_mode,
+            case_sensitive: self.case_sensitive,
+            filters: self.list_filters(cx),
+            search_generation: self
+                .project
+                .read_with(cx, |project, _| project.search_generation()),
+        }
+    }
+
+    /// Refresh the cached filtered mods if the search or the mods have changed.
+    fn update_filtered_mods(&mut self, cx: &mut Context<Self>) {
+        let key = self.filter_key(cx);
+        if self
+            .filtered_mods
+            .as_ref()
+            .is_some_and(|(cached_key, _)| *cached_key == key)
+        {
+            return;
+        }
+        let mods = self.filter_mods_for_list_type(cx);
+        self.filtered_mods = Some((key, mods));
+    }
+
+    /// Get the filtered mods, from the cache if it is still valid.
     fn filtered_mods_for_list_type(&self, cx: &mut Context<Self>) -> Vec<ModMetaData> {
-        let search = self.search_text.to_string();
+        match &self.filtered_mods {
+            Some((key, mods)) if *key == self.filter_key(cx) => mods.clone(),
+            _ => self.filter_mods_for_list_type(cx),
+        }
+    }
+
+    fn filter_mods_for_list_type(&self, cx: &mut Context<Self>) -> Vec<ModMetaData> {
+        // invalid searches are reported in the header and filter nothing
+        let matcher = self.search_matcher().ok();
         let is_inactive = self.list_type == ModListType::Inactive;
         let show_supported_only = is_inactive
             && self
@@ -412,26 +860,50 @@ impl ModList {
             None
         };
 
-        self.mods_for_list_type(cx)
+        let filters = self.list_filters(cx);
+        let mods = self.mods_for_list_type(cx);
+        let project = self.project.read(cx);
+        let mut scored: Vec<(i64, &ModMetaData)> = mods
             .iter()
+            // Quick filters
             .filter(|mod_meta| {
-                // Search filter
-                (search.is_empty()
-                    || if self.case_sensitive {
-                        mod_meta.name.contains(&search) || mod_meta.id.contains(&search)
-                    } else {
-                        let search_lower = search.to_lowercase();
-                        mod_meta.name.to_lowercase().contains(&search_lower)
-                            || mod_meta.id.to_lowercase().contains(&search_lower)
-                    })
-                // Supported mods filter (only for inactive list)
-                && (!show_supported_only
+                matches_filters(&filters, mod_meta, &project.filter_context(&mod_meta.id))
+            })
+            // Supported mods filter (only for inactive list)
+            .filter(|mod_meta| {
+                !show_supported_only
                     || match &game_version {
                         Some(version) => mod_meta.supported_versions.contains(version),
                         None => true,
-                    })
+                    }
             })
-            .cloned()
+            // Search filter
+            .filter_map(|mod_meta| {
+                let match_cx = MatchContext {
+                    tags: project.tags_for_mod(&mod_meta.id),
+                    note: project.note_for_mod(&mod_meta.id).map(String::as_str),
+                    issues: project.issues_for_mod(&mod_meta.id),
+                    languages: project.languages_for_mod(&mod_meta.id),
+                    entry: project.search_index().entry(&mod_meta.id),
+                    case_sensitive: self.case_sensitive,
+                };
+                match &matcher {
+                    Some(matcher) => matcher.score(mod_meta, &match_cx),
+                    None => Some(0),
+                }
+                .map(|score| (score, mod_meta))
+            })
+            .collect();
+
+        // rank fuzzy matches, keeping the list order for equal scores; the active list stays in
+        // load order so reordering acts on what is shown
+        if self.search_mode == SearchMode::Fuzzy && is_inactive {
+            scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        }
+
+        scored
+            .into_iter()
+            .map(|(_, mod_meta)| mod_meta.clone())
             .collect()
     }
 
@@ -441,16 +913,175 @@ impl ModList {
         target_mod_id: String,
         cx: &mut Context<Self>,
     ) {
-        // move dragged mod to other side of target mod
-        let source = selection.selected.id.clone();
+        // move dragged mods to other side of target mod
+        let sources: Vec<String> = selection.selected.iter().map(|m| m.id.clone()).collect();
         let target = target_mod_id.clone();
-        self.project.update(cx, |project, _| {
-            if let Err(e) = project.move_active_mod(source.clone(), target.clone()) {
-                log::error!("error moving {source} to {target}: {e}");
+        self.project.update(cx, |project, cx| {
+            if let Err(e) = project.move_active_mods(&sources, target.clone(), cx) {
+                log::error!("error moving {sources:?} to {target}: {e}");
             }
         });
     }
 
+    /// Get the index of the selected mod within the visible mods of this list.
+    fn selected_index(&self, mods: &[ModMetaData], cx: &mut Context<Self>) -> Option<usize> {
+        self.project.read_with(cx, |project, _| {
+            let selected = project.selected_mod()?;
+            mods.iter().position(|m| m.id == selected.id)
+        })
+    }
+
+    /// Select the visible mod at the given index and scroll it into view.
+    fn select_index(&mut self, index: usize, cx: &mut Context<Self>) {
+        let mods = self.filtered_mods_for_list_type(cx);
+        let Some(mod_meta) = mods.get(index) else {
+            return;
+        };
+        self.selection_anchor = Some(mod_meta.id.clone());
+        self.project.update(cx, |project, cx| {
+            project.select_mod(mod_meta, cx);
+        });
+        self.scroll_handle
+            .scroll_to_item(index, ScrollStrategy::Center);
+        cx.notify();
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        let mods = self.filtered_mods_for_list_type(cx);
+        let index = match self.selected_index(&mods, cx) {
+            Some(index) => (index + 1).min(mods.len().saturating_sub(1)),
+            None => 0,
+        };
+        self.select_index(index, cx);
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        let mods = self.filtered_mods_for_list_type(cx);
+        let index = match self.selected_index(&mods, cx) {
+            Some(index) => index.saturating_sub(1),
+            None => mods.len().saturating_sub(1),
+        };
+        self.select_index(index, cx);
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_index(0, cx);
+    }
+
+    fn select_last(&mut self, _: &SelectLast, _: &mut Window, cx: &mut Context<Self>) {
+        let len = self.filtered_mods_for_list_type(cx).len();
+        self.select_index(len.saturating_sub(1), cx);
+    }
+
+    fn toggle_activation(&mut self, _: &ToggleActivation, _: &mut Window, cx: &mut Context<Self>) {
+        let selected = self.selected_mods_for_list_type(cx);
+        if selected.is_empty() {
+            return;
+        }
+        self.project.update(cx, |project, cx| match self.list_type {
+            ModListType::Active => project.deactivate_mods(&selected, cx),
+            ModListType::Inactive => project.activate_mods(&selected, cx),
+        });
+    }
+
+    fn move_up(&mut self, _: &MoveUp, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_selected_by_one(true, cx);
+    }
+
+    fn move_down(&mut self, _: &MoveDown, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_selected_by_one(false, cx);
+    }
+
+    /// Move the selected active mods one place up or down, past their neighbouring mod.
+    fn move_selected_by_one(&mut self, up: bool, cx: &mut Context<Self>) {
+        if self.list_type != ModListType::Active {
+            return;
+        }
+        let selected = self.selected_mods_for_list_type(cx);
+        let (Some(first), Some(last)) = (selected.first(), selected.last()) else {
+            return;
+        };
+
+        let active = self.mods_for_list_type(cx);
+        let neighbour = match up {
+            true => active
+                .iter()
+                .position(|m| m.id == first.id)
+                .and_then(|index| index.checked_sub(1)),
+            false => active
+                .iter()
+                .position(|m| m.id == last.id)
+                .map(|index| index + 1),
+        };
+        let Some(target) = neighbour.and_then(|index| active.get(index)) else {
+            return;
+        };
+
+        let sources: Vec<String> = selected.iter().map(|m| m.id.clone()).collect();
+        let target = target.id.clone();
+        self.project.update(cx, |project, cx| {
+            if let Err(e) = project.move_active_mods(&sources, target.clone(), cx) {
+                log::error!("error moving {sources:?} to {target}: {e}");
+            }
+        });
+
+        let mods = self.filtered_mods_for_list_type(cx);
+        if let Some(index) = self.selected_index(&mods, cx) {
+            self.scroll_handle
+                .scroll_to_item(index, ScrollStrategy::Center);
+        }
+    }
+
+    fn focus_search(&mut self, _: &FocusSearch, window: &mut Window, cx: &mut Context<Self>) {
+        window.focus(&self.text_input.focus_handle(cx));
+    }
+
+    fn focus_list(&mut self, _: &FocusList, window: &mut Window, _: &mut Context<Self>) {
+        window.focus(&self.focus_handle);
+    }
+
+    /// Jump to the first visible mod whose name starts with the typed characters.
+    fn type_ahead(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.focus_handle.is_focused(window) {
+            return;
+        }
+        let modifiers = &event.keystroke.modifiers;
+        if modifiers.control || modifiers.alt || modifiers.platform || modifiers.function {
+            return;
+        }
+        let Some(key_char) = event
+            .keystroke
+            .key_char
+            .as_ref()
+            .filter(|c| !c.trim().is_empty())
+        else {
+            return;
+        };
+
+        let now = Instant::now();
+        if self
+            .type_ahead_at
+            .is_none_or(|at| now.duration_since(at) > TYPE_AHEAD_TIMEOUT)
+        {
+            self.type_ahead.clear();
+        }
+        self.type_ahead.push_str(&key_char.to_lowercase());
+        self.type_ahead_at = Some(now);
+
+        let mods = self.filtered_mods_for_list_type(cx);
+        let index = mods
+            .iter()
+            .position(|m| m.name.to_lowercase().starts_with(&self.type_ahead))
+            .or_else(|| {
+                mods.iter()
+                    .position(|m| m.name.to_lowercase().contains(&self.type_ahead))
+            });
+        if let Some(index) = index {
+            self.select_index(index, cx);
+            cx.stop_propagation();
+        }
+    }
+
     fn smart_case(&mut self, cx: &mut Context<Self>) {
         if self
             .settings
@@ -468,8 +1099,15 @@ impl ModList {
     }
 }
 
+impl Focusable for ModList {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
 impl Render for ModList {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        self.update_filtered_mods(cx);
         div()
             .flex()
             .flex_col()
@@ -478,17 +1116,39 @@ impl Render for ModList {
             .border_r_1()
             .border_color(rgba(colors::BORDER))
             .text_sm()
+            .key_context("ModList")
+            .on_action(cx.listener(Self::focus_search))
+            .on_action(cx.listener(Self::focus_list))
             .child(self.render_header(cx))
-            .child(self.render_list(cx))
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .flex_grow()
+                    .key_context("ModListEntries")
+                    .track_focus(&self.focus_handle)
+                    .on_action(cx.listener(Self::select_next))
+                    .on_action(cx.listener(Self::select_previous))
+                    .on_action(cx.listener(Self::select_first))
+                    .on_action(cx.listener(Self::select_last))
+                    .on_action(cx.listener(Self::toggle_activation))
+                    .on_action(cx.listener(Self::move_up))
+                    .on_action(cx.listener(Self::move_down))
+                    .on_key_down(cx.listener(Self::type_ahead))
+                    .child(self.render_list(cx)),
+            )
+            .children(self.context_menu.as_ref().map(|(menu, position, _)| {
+                deferred(anchored().position(*position).child(menu.clone())).with_priority(1)
+            }))
     }
 }
 
 struct DraggedSelection {
-    selected: ModMetaData,
+    selected: Vec<ModMetaData>,
 }
 
 struct DraggedModListItemView {
-    mod_meta: ModMetaData,
+    mods: Vec<ModMetaData>,
     click_offset: Point<Pixels>,
 }
 
@@ -511,7 +1171,10 @@ impl Render for DraggedModListItemView {
                     .rounded_lg()
                     .bg(rgba(colors::BACKGROUND))
                     .text_color(rgba(colors::TEXT))
-                    .map(|this| this.child(self.mod_meta.name.clone())),
+                    .map(|this| match self.mods.as_slice() {
+                        [mod_meta] => this.child(mod_meta.name.clone()),
+                        mods => this.child(format!("{} mods", mods.len())),
+                    }),
             )
     }
 }
@@ -146,6 +146,12 @@ impl TextInput {
         }
     }
 
+    fn confirm(&mut self, _: &Confirm, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(TextInputEvent::Confirmed {
+            content: self.content.clone(),
+        });
+    }
+
     fn move_to(&mut self, offset: usize, cx: &mut Context<Self>) {
         self.selected_range = offset..offset;
         cx.notify()
@@ -252,6 +258,13 @@ impl TextInput {
         self.last_bounds = None;
         self.is_selecting = false;
     }
+
+    /// Replace the content without emitting a change event.
+    pub fn set_content(&mut self, content: impl Into<SharedString>) {
+        self.reset();
+        self.content = content.into();
+        self.selected_range = self.content.len()..self.content.len();
+    }
 }
 
 impl EntityInputHandler for TextInput {
@@ -410,6 +423,7 @@ impl Render for TextInput {
             .on_action(cx.listener(Self::paste))
             .on_action(cx.listener(Self::cut))
             .on_action(cx.listener(Self::copy))
+            .on_action(cx.listener(Self::confirm))
             .on_mouse_down(MouseButton::Left, cx.listener(Self::on_mouse_down))
             .on_mouse_up(MouseButton::Left, cx.listener(Self::on_mouse_up))
             .on_mouse_up_out(MouseButton::Left, cx.listener(Self::on_mouse_up))
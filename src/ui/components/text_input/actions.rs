@@ -15,5 +15,6 @@ actions!(
         Paste,
         Cut,
         Copy,
+        Confirm,
     ]
 );
@@ -5,6 +5,7 @@ use super::TextInput;
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum TextInputEvent {
     ContentChanged { content: SharedString },
+    Confirmed { content: SharedString },
 }
 
 impl EventEmitter<TextInputEvent> for TextInput {}
@@ -1,3 +1,5 @@
+pub mod def_browser;
 pub mod settings;
 
+pub use def_browser::*;
 pub use settings::*;
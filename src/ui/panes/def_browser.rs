@@ -0,0 +1,280 @@
+use std::{ops::Range, path::PathBuf};
+
+use gpui::{ClickEvent, Task, UniformListScrollHandle, relative, uniform_list};
+
+use crate::{
+    db::defs::DefRecord,
+    project::Project,
+    theme::colors,
+    ui::{TextInput, TextInputEvent, prelude::*},
+};
+
+/// Maximum number of defs listed, to keep searches responsive.
+const MAX_RESULTS: usize = 1000;
+
+pub struct DefBrowser {
+    project: Entity<Project>,
+    search_input: Entity<TextInput>,
+    search_text: SharedString,
+    scroll_handle: UniformListScrollHandle,
+    /// search results and the inputs they were searched with
+    results: Option<(ResultsKey, Vec<DefRow>)>,
+    /// inputs of the search in progress, whose results replace the current ones when done
+    pending_key: Option<ResultsKey>,
+    search_task: Option<Task<()>>,
+}
+
+/// Inputs that determine the search results, used to invalidate cached results.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ResultsKey {
+    search_text: SharedString,
+    mod_id: Option<String>,
+    indexing: bool,
+}
+
+/// A search result with the details needed to render it.
+#[derive(Debug, Clone)]
+struct DefRow {
+    record: DefRecord,
+    mod_name: SharedString,
+    path: Option<PathBuf>,
+}
+
+impl DefBrowser {
+    pub fn new(project: Entity<Project>, cx: &mut Context<Self>) -> Self {
+        let search_input = TextInput::new(cx);
+        search_input.update(cx, |input, _| {
+            input.placeholder("Search defs by type, defName, label or file...");
+        });
+
+        cx.subscribe(&search_input, |browser, _, event, cx| match event {
+            TextInputEvent::ContentChanged { content } => {
+                browser.search_text = content.into();
+                cx.notify();
+            }
+            TextInputEvent::Confirmed { .. } => {}
+        })
+        .detach();
+
+        Self {
+            project,
+            search_input,
+            search_text: "".into(),
+            scroll_handle: UniformListScrollHandle::new(),
+            results: None,
+            pending_key: None,
+            search_task: None,
+        }
+    }
+
+    /// Search the def index in the background if the inputs changed since the last search.
+    ///
+    /// The previous results stay visible until the new search finishes.
+    fn update_results(&mut self, cx: &mut Context<Self>) {
+        let project = self.project.read(cx);
+        let key = ResultsKey {
+            search_text: self.search_text.clone(),
+            mod_id: project.def_browser_mod().map(|m| m.id.clone()),
+            indexing: project.is_indexing_defs(),
+        };
+        let current_key = self
+            .pending_key
+            .as_ref()
+            .or(self.results.as_ref().map(|(k, _)| k));
+        if current_key == Some(&key) {
+            return;
+        }
+
+        let task = project.search_defs(self.search_text.to_string(), MAX_RESULTS, cx);
+        self.pending_key = Some(key.clone());
+        self.search_task = Some(cx.spawn(async move |this, cx| {
+            let records = task.await;
+            this.update(cx, |browser, cx| {
+                let project = browser.project.read(cx);
+                let rows = records
+                    .into_iter()
+                    .map(|record| DefRow {
+                        mod_name: project
+                            .find_mod(&record.mod_id)
+                            .map(|m| m.name.clone())
+                            .unwrap_or_else(|| record.mod_id.clone())
+                            .into(),
+                        path: project.def_file_path(&record),
+                        record,
+                    })
+                    .collect();
+                browser.results = Some((key, rows));
+                browser.pending_key = None;
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> Div {
+        let project = self.project.read(cx);
+        let filter_mod = project.def_browser_mod().map(|m| m.name.clone());
+        let selected_mod = project.selected_mod().cloned();
+
+        div()
+            .flex()
+            .flex_row()
+            .items_center()
+            .justify_between()
+            .w_full()
+            .gap_2()
+            .child("Defs")
+            .child(div().flex_auto().child(self.search_input.clone()))
+            .when_some(filter_mod.clone(), |this, name| {
+                this.child(
+                    div()
+                        .text_sm()
+                        .text_color(rgba(colors::TEXT_SECONDARY))
+                        .child(format!("Mod: {name}")),
+                )
+            })
+            .child(
+                IconButton::from_name("filter def mod", IconName::Filter)
+                    .toggle_state(filter_mod.is_some())
+                    .disabled(filter_mod.is_none() && selected_mod.is_none())
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        let mod_id = match filter_mod.is_some() {
+                            true => None,
+                            false => selected_mod.as_ref().map(|m| m.id.as_str()),
+                        };
+                        this.project.update(cx, |project, cx| {
+                            project.set_def_browser_mod(mod_id);
+                            cx.notify();
+                        });
+                        cx.notify();
+                    }))
+                    .tooltip(Tooltip::text(
+                        "Toggle showing only defs from the selected mod",
+                    )),
+            )
+            .child(
+                IconButton::from_name("close defs", IconName::Clear)
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.project.update(cx, |project, cx| {
+                            project.toggle_def_browser();
+                            cx.notify();
+                        });
+                    }))
+                    .tooltip(Tooltip::text("Close def browser")),
+            )
+    }
+
+    fn render_row(&self, ix: usize, row: &DefRow, cx: &mut Context<Self>) -> impl IntoElement {
+        let def = &row.record.def;
+        let file = row.record.def.folder.join(&row.record.def.file);
+        let path = row.path.clone();
+
+        div()
+            .id(ix)
+            .flex()
+            .flex_row()
+            .items_center()
+            .w_full()
+            .px_2()
+            .gap_2()
+            .text_sm()
+            .hover(|style| style.bg(rgba(colors::ELEMENT_HOVER)))
+            .when_some(row.path.clone(), |this, path| {
+                this.tooltip(Tooltip::text(format!(
+                    "{}\nDouble-click to open",
+                    path.display()
+                )))
+            })
+            .on_click(cx.listener(move |_, event: &ClickEvent, _, cx| {
+                if let ClickEvent::Mouse(mouse_event) = event
+                    && mouse_event.up.click_count == 2
+                    && let Some(path) = &path
+                {
+                    cx.open_with_system(path);
+                }
+            }))
+            .child(
+                div()
+                    .flex_none()
+                    .flex_basis(relative(0.15))
+                    .overflow_hidden()
+                    .text_color(rgba(colors::TEXT_SECONDARY))
+                    .child(def.def_type.clone()),
+            )
+            .child(
+                div()
+                    .flex_none()
+                    .flex_basis(relative(0.25))
+                    .overflow_hidden()
+                    .child(def.def_name.clone()),
+            )
+            .child(
+                div()
+                    .flex_none()
+                    .flex_basis(relative(0.2))
+                    .overflow_hidden()
+                    .text_color(rgba(colors::TEXT_SECONDARY))
+                    .child(def.label.clone().unwrap_or_default()),
+            )
+            .child(
+                div()
+                    .flex_none()
+                    .flex_basis(relative(0.2))
+                    .overflow_hidden()
+                    .child(row.mod_name.clone()),
+            )
+            .child(
+                div()
+                    .flex_auto()
+                    .overflow_hidden()
+                    .text_color(rgba(colors::TEXT_SECONDARY))
+                    .child(file.display().to_string()),
+            )
+    }
+}
+
+impl Render for DefBrowser {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        self.update_results(cx);
+        let rows = self
+            .results
+            .as_ref()
+            .map(|(_, rows)| rows.clone())
+            .unwrap_or_default();
+        let status = match self.project.read(cx).is_indexing_defs() {
+            true => "Indexing defs...".to_string(),
+            false if self.pending_key.is_some() => "Searching...".to_string(),
+            false if rows.len() >= MAX_RESULTS => format!("Showing the first {MAX_RESULTS} defs"),
+            false => format!("{} defs", rows.len()),
+        };
+
+        div()
+            .size_full()
+            .flex_grow()
+            .flex()
+            .flex_col()
+            .overflow_hidden()
+            .p_2()
+            .gap_2()
+            .child(self.render_header(cx))
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(rgba(colors::TEXT_SECONDARY))
+                    .child(status),
+            )
+            .child(
+                uniform_list(
+                    "def browser",
+                    rows.len(),
+                    cx.processor(move |this, range: Range<usize>, _window, cx| {
+                        range
+                            .map(|ix| this.render_row(ix, &rows[ix], cx))
+                            .collect::<Vec<_>>()
+                    }),
+                )
+                .track_scroll(self.scroll_handle.clone())
+                .flex_grow(),
+            )
+    }
+}
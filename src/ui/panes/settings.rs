@@ -3,6 +3,7 @@ use std::path::PathBuf;
 use gpui::{EntityInputHandler, relative};
 
 use crate::{
+    game::paths,
     settings::Settings,
     ui::{TextInput, TextInputEvent, prelude::*},
 };
@@ -14,6 +15,9 @@ pub struct SettingsPane {
     local_mods: Entity<TextInput>,
     steam_mods: Entity<TextInput>,
     config: Entity<TextInput>,
+    max_texture_resolution: Entity<TextInput>,
+    executable: Entity<TextInput>,
+    launch_args: Entity<TextInput>,
     // todo: add toggle for separate search bar
 }
 
@@ -24,6 +28,9 @@ impl SettingsPane {
         let local_mods = TextInput::new(cx);
         let steam_mods = TextInput::new(cx);
         let config = TextInput::new(cx);
+        let max_texture_resolution = TextInput::new(cx);
+        let executable = TextInput::new(cx);
+        let launch_args = TextInput::new(cx);
 
         settings.update(cx, |settings, cx| {
             if let Some(path) = settings.game_dir().to_str() {
@@ -55,6 +62,22 @@ impl SettingsPane {
                     input.replace_text_in_range(None, path, window, cx);
                 });
             }
+
+            let resolution = settings.max_texture_resolution().to_string();
+            max_texture_resolution.update(cx, |input, cx| {
+                input.replace_text_in_range(None, &resolution, window, cx);
+            });
+
+            if let Some(path) = settings.game_executable().to_str() {
+                executable.update(cx, |input, cx| {
+                    input.replace_text_in_range(None, path, window, cx);
+                });
+            }
+
+            let args = settings.launch_args().to_string();
+            launch_args.update(cx, |input, cx| {
+                input.replace_text_in_range(None, &args, window, cx);
+            });
         });
 
         cx.subscribe(&game, |this, _, event, cx| match event {
@@ -63,6 +86,7 @@ impl SettingsPane {
                     settings.set_game_dir(PathBuf::from(content.to_string()));
                 });
             }
+            TextInputEvent::Confirmed { .. } => {}
         })
         .detach();
 
@@ -72,6 +96,7 @@ impl SettingsPane {
                     settings.set_official_mods_dir(PathBuf::from(content.to_string()));
                 });
             }
+            TextInputEvent::Confirmed { .. } => {}
         })
         .detach();
 
@@ -81,6 +106,7 @@ impl SettingsPane {
                     settings.set_local_mods_dir(PathBuf::from(content.to_string()));
                 });
             }
+            TextInputEvent::Confirmed { .. } => {}
         })
         .detach();
 
@@ -90,6 +116,7 @@ impl SettingsPane {
                     settings.set_steam_mods_dir(PathBuf::from(content.to_string()));
                 });
             }
+            TextInputEvent::Confirmed { .. } => {}
         })
         .detach();
 
@@ -99,6 +126,49 @@ impl SettingsPane {
                     settings.set_config_dir(PathBuf::from(content.to_string()));
                 });
             }
+            TextInputEvent::Confirmed { .. } => {}
+        })
+        .detach();
+
+        cx.subscribe(&max_texture_resolution, |this, _, event, cx| match event {
+            TextInputEvent::ContentChanged { content } => {
+                let Ok(resolution) = content.trim().parse() else {
+                    return;
+                };
+                this.settings.update(cx, |settings, cx| {
+                    settings.set_max_texture_resolution(resolution);
+                    // the project analyzes textures again for the new resolution
+                    cx.notify();
+                });
+            }
+            TextInputEvent::Confirmed { .. } => {}
+        })
+        .detach();
+
+        cx.subscribe(&executable, |this, _, event, cx| match event {
+            TextInputEvent::ContentChanged { content } => {
+                this.settings.update(cx, |settings, _| {
+                    // keep following the game directory unless a different executable is set
+                    let executable = PathBuf::from(content.trim());
+                    let is_default = executable.as_os_str().is_empty()
+                        || executable == paths::game_executable(settings.game_dir());
+                    match is_default {
+                        true => settings.set_game_executable(None),
+                        false => settings.set_game_executable(Some(executable)),
+                    }
+                });
+            }
+            TextInputEvent::Confirmed { .. } => {}
+        })
+        .detach();
+
+        cx.subscribe(&launch_args, |this, _, event, cx| match event {
+            TextInputEvent::ContentChanged { content } => {
+                this.settings.update(cx, |settings, _| {
+                    settings.set_launch_args(content.to_string());
+                });
+            }
+            TextInputEvent::Confirmed { .. } => {}
         })
         .detach();
 
@@ -109,7 +179,28 @@ impl SettingsPane {
             local_mods,
             steam_mods,
             config,
+            max_texture_resolution,
+            executable,
+            launch_args,
+        }
+    }
+
+    /// Show the directories of the instance in use, after switching instances.
+    pub fn reload_instance_dirs(&mut self, cx: &mut Context<Self>) {
+        let settings = self.settings.read(cx);
+        let dirs = [
+            (&self.game, settings.game_dir().clone()),
+            (&self.official_mods, settings.official_mods_dir()),
+            (&self.local_mods, settings.local_mods_dir().clone()),
+            (&self.config, settings.config_dir().clone()),
+            (&self.executable, settings.game_executable()),
+        ];
+        for (input, path) in dirs {
+            input.update(cx, |input, _| {
+                input.set_content(path.to_string_lossy().to_string());
+            });
         }
+        cx.notify();
     }
 }
 
@@ -210,5 +301,56 @@ impl Render for SettingsPane {
                     )
                     .child(div().flex_auto().child(self.config.clone())),
             )
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .items_center()
+                    .justify_between()
+                    .w_full()
+                    .gap_1()
+                    .child(
+                        div()
+                            .flex_none()
+                            .flex_basis(relative(0.1))
+                            .min_w_24()
+                            .child("Max Texture Size:"),
+                    )
+                    .child(div().flex_auto().child(self.max_texture_resolution.clone())),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .items_center()
+                    .justify_between()
+                    .w_full()
+                    .gap_1()
+                    .child(
+                        div()
+                            .flex_none()
+                            .flex_basis(relative(0.1))
+                            .min_w_24()
+                            .child("Executable:"),
+                    )
+                    .child(div().flex_auto().child(self.executable.clone())),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .items_center()
+                    .justify_between()
+                    .w_full()
+                    .gap_1()
+                    .child(
+                        div()
+                            .flex_none()
+                            .flex_basis(relative(0.1))
+                            .min_w_24()
+                            .child("Launch Args:"),
+                    )
+                    .child(div().flex_auto().child(self.launch_args.clone())),
+            )
     }
 }
@@ -7,10 +7,10 @@ use status_bar::StatusBar;
 use title_bar::TitleBar;
 
 use crate::{
-    project::Project,
+    project::{Project, ProjectEvent},
     settings::Settings,
     theme::{self, colors},
-    ui::{SettingsPane, prelude::*},
+    ui::{DefBrowser, SettingsPane, prelude::*},
 };
 
 mod main_pane;
@@ -23,6 +23,7 @@ pub struct Workspace {
     title_bar: Entity<TitleBar>,
     main_pane: Entity<MainPane>,
     settings_pane: Entity<SettingsPane>,
+    def_browser: Entity<DefBrowser>,
     status_bar: Entity<StatusBar>,
 }
 
@@ -34,12 +35,23 @@ impl Workspace {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Self {
+        let settings_pane = cx.new(|cx| SettingsPane::new(settings.clone(), window, cx));
+
+        cx.subscribe(&project, |this, _, event, cx| {
+            if let ProjectEvent::InstanceChanged = event {
+                this.settings_pane
+                    .update(cx, |pane, cx| pane.reload_instance_dirs(cx));
+            }
+        })
+        .detach();
+
         Self {
             project: project.clone(),
             // settings: settings.clone(),
-            title_bar: cx.new(|_| TitleBar::new(app_version)),
+            title_bar: cx.new(|_| TitleBar::new(app_version, project.clone())),
             main_pane: cx.new(|cx| MainPane::new(project.clone(), cx)),
-            settings_pane: cx.new(|cx| SettingsPane::new(settings.clone(), window, cx)),
+            settings_pane,
+            def_browser: cx.new(|cx| DefBrowser::new(project.clone(), cx)),
             status_bar: cx.new(|_| StatusBar::new(project.clone())),
         }
     }
@@ -78,9 +90,15 @@ impl Render for Workspace {
                         .border_t_1()
                         .border_b_1()
                         .border_color(rgba(colors::BORDER))
-                        .child(match self.project.read(cx).is_settings_open() {
-                            true => self.settings_pane.clone().into_any_element(),
-                            false => self.main_pane.clone().into_any_element(),
+                        .child({
+                            let project = self.project.read(cx);
+                            if project.is_settings_open() {
+                                self.settings_pane.clone().into_any_element()
+                            } else if project.is_def_browser_open() {
+                                self.def_browser.clone().into_any_element()
+                            } else {
+                                self.main_pane.clone().into_any_element()
+                            }
                         })
                         .child(self.status_bar.clone()),
                 ),
@@ -1,6 +1,8 @@
+use gpui::Focusable;
+
 use crate::{
     project::Project,
-    ui::{ModDetails, ModList, prelude::*},
+    ui::{FocusActiveList, FocusInactiveList, ModDetails, ModList, ToggleListFocus, prelude::*},
 };
 
 pub struct MainPane {
@@ -16,19 +18,58 @@ impl MainPane {
         MainPane {
             active_list: cx.new(|cx| ModList::new_active(project.clone(), cx)),
             inactive_list: cx.new(|cx| ModList::new_inactive(project.clone(), cx)),
-            details_pane: cx.new(|_| ModDetails::new(project.clone())),
+            details_pane: cx.new(|cx| ModDetails::new(project.clone(), cx)),
+        }
+    }
+
+    fn focus_active_list(
+        &mut self,
+        _: &FocusActiveList,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        window.focus(&self.active_list.focus_handle(cx));
+    }
+
+    fn focus_inactive_list(
+        &mut self,
+        _: &FocusInactiveList,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        window.focus(&self.inactive_list.focus_handle(cx));
+    }
+
+    fn toggle_list_focus(
+        &mut self,
+        _: &ToggleListFocus,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let active_focused = self
+            .active_list
+            .read(cx)
+            .focus_handle(cx)
+            .contains_focused(window, cx);
+        match active_focused {
+            true => window.focus(&self.inactive_list.focus_handle(cx)),
+            false => window.focus(&self.active_list.focus_handle(cx)),
         }
     }
 }
 
 impl Render for MainPane {
-    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         div()
             .size_full()
             .flex_grow()
             .flex()
             .flex_row()
             .overflow_hidden()
+            .key_context("MainPane")
+            .on_action(cx.listener(Self::focus_active_list))
+            .on_action(cx.listener(Self::focus_inactive_list))
+            .on_action(cx.listener(Self::toggle_list_focus))
             .child(self.inactive_list.clone())
             .child(self.active_list.clone())
             .child(self.details_pane.clone())
@@ -1,6 +1,11 @@
-use gpui::{Pixels, px};
+use gpui::{Pixels, PromptLevel, px};
 
-use crate::{project::Project, theme::colors, ui::prelude::*};
+use crate::{
+    game::mods::{BisectOutcome, format_bytes},
+    project::Project,
+    theme::colors,
+    ui::prelude::*,
+};
 
 pub struct StatusBar {
     project: Entity<Project>,
@@ -21,11 +26,98 @@ impl StatusBar {
         // todo(windows) instead of hard coded size report the actual size to the Windows platform API
         px(32.0)
     }
+
+    /// Remove the settings files of uninstalled mods once the user confirms, which backs them
+    /// up first.
+    fn confirm_remove_orphaned_mod_settings(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let count = self.project.read(cx).orphaned_mod_settings().len();
+        let answer = window.prompt(
+            PromptLevel::Warning,
+            &format!("Remove {count} settings files of mods that are not installed?"),
+            Some("The files are backed up before they are removed."),
+            &["Remove", "Cancel"],
+            cx,
+        );
+        cx.spawn(async move |this, cx| {
+            if !matches!(answer.await, Ok(0)) {
+                return;
+            }
+            this.update(cx, |this, cx| {
+                this.project.update(cx, |project, cx| {
+                    project.remove_orphaned_mod_settings(cx);
+                    cx.notify();
+                });
+            })
+            .ok();
+        })
+        .detach();
+    }
 }
 
 impl Render for StatusBar {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let height = Self::height(window);
+        let project = self.project.read(cx);
+        let active_count = project.active_mods().len();
+        let stats = project.active_mods_stats();
+        let status = match project.is_computing_mod_stats() {
+            true => format!("{active_count} active mods, computing statistics..."),
+            false => format!(
+                "{active_count} active mods, {}, {} files, {} textures ({}), {} assemblies",
+                format_bytes(stats.disk_size),
+                stats.file_count,
+                stats.texture_count,
+                format_bytes(stats.texture_bytes),
+                stats.assembly_count,
+            ),
+        };
+        let bisect_status = project.bisect().map(|bisect| {
+            let names = |ids: Vec<&str>| {
+                ids.iter()
+                    .map(|id| match project.find_mod(id) {
+                        Some(mod_meta) => format!("'{}'", mod_meta.name),
+                        None => format!("'{id}'"),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            let suspects = bisect.suspects();
+            match (bisect.is_finished(), suspects.len()) {
+                (false, count) => format!(
+                    "Bisect step {}: testing {} of {count} suspects, did the problem happen?",
+                    bisect.step(),
+                    bisect.testing_suspects().len(),
+                ),
+                (true, 0) => "Bisect finished without suspects".to_string(),
+                (true, 1) => format!("Bisect found {}", names(suspects)),
+                (true, count) => {
+                    format!("Bisect narrowed down to {count} mods: {}", names(suspects))
+                }
+            }
+        });
+        let bisect_running = project.bisect().is_some_and(|bisect| !bisect.is_finished());
+        let game_running = project.is_game_running();
+        let version_warning = project.game_version_mismatch().map(|(installed, config)| {
+            format!(
+                "Game updated to {installed}, but mod config is from {config}. \
+                     Launch the game to update it."
+            )
+        });
+        let orphaned_settings = project.orphaned_mod_settings();
+        let orphaned_tooltip = format!(
+            "Remove {} settings files of mods that are not installed:\n{}",
+            orphaned_settings.len(),
+            orphaned_settings
+                .iter()
+                .map(|file| file.file_name())
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+        let has_orphaned_settings = !orphaned_settings.is_empty();
 
         div()
             .w_full()
@@ -38,15 +130,103 @@ impl Render for StatusBar {
             .border_t_1()
             .border_color(rgba(colors::BORDER))
             .text_sm()
-            .child("status bar is wip".to_string())
             .child(
-                IconButton::from_name("sort", IconName::Settings)
-                    .on_click(cx.listener(|this, _, _, cx| {
-                        this.project.update(cx, |project, cx| {
-                            project.toggle_settings(cx);
-                        });
-                    }))
-                    .tooltip(Tooltip::text("Toggle settings")),
+                div()
+                    .flex()
+                    .flex_row()
+                    .items_center()
+                    .gap_2()
+                    .child(
+                        div()
+                            .text_color(rgba(colors::TEXT_SECONDARY))
+                            .child(bisect_status.unwrap_or(status)),
+                    )
+                    .when_some(version_warning, |el, warning| {
+                        el.child(
+                            div()
+                                .flex()
+                                .flex_row()
+                                .items_center()
+                                .gap_1()
+                                .text_color(rgba(colors::WARNING_TEXT))
+                                .child(
+                                    Icon::from_name(IconName::Warning)
+                                        .color(rgba(colors::WARNING_TEXT)),
+                                )
+                                .child(warning),
+                        )
+                    }),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .items_center()
+                    .gap_1()
+                    .when(bisect_running, |el| {
+                        el.child(
+                            IconButton::from_name("bisect good", IconName::Good)
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.project.update(cx, |project, cx| {
+                                        project.report_bisect(BisectOutcome::Good, cx);
+                                        cx.notify();
+                                    });
+                                }))
+                                .tooltip(Tooltip::text("The problem did not happen")),
+                        )
+                        .child(
+                            IconButton::from_name("bisect bad", IconName::Bad)
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.project.update(cx, |project, cx| {
+                                        project.report_bisect(BisectOutcome::Bad, cx);
+                                        cx.notify();
+                                    });
+                                }))
+                                .tooltip(Tooltip::text("The problem happened")),
+                        )
+                    })
+                    .when(has_orphaned_settings, |el| {
+                        el.child(
+                            IconButton::from_name("remove orphaned settings", IconName::Remove)
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.confirm_remove_orphaned_mod_settings(window, cx);
+                                }))
+                                .tooltip(Tooltip::text(orphaned_tooltip)),
+                        )
+                    })
+                    .child(
+                        IconButton::from_name("launch", IconName::Launch)
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.project.update(cx, |project, cx| {
+                                    project.launch_game(cx);
+                                    cx.notify();
+                                });
+                            }))
+                            .disabled(game_running)
+                            .tooltip(Tooltip::text(match game_running {
+                                true => "RimWorld is running",
+                                false => "Launch RimWorld",
+                            })),
+                    )
+                    .child(
+                        IconButton::from_name("defs", IconName::Defs)
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.project.update(cx, |project, cx| {
+                                    project.toggle_def_browser();
+                                    cx.notify();
+                                });
+                            }))
+                            .tooltip(Tooltip::text("Toggle def browser")),
+                    )
+                    .child(
+                        IconButton::from_name("sort", IconName::Settings)
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.project.update(cx, |project, cx| {
+                                    project.toggle_settings(cx);
+                                });
+                            }))
+                            .tooltip(Tooltip::text("Toggle settings")),
+                    ),
             )
     }
 }
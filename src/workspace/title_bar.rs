@@ -1,9 +1,14 @@
-use gpui::{ClickEvent, Decorations, Pixels, WindowControlArea, px};
+use gpui::{
+    ClickEvent, Decorations, DismissEvent, Focusable, Pixels, Point, Subscription,
+    WindowControlArea, anchored, deferred, px,
+};
 use platforms::{PlatformStyle, macos, windows};
 
 use crate::{
+    project::Project,
+    settings::DEFAULT_INSTANCE,
     theme::{self, colors},
-    ui::prelude::*,
+    ui::{ContextMenu, prelude::*},
 };
 
 mod platforms;
@@ -11,20 +16,77 @@ mod platforms;
 pub struct TitleBar {
     app_version: &'static str,
     platform_style: PlatformStyle,
+    project: Entity<Project>,
+    instance_menu: Option<(Entity<ContextMenu>, Point<Pixels>, Subscription)>,
     // should_move: bool, // todo(linux)
 }
 
 impl TitleBar {
-    pub fn new(app_version: &'static str) -> Self {
+    pub fn new(app_version: &'static str, project: Entity<Project>) -> Self {
         let platform_style = PlatformStyle::platform();
 
         Self {
             app_version,
             platform_style,
+            project,
+            instance_menu: None,
             // should_move: false, // todo(linux)
         }
     }
 
+    fn deploy_instance_menu(
+        &mut self,
+        position: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let project = self.project.clone();
+        let (current, names) = project.read_with(cx, |project, cx| {
+            let settings = project.settings().read(cx);
+            let names: Vec<String> = settings
+                .instances()
+                .iter()
+                .map(|instance| instance.name.clone())
+                .collect();
+            (settings.active_instance().map(|i| i.name.clone()), names)
+        });
+
+        let menu = ContextMenu::build(window, cx, move |menu, _, _| {
+            let instances = std::iter::once(None).chain(names.into_iter().map(Some));
+            instances
+                .fold(menu, |menu, name| {
+                    let label = name.clone().unwrap_or_else(|| "Default".to_string());
+                    match name == current {
+                        true => menu.disabled_entry(format!("{label} (current)")),
+                        false => {
+                            let project = project.clone();
+                            menu.entry(label, move |_, cx| {
+                                project.update(cx, |project, cx| {
+                                    project.switch_instance(name.clone(), cx);
+                                    cx.notify();
+                                });
+                            })
+                        }
+                    }
+                })
+                .separator()
+                .entry("New instance from current setup", move |_, cx| {
+                    project.update(cx, |project, cx| {
+                        project.create_instance(cx);
+                        cx.notify();
+                    });
+                })
+        });
+
+        window.focus(&menu.focus_handle(cx));
+        let subscription = cx.subscribe(&menu, |this, _, _: &DismissEvent, cx| {
+            this.instance_menu = None;
+            cx.notify();
+        });
+        self.instance_menu = Some((menu, position, subscription));
+        cx.notify();
+    }
+
     #[cfg(not(target_os = "windows"))]
     pub fn height(window: &mut Window) -> Pixels {
         (1.75 * window.rem_size()).max(px(34.0))
@@ -58,6 +120,7 @@ impl Render for TitleBar {
         let decorations = window.window_decorations();
         let height = Self::height(window);
         let titlebar_color = self.title_bar_color(window, cx);
+        let instance = self.project.read(cx).instance_name(cx).to_string();
 
         div()
             .id("title-bar")
@@ -123,9 +186,36 @@ impl Render for TitleBar {
                             .items_center()
                             .gap_1()
                             .text_sm()
-                            .child(format!("rimru {}", self.app_version)),
+                            .child(format!("rimru {}", self.app_version))
+                            .child(
+                                div()
+                                    .id("instance-switcher")
+                                    .px_1()
+                                    .rounded_sm()
+                                    .text_color(rgba(colors::TEXT_SECONDARY))
+                                    .hover(|style| style.bg(rgba(colors::ELEMENT_HOVER)))
+                                    .child(match instance.as_str() {
+                                        DEFAULT_INSTANCE => "Default".to_string(),
+                                        name => name.to_string(),
+                                    })
+                                    .on_click(cx.listener(|this, event, window, cx| {
+                                        let ClickEvent::Mouse(mouse_event) = event else {
+                                            return;
+                                        };
+                                        cx.stop_propagation();
+                                        this.deploy_instance_menu(
+                                            mouse_event.up.position,
+                                            window,
+                                            cx,
+                                        );
+                                    }))
+                                    .tooltip(Tooltip::text("Switch instance")),
+                            ),
                     ),
             )
+            .children(self.instance_menu.as_ref().map(|(menu, position, _)| {
+                deferred(anchored().position(*position).child(menu.clone())).with_priority(1)
+            }))
             .when(!window.is_fullscreen(), |title_bar| {
                 match self.platform_style {
                     PlatformStyle::Mac => title_bar,